@@ -1,17 +1,22 @@
 use std::fmt;
+use std::ops::{Range, RangeInclusive};
 
 use crate::parser::{take, Output, Parser};
 
 pub fn any(input: &str) -> Output<char> {
-    take(|_| true)
+    take(|_: char| true)
         .parse(input)
         .map(|(out, rem)| (out.chars().next().unwrap(), rem))
         .map_err(|err| err.but_expect(Character::Any))
 }
 
 pub fn is_decimal(ch: char) -> bool {
-    match ch as u8 {
-        b'0'..=b'9' => true,
+    is_ascii_decimal(ch)
+}
+
+pub fn is_ascii_decimal(ch: char) -> bool {
+    match ch {
+        '0'..='9' => true,
         _ => false,
     }
 }
@@ -24,8 +29,12 @@ pub fn decimal(input: &str) -> Output<char> {
 }
 
 pub fn is_hexadecimal(ch: char) -> bool {
-    match ch as u8 {
-        b'0'..=b'9' | b'A'..=b'F' | b'a'..=b'f' => true,
+    is_ascii_hexadecimal(ch)
+}
+
+pub fn is_ascii_hexadecimal(ch: char) -> bool {
+    match ch {
+        '0'..='9' | 'A'..='F' | 'a'..='f' => true,
         _ => false,
     }
 }
@@ -38,8 +47,12 @@ pub fn hexadecimal(input: &str) -> Output<char> {
 }
 
 pub fn is_alphabetic(ch: char) -> bool {
-    match ch as u8 {
-        b'A'..=b'Z' | b'a'..=b'z' => true,
+    is_ascii_alphabetic(ch)
+}
+
+pub fn is_ascii_alphabetic(ch: char) -> bool {
+    match ch {
+        'A'..='Z' | 'a'..='z' => true,
         _ => false,
     }
 }
@@ -52,8 +65,12 @@ pub fn alphabetic(input: &str) -> Output<char> {
 }
 
 pub fn is_alphanumeric(ch: char) -> bool {
-    match ch as u8 {
-        b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z' => true,
+    is_ascii_alphanumeric(ch)
+}
+
+pub fn is_ascii_alphanumeric(ch: char) -> bool {
+    match ch {
+        '0'..='9' | 'A'..='Z' | 'a'..='z' => true,
         _ => false,
     }
 }
@@ -65,9 +82,31 @@ pub fn alphanumeric(input: &str) -> Output<char> {
         .map_err(|err| err.but_expect(Character::Alphanumeric))
 }
 
+pub fn is_word(ch: char) -> bool {
+    is_ascii_word(ch)
+}
+
+pub fn is_ascii_word(ch: char) -> bool {
+    match ch {
+        '0'..='9' | 'A'..='Z' | 'a'..='z' | '_' => true,
+        _ => false,
+    }
+}
+
+pub fn word(input: &str) -> Output<char> {
+    take(is_word)
+        .parse(input)
+        .map(|(out, rem)| (out.chars().next().unwrap(), rem))
+        .map_err(|err| err.but_expect(Character::Word))
+}
+
 pub fn is_lowercase(ch: char) -> bool {
-    match ch as u8 {
-        b'a'..=b'z' => true,
+    is_ascii_lowercase(ch)
+}
+
+pub fn is_ascii_lowercase(ch: char) -> bool {
+    match ch {
+        'a'..='z' => true,
         _ => false,
     }
 }
@@ -80,8 +119,12 @@ pub fn lowercase(input: &str) -> Output<char> {
 }
 
 pub fn is_uppercase(ch: char) -> bool {
-    match ch as u8 {
-        b'A'..=b'Z' => true,
+    is_ascii_uppercase(ch)
+}
+
+pub fn is_ascii_uppercase(ch: char) -> bool {
+    match ch {
+        'A'..='Z' => true,
         _ => false,
     }
 }
@@ -94,8 +137,12 @@ pub fn uppercase(input: &str) -> Output<char> {
 }
 
 pub fn is_indent(ch: char) -> bool {
-    match ch as u8 {
-        b' ' | b'\t' => true,
+    is_ascii_indent(ch)
+}
+
+pub fn is_ascii_indent(ch: char) -> bool {
+    match ch {
+        ' ' | '\t' => true,
         _ => false,
     }
 }
@@ -108,8 +155,12 @@ pub fn indent(input: &str) -> Output<char> {
 }
 
 pub fn is_linebreak(ch: char) -> bool {
-    match ch as u8 {
-        b'\n' | b'\r' | b'\x0C' => true,
+    is_ascii_linebreak(ch)
+}
+
+pub fn is_ascii_linebreak(ch: char) -> bool {
+    match ch {
+        '\n' | '\r' | '\x0C' => true,
         _ => false,
     }
 }
@@ -122,8 +173,12 @@ pub fn linebreak(input: &str) -> Output<char> {
 }
 
 pub fn is_whitespace(ch: char) -> bool {
-    match ch as u8 {
-        b' ' | b'\t' | b'\n' | b'\r' | b'\x0C' => true,
+    is_ascii_whitespace(ch)
+}
+
+pub fn is_ascii_whitespace(ch: char) -> bool {
+    match ch {
+        ' ' | '\t' | '\n' | '\r' | '\x0C' => true,
         _ => false,
     }
 }
@@ -135,6 +190,147 @@ pub fn whitespace(input: &str) -> Output<char> {
         .map_err(|err| err.but_expect(Character::Whitespace))
 }
 
+pub trait CharPredicate {
+    fn is_match(&self, ch: char) -> bool;
+
+    fn or<P>(self, other: P) -> Or<Self, P>
+    where
+        Self: Sized,
+        P: CharPredicate,
+    {
+        Or(self, other)
+    }
+
+    fn and<P>(self, other: P) -> And<Self, P>
+    where
+        Self: Sized,
+        P: CharPredicate,
+    {
+        And(self, other)
+    }
+
+    fn negate(self) -> Negate<Self>
+    where
+        Self: Sized,
+    {
+        Negate(self)
+    }
+}
+
+impl<F> CharPredicate for F
+where
+    F: Fn(char) -> bool,
+{
+    fn is_match(&self, ch: char) -> bool {
+        self(ch)
+    }
+}
+
+impl CharPredicate for fn(&char) -> bool {
+    fn is_match(&self, ch: char) -> bool {
+        self(&ch)
+    }
+}
+
+impl CharPredicate for char {
+    fn is_match(&self, ch: char) -> bool {
+        *self == ch
+    }
+}
+
+impl CharPredicate for Range<char> {
+    fn is_match(&self, ch: char) -> bool {
+        self.contains(&ch)
+    }
+}
+
+impl CharPredicate for RangeInclusive<char> {
+    fn is_match(&self, ch: char) -> bool {
+        self.contains(&ch)
+    }
+}
+
+impl CharPredicate for &str {
+    fn is_match(&self, ch: char) -> bool {
+        self.contains(ch)
+    }
+}
+
+impl CharPredicate for String {
+    fn is_match(&self, ch: char) -> bool {
+        self.contains(ch)
+    }
+}
+
+impl CharPredicate for &[char] {
+    fn is_match(&self, ch: char) -> bool {
+        self.contains(&ch)
+    }
+}
+
+impl<const N: usize> CharPredicate for [char; N] {
+    fn is_match(&self, ch: char) -> bool {
+        self.contains(&ch)
+    }
+}
+
+impl CharPredicate for Character {
+    fn is_match(&self, ch: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Decimal => is_decimal(ch),
+            Self::Hexadecimal => is_hexadecimal(ch),
+            Self::Alphabetic => is_alphabetic(ch),
+            Self::Alphanumeric => is_alphanumeric(ch),
+            Self::Word => is_word(ch),
+            Self::Lowercase => is_lowercase(ch),
+            Self::Uppercase => is_uppercase(ch),
+            Self::Indent => is_indent(ch),
+            Self::Linebreak => is_linebreak(ch),
+            Self::Whitespace => is_whitespace(ch),
+            Self::Custom(custom) => *custom == ch,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Or<A, B>(A, B);
+
+impl<A, B> CharPredicate for Or<A, B>
+where
+    A: CharPredicate,
+    B: CharPredicate,
+{
+    fn is_match(&self, ch: char) -> bool {
+        self.0.is_match(ch) || self.1.is_match(ch)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct And<A, B>(A, B);
+
+impl<A, B> CharPredicate for And<A, B>
+where
+    A: CharPredicate,
+    B: CharPredicate,
+{
+    fn is_match(&self, ch: char) -> bool {
+        self.0.is_match(ch) && self.1.is_match(ch)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Negate<P>(P);
+
+impl<P> CharPredicate for Negate<P>
+where
+    P: CharPredicate,
+{
+    fn is_match(&self, ch: char) -> bool {
+        !self.0.is_match(ch)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Character {
     Any,
@@ -142,6 +338,7 @@ pub enum Character {
     Hexadecimal,
     Alphabetic,
     Alphanumeric,
+    Word,
     Lowercase,
     Uppercase,
     Indent,
@@ -167,6 +364,7 @@ impl<'a> Parser<'a, char> for Character {
             Self::Hexadecimal => hexadecimal.parse(input),
             Self::Alphabetic => alphabetic.parse(input),
             Self::Alphanumeric => alphanumeric.parse(input),
+            Self::Word => word.parse(input),
             Self::Lowercase => lowercase.parse(input),
             Self::Uppercase => uppercase.parse(input),
             Self::Indent => indent.parse(input),
@@ -185,6 +383,7 @@ impl fmt::Display for Character {
             Self::Hexadecimal => write!(f, "hexadecimal"),
             Self::Alphabetic => write!(f, "alphabetic"),
             Self::Alphanumeric => write!(f, "alphanumeric"),
+            Self::Word => write!(f, "word"),
             Self::Lowercase => write!(f, "lowercase"),
             Self::Uppercase => write!(f, "uppercase"),
             Self::Indent => write!(f, "indent"),
@@ -639,6 +838,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_word() {
+        for ch in "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_".chars() {
+            assert_eq!(parse(&ch.to_string(), word), Ok((ch, "")));
+            assert_eq!(parse(&(ch.to_string() + "$"), word), Ok((ch, "$")));
+        }
+
+        for ch in "$- \nß".chars() {
+            assert_eq!(
+                parse(&ch.to_string(), word),
+                Err(Error::expect(Character::Word).but_found(ch))
+            );
+        }
+
+        assert_eq!(
+            parse("", word),
+            Err(Error::expect(Character::Word).but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_word_variant() {
+        for ch in "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_".chars() {
+            assert_eq!(parse(&ch.to_string(), Character::Word), Ok((ch, "")));
+            assert_eq!(
+                parse(&(ch.to_string() + "$"), Character::Word),
+                Ok((ch, "$"))
+            );
+        }
+
+        for ch in "$- \nß".chars() {
+            assert_eq!(
+                parse(&ch.to_string(), Character::Word),
+                Err(Error::expect(Character::Word).but_found(ch))
+            );
+        }
+
+        assert_eq!(
+            parse("", Character::Word),
+            Err(Error::expect(Character::Word).but_found_end())
+        );
+    }
+
     #[test]
     fn test_custom_variant() {
         assert_eq!(
@@ -653,4 +895,85 @@ mod tests {
         assert_eq!(parse("hello", Character::custom('h')), Ok(('h', "ello")));
         assert_eq!(parse("hello", Character::custom('h')), Ok(('h', "ello")));
     }
+
+    #[test]
+    fn test_non_ascii() {
+        let predicates: Vec<fn(char) -> bool> = vec![
+            is_decimal,
+            is_hexadecimal,
+            is_alphabetic,
+            is_alphanumeric,
+            is_word,
+            is_lowercase,
+            is_uppercase,
+            is_indent,
+            is_linebreak,
+            is_whitespace,
+            is_ascii_decimal,
+            is_ascii_hexadecimal,
+            is_ascii_alphabetic,
+            is_ascii_alphanumeric,
+            is_ascii_word,
+            is_ascii_lowercase,
+            is_ascii_uppercase,
+            is_ascii_indent,
+            is_ascii_linebreak,
+            is_ascii_whitespace,
+        ];
+
+        for predicate in predicates {
+            for ch in "ŉİĠĉĊčǡßℝ💣\u{0130}\u{0141}\u{0161}".chars() {
+                assert!(!predicate(ch), "{:?}", ch);
+            }
+        }
+
+        assert_eq!(
+            parse("ŉ", alphabetic),
+            Err(Error::expect(Character::Alphabetic).but_found('ŉ'))
+        );
+        assert_eq!(
+            parse("İ", decimal),
+            Err(Error::expect(Character::Decimal).but_found('İ'))
+        );
+        assert_eq!(
+            parse("Ġ", whitespace),
+            Err(Error::expect(Character::Whitespace).but_found('Ġ'))
+        );
+    }
+
+    #[test]
+    fn test_char_predicate() {
+        assert!(is_decimal.is_match('1'));
+        assert!(!is_decimal.is_match('a'));
+        assert!((char::is_ascii_digit as fn(&char) -> bool).is_match('1'));
+        assert!('x'.is_match('x'));
+        assert!(!'x'.is_match('y'));
+        assert!(('a'..'c').is_match('b'));
+        assert!(!('a'..'c').is_match('c'));
+        assert!(('a'..='c').is_match('c'));
+        assert!("+-".is_match('-'));
+        assert!(!"+-".is_match('*'));
+        assert!(String::from("+-").is_match('+'));
+        assert!(['x', 'y'].is_match('y'));
+        assert!((&['x', 'y'][..]).is_match('x'));
+        assert!(Character::Hexadecimal.is_match('F'));
+        assert!(Character::Custom('_').is_match('_'));
+        assert!(!Character::Whitespace.is_match('_'));
+    }
+
+    #[test]
+    fn test_char_predicate_compose() {
+        let ident = is_alphabetic.or('_').or('0'..='9');
+
+        assert!(ident.is_match('a'));
+        assert!(ident.is_match('_'));
+        assert!(ident.is_match('7'));
+        assert!(!ident.is_match('-'));
+
+        let consonant = is_lowercase.and("aeiou".negate());
+
+        assert!(consonant.is_match('b'));
+        assert!(!consonant.is_match('a'));
+        assert!(!consonant.is_match('B'));
+    }
 }
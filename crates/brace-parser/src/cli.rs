@@ -0,0 +1,141 @@
+use crate::parser::Output;
+use crate::shell::{tokens, Word};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Arg {
+    Long(String, Option<String>),
+    Short(char),
+    Positional(String),
+    Terminator,
+}
+
+pub fn args(input: &str) -> Output<Vec<Arg>> {
+    let (words, rem) = tokens(input)?;
+    let mut iter = words.into_iter();
+    let mut out = Vec::new();
+
+    while let Some(Word { text: word, quoted }) = iter.next() {
+        if quoted {
+            out.push(Arg::Positional(word));
+            continue;
+        }
+
+        if word == "--" {
+            out.push(Arg::Terminator);
+            out.extend(iter.by_ref().map(|word| Arg::Positional(word.text)));
+            break;
+        }
+
+        if let Some(long) = word.strip_prefix("--") {
+            let (name, value) = match long.find('=') {
+                Some(idx) => (&long[..idx], Some(long[idx + 1..].to_owned())),
+                None => (long, None),
+            };
+
+            if !name.is_empty() {
+                out.push(Arg::Long(name.to_owned(), value));
+                continue;
+            }
+        } else if let Some(short) = word.strip_prefix('-') {
+            if !short.is_empty() {
+                out.extend(short.chars().map(Arg::Short));
+                continue;
+            }
+        }
+
+        out.push(Arg::Positional(word));
+    }
+
+    Ok((out, rem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::parser::parse;
+
+    fn long(name: &str, value: Option<&str>) -> Arg {
+        Arg::Long(name.to_owned(), value.map(str::to_owned))
+    }
+
+    fn positional(value: &str) -> Arg {
+        Arg::Positional(value.to_owned())
+    }
+
+    #[test]
+    fn test_args() {
+        assert_eq!(parse("", args), Ok((vec![], "")));
+        assert_eq!(
+            parse("build --release -vq --target=x86_64 src", args),
+            Ok((
+                vec![
+                    positional("build"),
+                    long("release", None),
+                    Arg::Short('v'),
+                    Arg::Short('q'),
+                    long("target", Some("x86_64")),
+                    positional("src"),
+                ],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("--message=hello\\ world --empty= -", args),
+            Ok((
+                vec![
+                    positional("--message=hello world"),
+                    long("empty", Some("")),
+                    positional("-"),
+                ],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("--opt=a=b --=x", args),
+            Ok((vec![long("opt", Some("a=b")), positional("--=x")], ""))
+        );
+    }
+
+    #[test]
+    fn test_args_terminator() {
+        assert_eq!(
+            parse("rm -f -- -file --all --", args),
+            Ok((
+                vec![
+                    positional("rm"),
+                    Arg::Short('f'),
+                    Arg::Terminator,
+                    positional("-file"),
+                    positional("--all"),
+                    positional("--"),
+                ],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse(r#"grep -e "-foo" '--' x"#, args),
+            Ok((
+                vec![
+                    positional("grep"),
+                    Arg::Short('e'),
+                    positional("-foo"),
+                    positional("--"),
+                    positional("x"),
+                ],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse(r#"-'v' --"name"=x \-q"#, args),
+            Ok((
+                vec![positional("-v"), positional("--name=x"), positional("-q")],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("--name 'unterminated", args),
+            Err(Error::expect('\'').but_found_end().into_fail())
+        );
+    }
+}
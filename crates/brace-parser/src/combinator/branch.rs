@@ -1,8 +1,312 @@
+use std::panic::Location;
+
+use crate::combinator::map;
 use crate::error::Error;
 use crate::parser::{Output, Parser};
+use crate::position::Position;
+use crate::sequence::Sequence;
+use crate::state;
 
+#[track_caller]
 pub fn branch<'a, O>(branch: impl Branch<'a, O>) -> impl Parser<'a, O> {
-    move |input| branch.parse_branch(input)
+    let site = Location::caller();
+
+    move |input| {
+        let flags = state::flags();
+
+        if !flags.branch_hooks() {
+            return branch.parse_branch(input);
+        }
+
+        match flags.detecting_ambiguity() {
+            true => parse_checked(&branch, input),
+            false => parse_counted(&branch, site, input),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ArmStats {
+    pub tried: usize,
+    pub discarded: usize,
+    pub rescanned: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BranchStats {
+    pub position: Position,
+    pub arms: Vec<ArmStats>,
+}
+
+pub fn parse_backtracking<'a, P, O>(input: &'a str, parser: P) -> (Output<'a, O>, Vec<BranchStats>)
+where
+    P: Parser<'a, O>,
+{
+    let (out, found) = state::with_backtrack_stats(|| parser.parse(input));
+    let start = input.as_ptr() as usize;
+    let found = found
+        .into_iter()
+        .filter_map(|(_, pos, arms)| {
+            let offset = pos
+                .checked_sub(start)
+                .filter(|offset| *offset <= input.len())?;
+
+            Some(BranchStats {
+                position: Position::new(input, offset),
+                arms: arms
+                    .into_iter()
+                    .map(|[tried, discarded, rescanned]| ArmStats {
+                        tried,
+                        discarded,
+                        rescanned,
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+
+    (out, found)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ambiguity {
+    pub position: Position,
+    pub matches: Vec<(usize, usize)>,
+}
+
+pub fn parse_ambiguous<'a, P, O>(input: &'a str, parser: P) -> (Output<'a, O>, Vec<Ambiguity>)
+where
+    P: Parser<'a, O>,
+{
+    let (out, found) = state::with_ambiguity_detection(|| parser.parse(input));
+    let start = input.as_ptr() as usize;
+    let found = found
+        .into_iter()
+        .filter_map(|(pos, matches)| {
+            let offset = pos
+                .checked_sub(start)
+                .filter(|offset| *offset <= input.len())?;
+
+            Some(Ambiguity {
+                position: Position::new(input, offset),
+                matches,
+            })
+        })
+        .collect();
+
+    (out, found)
+}
+
+pub fn parse_all_alternatives<'a, O>(
+    input: &'a str,
+    branch: impl Branch<'a, O>,
+) -> Result<Vec<(O, &'a str)>, Error> {
+    let mut out = Vec::new();
+    let mut last: Option<Error> = None;
+
+    for idx in 0..branch.arms() {
+        match branch.parse_arm(idx, input) {
+            Ok(res) => out.push(res),
+            Err(_) if matches!(last, Some(Error::Fail(_))) => {}
+            Err(err) => last = Some(err),
+        }
+    }
+
+    match out.is_empty() {
+        true => Err(last.unwrap_or_else(Error::invalid)),
+        false => Ok(out),
+    }
+}
+
+fn parse_counted<'a, O>(
+    branch: &impl Branch<'a, O>,
+    site: &'static Location<'static>,
+    input: &'a str,
+) -> Output<'a, O> {
+    let arms = branch.arms();
+    let mut out = Err(Error::invalid());
+
+    for idx in 0..arms {
+        let outer = state::start_arm(input);
+
+        out = branch.parse_arm(idx, input);
+
+        let discarded = matches!(out, Err(Error::Pass(_)));
+
+        state::finish_arm(site, input, (idx, arms), outer, discarded);
+
+        if !discarded {
+            break;
+        }
+    }
+
+    out
+}
+
+fn parse_checked<'a, O>(branch: &impl Branch<'a, O>, input: &'a str) -> Output<'a, O> {
+    let mut out = Err(Error::invalid());
+    let mut matches = Vec::new();
+
+    for idx in 0..branch.arms() {
+        let res = match out.is_ok() {
+            true => state::without_ambiguity_detection(|| branch.parse_arm(idx, input)),
+            false => branch.parse_arm(idx, input),
+        };
+
+        match res {
+            Ok((item, rem)) => {
+                matches.push((idx, input.len() - rem.len()));
+
+                if out.is_err() {
+                    out = Ok((item, rem));
+                }
+            }
+            Err(Error::Fail(inner)) if out.is_err() => return Err(Error::Fail(inner)),
+            Err(err) if out.is_err() => out = Err(err),
+            Err(_) => {}
+        }
+    }
+
+    if matches.iter().any(|(_, len)| *len != matches[0].1) {
+        state::report_ambiguity(input, matches);
+    }
+
+    out
+}
+
+#[track_caller]
+pub fn branch_iter<'a, O, P, I>(parsers: I) -> impl Parser<'a, O>
+where
+    I: IntoIterator<Item = P>,
+    P: Parser<'a, O>,
+{
+    branch(parsers.into_iter().collect::<Vec<_>>())
+}
+
+pub fn keywords<'a, I, T>(keywords: I) -> impl Parser<'a, &'a str>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<String>,
+{
+    let keywords = keywords
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<String>>();
+
+    move |input: &'a str| {
+        let len = input
+            .char_indices()
+            .find(|(_, ch)| !ch.is_alphanumeric() && *ch != '_')
+            .map_or(input.len(), |(idx, _)| idx);
+        let (word, rem) = input.split_at(len);
+        let err = Error::expect(Sequence::custom("keyword"));
+
+        if keywords.iter().any(|keyword| keyword == word) {
+            return Ok((word, rem));
+        }
+
+        match input.chars().next() {
+            None => Err(err.but_found_end()),
+            Some(ch) if word.is_empty() => Err(err.but_found(ch)),
+            Some(_) => Err(suggestions(word, &keywords).into_iter().fold(
+                err.but_found(Sequence::custom(word)),
+                Error::with_suggestion,
+            )),
+        }
+    }
+}
+
+pub fn suggestions<I, T>(word: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    let limit = std::cmp::max(1, word.chars().count() / 3);
+    let mut found = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let candidate = candidate.as_ref();
+            let distance = edit_distance(word, candidate);
+
+            match distance <= limit && distance > 0 {
+                true => Some((distance, candidate.to_owned())),
+                false => None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    found.sort_by_key(|(distance, _)| *distance);
+    found.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut prev2 = prev.clone();
+
+    for idx in 1..=a.len() {
+        let mut row = vec![idx; b.len() + 1];
+
+        for jdx in 1..=b.len() {
+            let cost = match a[idx - 1] == b[jdx - 1] {
+                true => 0,
+                false => 1,
+            };
+
+            row[jdx] = (prev[jdx] + 1)
+                .min(row[jdx - 1] + 1)
+                .min(prev[jdx - 1] + cost);
+
+            if idx > 1 && jdx > 1 && a[idx - 1] == b[jdx - 2] && a[idx - 2] == b[jdx - 1] {
+                row[jdx] = row[jdx].min(prev2[jdx - 2] + 1);
+            }
+        }
+
+        prev2 = std::mem::replace(&mut prev, row);
+    }
+
+    prev[b.len()]
+}
+
+pub fn branch_indexed<'a, O>(branch: impl Branch<'a, O>) -> impl Parser<'a, (usize, O)> {
+    move |input| {
+        let mut out = Err(Error::invalid());
+
+        for idx in 0..branch.arms() {
+            match branch.parse_arm(idx, input) {
+                Ok((item, rem)) => return Ok(((idx, item), rem)),
+                Err(Error::Fail(inner)) => return Err(Error::Fail(inner)),
+                Err(err) => out = Err(err),
+            }
+        }
+
+        out
+    }
+}
+
+pub fn branch_tagged<'a, O, T>(branch: impl TaggedBranch<'a, T, O>) -> impl Parser<'a, (T, O)> {
+    move |input| branch.parse_tagged(input)
+}
+
+pub fn longest<'a, O>(branch: impl Branch<'a, O>) -> impl Parser<'a, O> {
+    move |input| {
+        let mut out: Output<'a, O> = Err(Error::invalid());
+
+        for idx in 0..branch.arms() {
+            match branch.parse_arm(idx, input) {
+                Ok((item, rem)) => match out {
+                    Ok((_, best)) if best.len() <= rem.len() => {}
+                    _ => out = Ok((item, rem)),
+                },
+                Err(Error::Fail(inner)) => return Err(Error::Fail(inner)),
+                Err(err) if out.is_err() => out = Err(err),
+                Err(_) => {}
+            }
+        }
+
+        out
+    }
 }
 
 pub fn either<'a, O>(a: impl Parser<'a, O>, b: impl Parser<'a, O>) -> impl Parser<'a, O> {
@@ -14,6 +318,114 @@ pub fn either<'a, O>(a: impl Parser<'a, O>, b: impl Parser<'a, O>) -> impl Parse
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Either2<A, B> {
+    A(A),
+    B(B),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Either3<A, B, C> {
+    A(A),
+    B(B),
+    C(C),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Either4<A, B, C, D> {
+    A(A),
+    B(B),
+    C(C),
+    D(D),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Either5<A, B, C, D, E> {
+    A(A),
+    B(B),
+    C(C),
+    D(D),
+    E(E),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Either6<A, B, C, D, E, F> {
+    A(A),
+    B(B),
+    C(C),
+    D(D),
+    E(E),
+    F(F),
+}
+
+#[track_caller]
+pub fn either2<'a, A, B>(
+    a: impl Parser<'a, A>,
+    b: impl Parser<'a, B>,
+) -> impl Parser<'a, Either2<A, B>> {
+    branch((map(a, Either2::A), map(b, Either2::B)))
+}
+
+#[track_caller]
+pub fn either3<'a, A, B, C>(
+    a: impl Parser<'a, A>,
+    b: impl Parser<'a, B>,
+    c: impl Parser<'a, C>,
+) -> impl Parser<'a, Either3<A, B, C>> {
+    branch((map(a, Either3::A), map(b, Either3::B), map(c, Either3::C)))
+}
+
+#[track_caller]
+pub fn either4<'a, A, B, C, D>(
+    a: impl Parser<'a, A>,
+    b: impl Parser<'a, B>,
+    c: impl Parser<'a, C>,
+    d: impl Parser<'a, D>,
+) -> impl Parser<'a, Either4<A, B, C, D>> {
+    branch((
+        map(a, Either4::A),
+        map(b, Either4::B),
+        map(c, Either4::C),
+        map(d, Either4::D),
+    ))
+}
+
+#[track_caller]
+pub fn either5<'a, A, B, C, D, E>(
+    a: impl Parser<'a, A>,
+    b: impl Parser<'a, B>,
+    c: impl Parser<'a, C>,
+    d: impl Parser<'a, D>,
+    e: impl Parser<'a, E>,
+) -> impl Parser<'a, Either5<A, B, C, D, E>> {
+    branch((
+        map(a, Either5::A),
+        map(b, Either5::B),
+        map(c, Either5::C),
+        map(d, Either5::D),
+        map(e, Either5::E),
+    ))
+}
+
+#[track_caller]
+pub fn either6<'a, A, B, C, D, E, F>(
+    a: impl Parser<'a, A>,
+    b: impl Parser<'a, B>,
+    c: impl Parser<'a, C>,
+    d: impl Parser<'a, D>,
+    e: impl Parser<'a, E>,
+    f: impl Parser<'a, F>,
+) -> impl Parser<'a, Either6<A, B, C, D, E, F>> {
+    branch((
+        map(a, Either6::A),
+        map(b, Either6::B),
+        map(c, Either6::C),
+        map(d, Either6::D),
+        map(e, Either6::E),
+        map(f, Either6::F),
+    ))
+}
+
 pub fn optional<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, Option<O>> {
     move |input| match parser.parse(input) {
         Ok((out, rem)) => Ok((Some(out), rem)),
@@ -24,8 +436,48 @@ pub fn optional<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, Option<O>>
     }
 }
 
+pub fn optional_if<'a, O>(
+    required: bool,
+    parser: impl Parser<'a, O>,
+) -> impl Parser<'a, Option<O>> {
+    move |input| match parser.parse(input) {
+        Ok((out, rem)) => Ok((Some(out), rem)),
+        Err(Error::Pass(_)) if !required => Ok((None, input)),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn opt_or<'a, O>(parser: impl Parser<'a, O>, default: O) -> impl Parser<'a, O>
+where
+    O: Clone,
+{
+    opt_or_else(parser, move || default.clone())
+}
+
+pub fn opt_or_else<'a, O, F>(parser: impl Parser<'a, O>, default: F) -> impl Parser<'a, O>
+where
+    F: Fn() -> O,
+{
+    move |input| match parser.parse(input) {
+        Ok(res) => Ok(res),
+        Err(Error::Pass(_)) => Ok((default(), input)),
+        Err(err) => Err(err),
+    }
+}
+
 pub trait Branch<'a, O> {
     fn parse_branch(&self, input: &'a str) -> Output<'a, O>;
+
+    fn arms(&self) -> usize {
+        1
+    }
+
+    fn parse_arm(&self, idx: usize, input: &'a str) -> Output<'a, O> {
+        match idx {
+            0 => self.parse_branch(input),
+            _ => Err(Error::invalid()),
+        }
+    }
 }
 
 impl<'a> Branch<'a, ()> for () {
@@ -39,18 +491,191 @@ where
     T: Parser<'a, O>,
 {
     fn parse_branch(&self, input: &'a str) -> Output<'a, O> {
-        let mut out = Err(Error::invalid());
+        parse_slice(self, input)
+    }
 
-        for parser in self {
-            match parser.parse(input) {
-                Ok(res) => return Ok(res),
-                Err(Error::Fail(inner)) => return Err(Error::Fail(inner)),
-                Err(Error::Pass(inner)) => out = Err(Error::Pass(inner)),
+    fn arms(&self) -> usize {
+        self.len()
+    }
+
+    fn parse_arm(&self, idx: usize, input: &'a str) -> Output<'a, O> {
+        match self.get(idx) {
+            Some(parser) => parser.parse(input),
+            None => Err(Error::invalid()),
+        }
+    }
+}
+
+impl<'a, T, O> Branch<'a, O> for &[T]
+where
+    T: Parser<'a, O>,
+{
+    fn parse_branch(&self, input: &'a str) -> Output<'a, O> {
+        parse_slice(self, input)
+    }
+
+    fn arms(&self) -> usize {
+        self.len()
+    }
+
+    fn parse_arm(&self, idx: usize, input: &'a str) -> Output<'a, O> {
+        match self.get(idx) {
+            Some(parser) => parser.parse(input),
+            None => Err(Error::invalid()),
+        }
+    }
+}
+
+impl<'a, T, O, const N: usize> Branch<'a, O> for [T; N]
+where
+    T: Parser<'a, O>,
+{
+    fn parse_branch(&self, input: &'a str) -> Output<'a, O> {
+        parse_slice(self, input)
+    }
+
+    fn arms(&self) -> usize {
+        self.len()
+    }
+
+    fn parse_arm(&self, idx: usize, input: &'a str) -> Output<'a, O> {
+        match self.get(idx) {
+            Some(parser) => parser.parse(input),
+            None => Err(Error::invalid()),
+        }
+    }
+}
+
+pub trait TaggedBranch<'a, T, O> {
+    fn parse_tagged(&self, input: &'a str) -> Output<'a, (T, O)>;
+}
+
+impl<'a, T, P, O> TaggedBranch<'a, T, O> for Vec<(T, P)>
+where
+    T: Clone,
+    P: Parser<'a, O>,
+{
+    fn parse_tagged(&self, input: &'a str) -> Output<'a, (T, O)> {
+        parse_tagged_slice(self, input)
+    }
+}
+
+impl<'a, T, P, O> TaggedBranch<'a, T, O> for &[(T, P)]
+where
+    T: Clone,
+    P: Parser<'a, O>,
+{
+    fn parse_tagged(&self, input: &'a str) -> Output<'a, (T, O)> {
+        parse_tagged_slice(self, input)
+    }
+}
+
+impl<'a, T, P, O, const N: usize> TaggedBranch<'a, T, O> for [(T, P); N]
+where
+    T: Clone,
+    P: Parser<'a, O>,
+{
+    fn parse_tagged(&self, input: &'a str) -> Output<'a, (T, O)> {
+        parse_tagged_slice(self, input)
+    }
+}
+
+fn parse_tagged_slice<'a, T, P, O>(arms: &[(T, P)], input: &'a str) -> Output<'a, (T, O)>
+where
+    T: Clone,
+    P: Parser<'a, O>,
+{
+    let mut out = Err(Error::invalid());
+
+    for (tag, parser) in arms {
+        match parser.parse(input) {
+            Ok((item, rem)) => return Ok(((tag.clone(), item), rem)),
+            Err(Error::Fail(inner)) => return Err(Error::Fail(inner)),
+            Err(Error::Pass(inner)) => out = Err(Error::Pass(inner)),
+        }
+    }
+
+    out
+}
+
+macro_rules! impl_tagged_branch {
+    ($(($a:tt, $b:ident),)+) => {
+        impl_tagged_branch!(@iter $(($a, $b),)+;);
+    };
+
+    (@iter ($a:tt, $b:ident),; $(($c:tt, $d:ident),)*) => {
+        impl_tagged_branch!(@impl $(($c, $d),)* ($a, $b),);
+    };
+
+    (@iter ($a:tt, $b:ident), $(($c:tt, $d:ident),)+; $(($e:tt, $f:ident),)*) => {
+        impl_tagged_branch!(@impl $(($e, $f),)* ($a, $b),);
+        impl_tagged_branch!(@iter $(($c, $d),)*; $(($e, $f),)* ($a, $b),);
+    };
+
+    (@impl $(($idx:tt, $P:ident),)+) => {
+        impl<'a, Tag, O, $($P,)+> TaggedBranch<'a, Tag, O> for ($((Tag, $P),)+)
+        where
+            Tag: Clone,
+            $($P: Parser<'a, O>,)+
+        {
+            fn parse_tagged(&self, input: &'a str) -> Output<'a, (Tag, O)> {
+                impl_tagged_branch!(@inner self; input; $($idx,)+)
             }
         }
+    };
 
-        out
+    (@inner $self:expr; $input:expr; $i:tt,) => {
+        $self.$i.1.parse($input).map(|(item, rem)| (($self.$i.0.clone(), item), rem))
+    };
+
+    (@inner $self:expr; $input:expr; $i:tt, $($idx:tt,)+) => {
+        match $self.$i.1.parse($input) {
+            Ok((item, rem)) => Ok((($self.$i.0.clone(), item), rem)),
+            Err(Error::Fail(inner)) => Err(Error::Fail(inner)),
+            Err(_) => impl_tagged_branch!(@inner $self; $input; $($idx,)+),
+        }
+    };
+}
+
+impl_tagged_branch! {
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F),
+    (6, G),
+    (7, H),
+    (8, I),
+    (9, J),
+    (10, K),
+    (11, L),
+    (12, M),
+    (13, N),
+    (14, P),
+    (15, Q),
+    (16, R),
+    (17, S),
+    (18, T),
+    (19, U),
+    (20, V),
+}
+
+fn parse_slice<'a, T, O>(parsers: &[T], input: &'a str) -> Output<'a, O>
+where
+    T: Parser<'a, O>,
+{
+    let mut out = Err(Error::invalid());
+
+    for parser in parsers {
+        match parser.parse(input) {
+            Ok(res) => return Ok(res),
+            Err(Error::Fail(inner)) => return Err(Error::Fail(inner)),
+            Err(Error::Pass(inner)) => out = Err(Error::Pass(inner)),
+        }
     }
+
+    out
 }
 
 macro_rules! impl_branch {
@@ -75,11 +700,22 @@ macro_rules! impl_branch {
             fn parse_branch(&self, input: &'a str) -> Output<'a, O> {
                 impl_branch!(@start self; input; $($idx,)+)
             }
+
+            fn arms(&self) -> usize {
+                [$($idx,)+].len()
+            }
+
+            fn parse_arm(&self, idx: usize, input: &'a str) -> Output<'a, O> {
+                match idx {
+                    $($idx => self.$idx.parse(input),)+
+                    _ => Err(Error::invalid()),
+                }
+            }
         }
     };
 
     (@start $self:expr; $input:expr; $($idx:tt,)+) => {
-        impl_branch!(@inner $self; $input; $($idx,)+);
+        impl_branch!(@inner $self; $input; $($idx,)+)
     };
 
     (@inner $self:expr; $input:expr; $i:tt,) => {
@@ -108,13 +744,24 @@ impl_branch! {
     (9, J),
     (10, K),
     (11, L),
+    (12, M),
+    (13, N),
+    (14, P),
+    (15, Q),
+    (16, R),
+    (17, S),
+    (18, T),
+    (19, U),
+    (20, V),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::combinator::series::list;
     use crate::error::Error;
     use crate::parser::parse;
+    use crate::sequence::decimal;
 
     fn pass(_: &str) -> Output<&str> {
         Err(Error::expect('!'))
@@ -206,4 +853,407 @@ mod tests {
         assert_eq!(parse("", optional(pass)), Ok((None, "")));
         assert_eq!(parse("", optional(fail)), Err(Error::invalid()));
     }
+
+    #[test]
+    fn test_branch_arity() {
+        let letters = || {
+            branch((
+                'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+                'q', 'r', 's', 't', 'u',
+            ))
+        };
+
+        assert_eq!(parse("a", letters()), Ok(('a', "")));
+        assert_eq!(parse("u", letters()), Ok(('u', "")));
+        assert_eq!(
+            parse("v", letters()),
+            Err(Error::expect('u').but_found('v'))
+        );
+    }
+
+    #[test]
+    fn test_branch_array() {
+        static KEYWORDS: [&str; 3] = ["let", "fn", "if"];
+
+        assert_eq!(parse("fn main", branch(KEYWORDS)), Ok(("fn", " main")));
+        assert_eq!(parse("if x", branch(&KEYWORDS[..])), Ok(("if", " x")));
+        assert_eq!(
+            parse("iff", branch(&KEYWORDS[..2])),
+            Err(Error::expect('f').but_found('i'))
+        );
+        assert_eq!(parse("a", branch([] as [&str; 0])), Err(Error::invalid()));
+        assert_eq!(
+            parse("a", branch([pass, fail, pass])),
+            Err(Error::invalid())
+        );
+    }
+
+    #[test]
+    fn test_branch_iter() {
+        let config = "let fn if";
+        let keywords = || branch_iter(config.split(' ').map(str::to_owned));
+
+        assert_eq!(parse("fn main", keywords()), Ok(("fn", " main")));
+        assert_eq!(parse("if", keywords()), Ok(("if", "")));
+        assert_eq!(
+            parse("else", keywords()),
+            Err(Error::expect('i').but_found('e'))
+        );
+        assert_eq!(
+            parse("a", branch_iter(Vec::<&str>::new())),
+            Err(Error::invalid())
+        );
+        assert_eq!(
+            parse("a", branch_iter(vec![pass, fail, pass])),
+            Err(Error::invalid())
+        );
+        assert_eq!(
+            parse(
+                "3",
+                branch_iter((1..=3).map(|n| std::char::from_digit(n, 10).unwrap()))
+            ),
+            Ok(('3', ""))
+        );
+    }
+
+    #[test]
+    fn test_longest() {
+        let operators = || longest((">", "=", ">=", ">>="));
+
+        assert_eq!(parse(">= 1", operators()), Ok((">=", " 1")));
+        assert_eq!(parse(">> 1", operators()), Ok((">", "> 1")));
+        assert_eq!(parse(">>= 1", operators()), Ok((">>=", " 1")));
+        assert_eq!(parse("= 1", operators()), Ok(("=", " 1")));
+        assert_eq!(
+            parse("< 1", operators()),
+            Err(Error::expect('>').but_found('<'))
+        );
+        assert_eq!(parse("ab", longest(vec!["a", "ab", "b"])), Ok(("ab", "")));
+        assert_eq!(parse("a", longest(["a", "a"])), Ok(("a", "")));
+        assert_eq!(parse("a", longest(vec![pass, fail])), Err(Error::invalid()));
+        assert_eq!(
+            parse("a", longest(Vec::<&str>::new())),
+            Err(Error::invalid())
+        );
+        assert_eq!(parse("a", longest(())), Ok(((), "a")));
+        assert_eq!(
+            parse("c", longest(("ab", "x"))),
+            parse("c", branch(("ab", "x")))
+        );
+        assert_eq!(
+            parse("c", longest(["a", "b"])),
+            Err(Error::expect('b').but_found('c'))
+        );
+    }
+
+    #[test]
+    fn test_branch_indexed() {
+        assert_eq!(
+            parse("fn main", branch_indexed(("let", "fn", "if"))),
+            Ok(((1, "fn"), " main"))
+        );
+        assert_eq!(
+            parse("if", branch_indexed(vec!["let", "fn", "if"])),
+            Ok(((2, "if"), ""))
+        );
+        assert_eq!(
+            parse("else", branch_indexed(["let", "fn", "if"])),
+            Err(Error::expect('i').but_found('e'))
+        );
+        assert_eq!(
+            parse("a", branch_indexed(vec![pass, fail, pass])),
+            Err(Error::invalid())
+        );
+    }
+
+    #[test]
+    fn test_branch_default_arms() {
+        struct Digits;
+
+        impl<'a> Branch<'a, &'a str> for Digits {
+            fn parse_branch(&self, input: &'a str) -> Output<'a, &'a str> {
+                decimal(input)
+            }
+        }
+
+        assert_eq!(Digits.arms(), 1);
+        assert_eq!(parse("12a", branch(Digits)), Ok(("12", "a")));
+        assert_eq!(parse("12a", branch_indexed(Digits)), Ok(((0, "12"), "a")));
+        assert_eq!(parse("12a", longest(Digits)), Ok(("12", "a")));
+        assert_eq!(Digits.parse_arm(1, "12"), Err(Error::invalid()));
+    }
+
+    #[test]
+    fn test_branch_tagged() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Keyword {
+            Let,
+            Fn,
+        }
+
+        assert_eq!(
+            parse(
+                "fn",
+                branch_tagged(((Keyword::Let, "let"), (Keyword::Fn, "fn")))
+            ),
+            Ok(((Keyword::Fn, "fn"), ""))
+        );
+        assert_eq!(
+            parse(
+                "let",
+                branch_tagged(((Keyword::Let, "let"), (Keyword::Fn, decimal)))
+            ),
+            Ok(((Keyword::Let, "let"), ""))
+        );
+        assert_eq!(
+            parse(
+                "if",
+                branch_tagged([(Keyword::Let, "let"), (Keyword::Fn, "fn")])
+            ),
+            Err(Error::expect('f').but_found('i'))
+        );
+        assert_eq!(
+            parse("fn", branch_tagged(vec![(Keyword::Fn, "fn")])),
+            Ok(((Keyword::Fn, "fn"), ""))
+        );
+    }
+
+    #[test]
+    fn test_either_n() {
+        use crate::sequence::{alphabetic, decimal};
+
+        let token = || either3(decimal, alphabetic, ('(', ')'));
+
+        assert_eq!(parse("12a", token()), Ok((Either3::A("12"), "a")));
+        assert_eq!(parse("ab1", token()), Ok((Either3::B("ab"), "1")));
+        assert_eq!(parse("()", token()), Ok((Either3::C(('(', ')')), "")));
+        assert_eq!(parse("(]", token()), Err(Error::expect(')').but_found(']')));
+        assert_eq!(parse("x", either2('x', "xy")), Ok((Either2::A('x'), "")));
+        assert_eq!(parse("a", either2(fail, 'a')), Err(Error::invalid()));
+        assert_eq!(
+            parse("f", either6('a', "b", 'c', "d", 'e', "f")),
+            Ok((Either6::F("f"), ""))
+        );
+    }
+
+    #[test]
+    fn test_opt_or() {
+        use crate::combinator::fail as commit;
+        use crate::combinator::series::{leading, list};
+        use crate::sequence::{decimal, Sequence};
+
+        assert_eq!(parse("12", opt_or(decimal, "0")), Ok(("12", "")));
+        assert_eq!(parse("x", opt_or(decimal, "0")), Ok(("0", "x")));
+        assert_eq!(
+            parse("=x", opt_or(leading('=', commit(decimal)), "0")),
+            Err(Error::expect(Sequence::Decimal).but_found('x').into_fail())
+        );
+        assert_eq!(
+            parse("1,2", opt_or_else(list(decimal, ','), Vec::new)),
+            Ok((vec!["1", "2"], ""))
+        );
+        assert_eq!(
+            parse("x", opt_or_else(list(decimal, ','), Vec::new)),
+            Ok((vec![], "x"))
+        );
+    }
+
+    #[test]
+    fn test_optional_if() {
+        let strict = true;
+
+        assert_eq!(
+            parse("a;", optional_if(strict, ';')),
+            Err(Error::expect(';').but_found('a'))
+        );
+        assert_eq!(parse(";a", optional_if(strict, ';')), Ok((Some(';'), "a")));
+        assert_eq!(parse("a;", optional_if(!strict, ';')), Ok((None, "a;")));
+        assert_eq!(parse(";a", optional_if(!strict, ';')), Ok((Some(';'), "a")));
+        assert_eq!(parse("a", optional_if(false, fail)), Err(Error::invalid()));
+    }
+
+    #[test]
+    fn test_parse_ambiguous() {
+        use crate::combinator::series::list;
+        use crate::sequence::{alphabetic, alphanumeric};
+
+        let ident = || branch((alphabetic, alphanumeric, "a"));
+        let (out, found) = parse_ambiguous("ab a1", list(ident(), ' '));
+
+        assert_eq!(out, Ok((vec!["ab", "a"], "1")));
+        assert_eq!(
+            found,
+            vec![
+                Ambiguity {
+                    position: Position::new("ab a1", 0),
+                    matches: vec![(0, 2), (1, 2), (2, 1)],
+                },
+                Ambiguity {
+                    position: Position::new("ab a1", 3),
+                    matches: vec![(0, 1), (1, 2), (2, 1)],
+                },
+            ]
+        );
+
+        let (out, found) = parse_ambiguous("abc", branch(("abc", alphabetic)));
+
+        assert_eq!(out, Ok(("abc", "")));
+        assert_eq!(found, vec![]);
+
+        let nested = || branch((alphabetic, "a"));
+        let (out, found) = parse_ambiguous("ab", branch(("a", nested())));
+
+        assert_eq!(out, Ok(("a", "b")));
+        assert_eq!(
+            found,
+            vec![Ambiguity {
+                position: Position::new("ab", 0),
+                matches: vec![(0, 1), (1, 2)],
+            }]
+        );
+        assert_eq!(parse("ab1", ident()), Ok(("ab", "1")));
+        assert_eq!(
+            parse_ambiguous("1", branch((pass, fail))).0,
+            Err(Error::invalid())
+        );
+    }
+
+    #[test]
+    fn test_parse_all_alternatives() {
+        use crate::combinator::series::pair;
+        use crate::sequence::{alphabetic, decimal};
+
+        assert_eq!(
+            parse_all_alternatives("ab1", (alphabetic, "a", pass, "ab1")),
+            Ok(vec![("ab", "1"), ("a", "b1"), ("ab1", "")])
+        );
+        assert_eq!(
+            parse_all_alternatives("x", ("a", fail, "b")),
+            Err(Error::invalid())
+        );
+        assert_eq!(
+            parse_all_alternatives("x", ("a", "b")),
+            Err(Error::expect('b').but_found('x'))
+        );
+        assert_eq!(
+            parse_all_alternatives("12", (pair(decimal, decimal), pair("1", decimal))),
+            Ok(vec![(("1", "2"), "")])
+        );
+    }
+
+    #[test]
+    fn test_keywords() {
+        let keyword = || keywords(vec!["function", "for", "return"]);
+
+        assert_eq!(parse("for x", keyword()), Ok(("for", " x")));
+        assert_eq!(parse("function(", keyword()), Ok(("function", "(")));
+        assert_eq!(
+            parse("fucntion main", keyword()),
+            Err(Error::expect(Sequence::custom("keyword"))
+                .but_found(Sequence::custom("fucntion"))
+                .with_suggestion("function"))
+        );
+        assert_eq!(
+            parse("fro", keyword()),
+            Err(Error::expect(Sequence::custom("keyword"))
+                .but_found(Sequence::custom("fro"))
+                .with_suggestion("for"))
+        );
+        assert_eq!(
+            parse("while", keyword()),
+            Err(Error::expect(Sequence::custom("keyword")).but_found(Sequence::custom("while")))
+        );
+        assert_eq!(
+            parse("(", keyword()),
+            Err(Error::expect(Sequence::custom("keyword")).but_found('('))
+        );
+        assert_eq!(
+            parse("", keyword()),
+            Err(Error::expect(Sequence::custom("keyword")).but_found_end())
+        );
+        assert!(parse("retrun", keyword())
+            .unwrap_err()
+            .to_string()
+            .ends_with("\nDid you mean `return`?"));
+    }
+
+    #[test]
+    fn test_suggestions() {
+        assert_eq!(
+            suggestions("fucntion", ["function", "format"]),
+            vec!["function"]
+        );
+        assert_eq!(
+            suggestions("cat", ["bat", "car", "dog"]),
+            vec!["bat", "car"]
+        );
+        assert_eq!(suggestions("cat", ["cat"]), Vec::<String>::new());
+        assert_eq!(
+            Error::expect("x")
+                .with_suggestion("a")
+                .with_suggestion("b")
+                .with_suggestion("c")
+                .to_string(),
+            "Error:\nExpected sequence: \"x\"\nDid you mean `a`, `b` or `c`?"
+        );
+    }
+
+    #[test]
+    fn test_parse_backtracking() {
+        let keyword = || branch(("function", "for", "fn"));
+        let (out, stats) = parse_backtracking("fn fn", list(keyword(), ' '));
+
+        assert_eq!(out, Ok((vec!["fn", "fn"], "")));
+        assert_eq!(
+            stats,
+            vec![BranchStats {
+                position: Position::new("fn fn", 0),
+                arms: vec![
+                    ArmStats {
+                        tried: 2,
+                        discarded: 2,
+                        rescanned: 0
+                    },
+                    ArmStats {
+                        tried: 2,
+                        discarded: 2,
+                        rescanned: 0
+                    },
+                    ArmStats {
+                        tried: 2,
+                        discarded: 0,
+                        rescanned: 0
+                    },
+                ],
+            }]
+        );
+
+        let pair = || branch(((decimal, ','), (decimal, ';')));
+        let (out, stats) = parse_backtracking("123;", pair());
+
+        assert_eq!(out, Ok((("123", ';'), "")));
+        assert_eq!(stats[0].arms[0].discarded, 1);
+        assert_eq!(stats[0].arms[0].rescanned, 3);
+        assert_eq!(stats[0].arms[1].discarded, 0);
+        assert_eq!(parse("123;", pair()), Ok((("123", ';'), "")));
+    }
+
+    #[test]
+    fn test_parse_backtracking_sites() {
+        fn small(input: &str) -> Output<'_, &str> {
+            branch(("a", "b")).parse(input)
+        }
+
+        fn large(input: &str) -> Output<'_, &str> {
+            branch(("c", "d", "e", "f", "g", "h", "i", "j")).parse(input)
+        }
+
+        let (out, stats) = parse_backtracking("ajb", list(branch((small, large)), ()));
+
+        assert_eq!(out, Ok((vec!["a", "j", "b"], "")));
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[1].arms.len(), 2);
+        assert_eq!(stats[2].arms.len(), 8);
+        assert_eq!(stats[2].arms[7].tried, 2);
+    }
 }
@@ -0,0 +1,168 @@
+use crate::error::Error;
+use crate::parser::{offset, Parser};
+
+pub fn join<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, &'a str>
+where
+    O: Join<'a>,
+{
+    move |input: &'a str| {
+        let (out, rem) = parser.parse(input)?;
+        let mut span = None;
+
+        if !out.join_into(input, &mut span) {
+            return Err(Error::invalid());
+        }
+
+        match span {
+            Some((start, end)) => Ok((&input[start..end], rem)),
+            None => Ok((&input[..0], rem)),
+        }
+    }
+}
+
+pub trait Join<'a> {
+    fn join_into(&self, source: &'a str, span: &mut Option<(usize, usize)>) -> bool;
+}
+
+impl<'a> Join<'a> for () {
+    fn join_into(&self, _: &'a str, _: &mut Option<(usize, usize)>) -> bool {
+        true
+    }
+}
+
+impl<'a> Join<'a> for &'a str {
+    fn join_into(&self, source: &'a str, span: &mut Option<(usize, usize)>) -> bool {
+        let start = match offset(source, self) {
+            Some(start) => start,
+            None => return false,
+        };
+
+        match span {
+            Some((_, end)) if *end == start => *end += self.len(),
+            Some(_) => return false,
+            None => *span = Some((start, start + self.len())),
+        }
+
+        true
+    }
+}
+
+impl<'a, T> Join<'a> for Option<T>
+where
+    T: Join<'a>,
+{
+    fn join_into(&self, source: &'a str, span: &mut Option<(usize, usize)>) -> bool {
+        match self {
+            Some(inner) => inner.join_into(source, span),
+            None => true,
+        }
+    }
+}
+
+impl<'a, T> Join<'a> for Vec<T>
+where
+    T: Join<'a>,
+{
+    fn join_into(&self, source: &'a str, span: &mut Option<(usize, usize)>) -> bool {
+        self.iter().all(|item| item.join_into(source, span))
+    }
+}
+
+macro_rules! impl_join {
+    ($(($idx:tt, $T:ident),)+) => {
+        impl_join!(@iter $(($idx, $T),)+;);
+    };
+
+    (@iter ($idx:tt, $T:ident),; $(($i:tt, $U:ident),)*) => {
+        impl_join!(@impl $(($i, $U),)* ($idx, $T),);
+    };
+
+    (@iter ($idx:tt, $T:ident), $(($a:tt, $b:ident),)+; $(($i:tt, $U:ident),)*) => {
+        impl_join!(@impl $(($i, $U),)* ($idx, $T),);
+        impl_join!(@iter $(($a, $b),)*; $(($i, $U),)* ($idx, $T),);
+    };
+
+    (@impl $(($idx:tt, $T:ident),)+) => {
+        impl<'a, $($T,)+> Join<'a> for ($($T,)+)
+        where
+            $($T: Join<'a>,)+
+        {
+            fn join_into(&self, source: &'a str, span: &mut Option<(usize, usize)>) -> bool {
+                $(self.$idx.join_into(source, span))&&+
+            }
+        }
+    };
+}
+
+impl_join! {
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F),
+    (6, G),
+    (7, H),
+    (8, I),
+    (9, J),
+    (10, K),
+    (11, L),
+    (12, M),
+    (13, N),
+    (14, P),
+    (15, Q),
+    (16, R),
+    (17, S),
+    (18, T),
+    (19, U),
+    (20, V),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::branch::optional;
+    use crate::combinator::map;
+    use crate::combinator::series::{leading, list, pair, repeat};
+    use crate::parser::parse;
+    use crate::sequence::{alphabetic, decimal};
+
+    #[test]
+    fn test_join() {
+        assert_eq!(
+            parse("abc123 rest", join(pair(alphabetic, decimal))),
+            Ok(("abc123", " rest"))
+        );
+        assert_eq!(
+            parse(
+                "-12.5e3;",
+                join((optional("-"), decimal, optional(pair(".", decimal))))
+            ),
+            Ok(("-12.5", "e3;"))
+        );
+        assert_eq!(
+            parse("ab.cd.ef!", join(list(alphabetic, "."))),
+            Err(Error::invalid())
+        );
+        assert_eq!(parse("abab!", join(repeat("ab"))), Ok(("abab", "!")));
+        assert_eq!(
+            parse("-abc", join(leading('-', alphabetic))),
+            Ok(("abc", ""))
+        );
+        assert_eq!(parse("x", join(())), Ok(("", "x")));
+        assert_eq!(
+            parse("abc", join(map(alphabetic, |_| "abc"))),
+            Err(Error::invalid())
+        );
+        assert_eq!(
+            parse(
+                "abcdefghijklmnopqrstu!",
+                join((
+                    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p",
+                    "q", "r", "s", "t", "u"
+                ))
+            ),
+            Ok(("abcdefghijklmnopqrstu", "!"))
+        );
+    }
+}
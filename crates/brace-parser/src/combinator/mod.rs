@@ -1,8 +1,21 @@
+use std::borrow::Cow;
+use std::cell::OnceCell;
+
+use crate::character::CharPredicate;
 use crate::error::{Error, Expect};
-use crate::parser::Parser;
+use crate::options::{NewlinePolicy, ParseOptions};
+use crate::parser::{source_offset, BoxedParser, Output, Parser};
+use crate::position::{LineIndex, Position, Span, Spanned};
+use crate::sequence::{whitespace_opt, Sequence};
+use crate::state;
 
 pub mod branch;
+pub mod join;
+pub mod nesting;
+pub mod pratt;
+pub mod recovery;
 pub mod series;
+pub mod sugar;
 
 pub fn map<'a, M, A, B>(parser: impl Parser<'a, A>, map: M) -> impl Parser<'a, B>
 where
@@ -30,6 +43,138 @@ where
     }
 }
 
+pub fn owned<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O::Owned>
+where
+    O: IntoOwned,
+{
+    move |input| {
+        parser
+            .parse(input)
+            .map(|(out, rem)| (out.into_owned(), rem))
+    }
+}
+
+pub trait IntoOwned {
+    type Owned: 'static;
+
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl IntoOwned for &str {
+    type Owned = String;
+
+    fn into_owned(self) -> Self::Owned {
+        self.to_owned()
+    }
+}
+
+impl IntoOwned for Cow<'_, str> {
+    type Owned = String;
+
+    fn into_owned(self) -> String {
+        Cow::into_owned(self)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Spanned<T> {
+    type Owned = Spanned<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+macro_rules! impl_into_owned {
+    ($($ty:ty),+) => {
+        $(
+            impl IntoOwned for $ty {
+                type Owned = Self;
+
+                fn into_owned(self) -> Self::Owned {
+                    self
+                }
+            }
+        )+
+    };
+}
+
+impl_into_owned!(
+    (),
+    bool,
+    char,
+    String,
+    Span,
+    Position,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+macro_rules! impl_into_owned_tuple {
+    ($(($($T:ident $idx:tt),+))+) => {
+        $(
+            impl<$($T: IntoOwned),+> IntoOwned for ($($T,)+) {
+                type Owned = ($($T::Owned,)+);
+
+                fn into_owned(self) -> Self::Owned {
+                    ($(self.$idx.into_owned(),)+)
+                }
+            }
+        )+
+    };
+}
+
+impl_into_owned_tuple! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, P 14)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, P 14, Q 15)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, P 14, Q 15, R 16)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, P 14, Q 15, R 16, S 17)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, P 14, Q 15, R 16, S 17, T 18)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, P 14, Q 15, R 16, S 17, T 18, U 19)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, P 14, Q 15, R 16, S 17, T 18, U 19, V 20)
+}
+
 pub fn consume<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, &'a str> {
     move |input| {
         parser
@@ -38,6 +183,14 @@ pub fn consume<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, &'a str> {
     }
 }
 
+pub fn consumed<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, (O, &'a str)> {
+    move |input: &'a str| {
+        parser
+            .parse(input)
+            .map(|(out, rem)| ((out, &input[..input.len() - rem.len()]), rem))
+    }
+}
+
 pub fn not<'a>(parser: impl Parser<'a, char>) -> impl Parser<'a, char> {
     move |input| match parser.parse(input) {
         Ok((ch, _)) => Err(Error::found(ch)),
@@ -128,6 +281,77 @@ pub fn unescape<'a>(
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EscapeRules {
+    rules: Vec<(char, char)>,
+}
+
+impl EscapeRules {
+    pub fn new() -> Self {
+        Self {
+            rules: vec![('\\', '\\')],
+        }
+    }
+
+    pub fn rule(mut self, code: char, ch: char) -> Self {
+        self.rules.retain(|(c, v)| *c != code && *v != ch);
+        self.rules.push((code, ch));
+        self
+    }
+
+    pub fn decode(&self, code: char) -> Option<char> {
+        self.rules
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, ch)| *ch)
+    }
+
+    pub fn encode(&self, ch: char) -> Option<char> {
+        self.rules
+            .iter()
+            .find(|(_, c)| *c == ch)
+            .map(|(code, _)| *code)
+    }
+
+    fn codes(&self) -> Sequence {
+        Sequence::IsA(self.rules.iter().map(|(code, _)| code).collect())
+    }
+}
+
+impl Default for EscapeRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parser<'a, char> for EscapeRules {
+    fn parse(&self, input: &'a str) -> Output<'a, char> {
+        match input.chars().next() {
+            Some(code) => match self.decode(code) {
+                Some(ch) => Ok((ch, &input[code.len_utf8()..])),
+                None => Err(Error::expect(self.codes()).but_found(code)),
+            },
+            None => Err(Error::expect(self.codes()).but_found_end()),
+        }
+    }
+}
+
+pub fn escape(text: &str, rules: &EscapeRules) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match rules.encode(ch) {
+            Some(code) => {
+                out.push('\\');
+                out.push(code);
+            }
+            None => out.push(ch),
+        }
+    }
+
+    out
+}
+
 pub fn pass<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
     move |input| parser.parse(input).map_err(|err| err.into_pass())
 }
@@ -168,13 +392,111 @@ pub fn complete<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
     }
 }
 
+pub fn lexeme<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    series::trailing(parser, whitespace_opt)
+}
+
+pub fn fold_case<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    move |input| state::with_fold_case(true, || parser.parse(input))
+}
+
+#[cfg(feature = "unicode")]
+pub fn normalized<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    move |input| state::with_normalize(true, || parser.parse(input))
+}
+
+pub fn spanned<'a, O>(source: &'a str, parser: impl Parser<'a, O>) -> impl Parser<'a, Spanned<O>> {
+    let index = OnceCell::new();
+
+    move |input: &'a str| {
+        let index = index.get_or_init(|| LineIndex::shared(source));
+        let start = source_offset(source, input)?;
+
+        match parser.parse(input) {
+            Ok((out, rem)) => {
+                let span = index.span(start, source_offset(source, rem)?);
+
+                Ok((Spanned::new(out, span), rem))
+            }
+            Err(err) if err.get_span().is_some() => Err(err),
+            Err(err) => Err(err.with_span(index.span(start, start))),
+        }
+    }
+}
+
+pub fn with_position<'a, O>(
+    source: &'a str,
+    parser: impl Parser<'a, O>,
+) -> impl Parser<'a, (O, Position)> {
+    let index = OnceCell::new();
+
+    move |input: &'a str| {
+        let index = index.get_or_init(|| LineIndex::shared(source));
+        let position = index.position(source_offset(source, input)?);
+
+        parser.parse(input).map(|(out, rem)| ((out, position), rem))
+    }
+}
+
+pub fn preceded_by<'a, P>(source: &'a str, predicate: P) -> impl Parser<'a, ()>
+where
+    P: CharPredicate,
+{
+    move |input: &'a str| match previous(source, input)? {
+        Some(ch) if predicate.is_match(ch) => Ok(((), input)),
+        _ => Err(Error::invalid().into_pass().with_context("preceded by")),
+    }
+}
+
+pub fn not_preceded_by<'a, P>(source: &'a str, predicate: P) -> impl Parser<'a, ()>
+where
+    P: CharPredicate,
+{
+    move |input: &'a str| match previous(source, input)? {
+        Some(ch) if predicate.is_match(ch) => {
+            Err(Error::invalid().into_pass().with_context("not preceded by"))
+        }
+        _ => Ok(((), input)),
+    }
+}
+
+pub fn at_line_start<'a>(source: &'a str) -> impl Parser<'a, ()> {
+    move |input: &'a str| {
+        let any = ParseOptions::current().get_newline() == NewlinePolicy::Any;
+
+        match previous(source, input)? {
+            None | Some('\n') => Ok(((), input)),
+            Some('\r') if any && !input.starts_with('\n') => Ok(((), input)),
+            _ => Err(Error::invalid().into_pass().with_context("line start")),
+        }
+    }
+}
+
+fn previous(source: &str, input: &str) -> Result<Option<char>, Error> {
+    Ok(source[..source_offset(source, input)?].chars().next_back())
+}
+
+pub fn boxed<'a, O>(parser: impl Parser<'a, O> + 'a) -> BoxedParser<'a, O> {
+    BoxedParser::new(parser)
+}
+
+#[cfg(feature = "erased")]
+pub fn rule<'a, O>(parser: impl Parser<'a, O> + 'a) -> impl Parser<'a, O> {
+    boxed(parser)
+}
+
+#[cfg(not(feature = "erased"))]
+pub fn rule<'a, O>(parser: impl Parser<'a, O> + 'a) -> impl Parser<'a, O> {
+    parser
+}
+
 #[cfg(test)]
 mod tests {
     use super::branch::{either, optional};
-    use super::series::{leading, repeat};
+    use super::series::{leading, list, pair, repeat};
     use super::*;
-    use crate::parser::parse;
-    use crate::sequence::{alphabetic, Sequence};
+    use crate::parser::{parse, parse_with, take};
+    use crate::sequence::{alphabetic, alphanumeric, decimal, Sequence};
 
     #[test]
     fn test_map() {
@@ -248,6 +570,60 @@ mod tests {
         assert_eq!(parse("hello", consume("")), Ok(("", "hello")));
     }
 
+    #[test]
+    fn test_owned() {
+        fn entry(input: &str) -> Output<(String, Vec<String>, Option<char>)> {
+            owned((
+                alphabetic,
+                leading('=', list(alphanumeric, ',')),
+                optional(';'),
+            ))
+            .parse(input)
+        }
+
+        let buffer = String::from("key=a,b1;rest");
+        let (out, _) = entry(&buffer).unwrap();
+
+        drop(buffer);
+
+        assert_eq!(
+            out,
+            (
+                "key".to_owned(),
+                vec!["a".to_owned(), "b1".to_owned()],
+                Some(';')
+            )
+        );
+        assert_eq!(
+            parse("12", owned(map(decimal, |n: &str| (n, n.len())))),
+            Ok((("12".to_owned(), 2), ""))
+        );
+        assert_eq!(
+            parse("!", owned(alphabetic)),
+            Err(Error::expect(Sequence::Alphabetic).but_found('!'))
+        );
+    }
+
+    #[test]
+    fn test_consumed() {
+        assert_eq!(
+            parse("help", consumed(('h', 'e', 'l', 'l', 'o'))),
+            Err(Error::expect('l').but_found('p'))
+        );
+        assert_eq!(
+            parse("hello world", consumed(('h', 'e', 'l', 'l', 'o'))),
+            Ok(((('h', 'e', 'l', 'l', 'o'), "hello"), " world"))
+        );
+        assert_eq!(
+            parse(
+                "0x1F!",
+                consumed(map(leading("0x", alphanumeric), str::len))
+            ),
+            Ok(((2, "0x1F"), "!"))
+        );
+        assert_eq!(parse("hello", consumed("")), Ok((("", ""), "hello")));
+    }
+
     #[test]
     fn test_not() {
         assert_eq!(parse("", not('h')), Err(Error::found_end()));
@@ -388,6 +764,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_escape() {
+        let rules = EscapeRules::new()
+            .rule('n', '\n')
+            .rule('t', '\t')
+            .rule('"', '"');
+        let string = || {
+            unescape(
+                escaped(not(either('"', '\n')), rules.clone()),
+                rules.clone(),
+            )
+        };
+
+        assert_eq!(escape("plain", &rules), "plain");
+        assert_eq!(
+            escape("say \"hi\"\n\tC:\\", &rules),
+            r#"say \"hi\"\n\tC:\\"#
+        );
+
+        let texts = ["", "a\"b", "\\\\", "\n\t\"", "üñï\\code"];
+        let escaped = texts
+            .iter()
+            .map(|text| escape(text, &rules))
+            .collect::<Vec<_>>();
+
+        for (text, escaped) in texts.iter().zip(&escaped) {
+            assert_eq!(
+                parse(escaped, string()),
+                match text.is_empty() {
+                    true => Err(Error::found_end()),
+                    false => Ok((text.to_string(), "")),
+                }
+            );
+        }
+
+        assert_eq!(
+            parse("a\\qb", string()),
+            Err(Error::expect(Sequence::IsA("\\nt\"".to_owned())).but_found('q'))
+        );
+        assert_eq!(rules.decode('n'), Some('\n'));
+        assert_eq!(rules.encode('\\'), Some('\\'));
+        assert_eq!(EscapeRules::new().rule('\\', '/').encode('\\'), None);
+    }
+
     #[test]
     fn test_pass() {
         assert_eq!(
@@ -543,4 +963,230 @@ mod tests {
             Err(Error::expect(Expect::End).but_found(' '))
         );
     }
+
+    #[test]
+    fn test_fold_case() {
+        assert_eq!(parse("HeLLo", fold_case("hello")), Ok(("HeLLo", "")));
+        assert_eq!(parse("Hello", fold_case('h')), Ok(('h', "ello")));
+        assert_eq!(
+            parse("SELECT * FROM", fold_case(pair("select", " *"))),
+            Ok((("SELECT", " *"), " FROM"))
+        );
+        assert_eq!(
+            parse("help", fold_case("HELLO")),
+            Err(Error::expect('L').but_found('p'))
+        );
+        assert_eq!(
+            parse("HE", fold_case("hello")),
+            Err(Error::expect('l').but_found_end())
+        );
+        assert_eq!(
+            parse("HELLO", fold_case(peek(not(fold_case('x'))))),
+            Ok(('H', "HELLO"))
+        );
+        assert_eq!(
+            parse("HELLO", "hello"),
+            Err(Error::expect('h').but_found('H'))
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_fold_case_unicode() {
+        assert_eq!(parse("STRASSE", fold_case("straße")), Ok(("STRASSE", "")));
+        assert_eq!(parse("Straße!", fold_case("STRASSE")), Ok(("Straße", "!")));
+        assert_eq!(parse("ΣΊΣΥΦΟΣ", fold_case("σίσυφος")), Ok(("ΣΊΣΥΦΟΣ", "")));
+        assert_eq!(parse("É", fold_case('é')), Ok(('é', "")));
+        assert_eq!(
+            parse("STRAẞE", fold_case("strasx")),
+            Err(Error::expect('x').but_found('ẞ'))
+        );
+        assert_eq!(
+            parse("ß", fold_case("s")),
+            Err(Error::expect("s").but_found('ß'))
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_normalized() {
+        assert_eq!(
+            parse("cafe\u{301} au lait", normalized("caf\u{e9}")),
+            Ok(("cafe\u{301}", " au lait"))
+        );
+        assert_eq!(
+            parse("caf\u{e9}", normalized("cafe\u{301}")),
+            Ok(("caf\u{e9}", ""))
+        );
+        assert_eq!(
+            parse("e\u{301}!", normalized('\u{e9}')),
+            Ok(('\u{e9}', "!"))
+        );
+        assert_eq!(
+            parse("q\u{307}\u{323}", normalized("q\u{323}\u{307}")),
+            Ok(("q\u{307}\u{323}", ""))
+        );
+        assert_eq!(
+            parse("E\u{301}", fold_case(normalized("\u{e9}"))),
+            Ok(("E\u{301}", ""))
+        );
+        assert_eq!(
+            parse("cafe\u{301}", normalized("cafe")),
+            Err(Error::expect("cafe").but_found('\u{301}'))
+        );
+        assert_eq!(
+            parse("cafe\u{301}", "caf\u{e9}"),
+            Err(Error::expect('\u{e9}').but_found('e'))
+        );
+    }
+
+    #[test]
+    fn test_spanned() {
+        let source = "let x\n  = 1;";
+        let (_, rem) = parse(source, "let ").unwrap();
+
+        assert_eq!(
+            parse(rem, spanned(source, alphabetic)),
+            Ok((Spanned::new("x", Span::new(source, 4, 5)), "\n  = 1;"))
+        );
+        assert_eq!(
+            parse(&source[8..], spanned(source, "= 1")),
+            Ok((Spanned::new("= 1", Span::new(source, 8, 11)), ";"))
+        );
+
+        let (name, _) = parse(rem, spanned(source, alphabetic)).unwrap();
+
+        assert_eq!(name.len(), 1);
+        assert_eq!(name.to_string(), "x at 1:5-1:6");
+        assert_eq!(name.map(str::len), Spanned::new(1, Span::new(source, 4, 5)));
+        assert_eq!(name.slice(source), "x");
+
+        let err = parse(&source[8..], spanned(source, "==")).unwrap_err();
+
+        assert_eq!(
+            err,
+            Error::expect('=')
+                .but_found(' ')
+                .with_span(Span::new(source, 8, 8))
+        );
+        assert_eq!(err.get_span().map(|span| span.start.line), Some(2));
+        assert_eq!(
+            err.to_string(),
+            "Error: at 2:3\nExpected character: '='\nFound character: ' '"
+        );
+    }
+
+    #[test]
+    fn test_with_position() {
+        let source = "a\n  bc";
+
+        assert_eq!(
+            parse(&source[4..], with_position(source, alphabetic)),
+            Ok((("bc", Position::new(source, 4)), ""))
+        );
+        assert_eq!(
+            parse(source, with_position(source, 'a')).map(|((_, pos), _)| (pos.line, pos.column)),
+            Ok((1, 1))
+        );
+        assert_eq!(
+            parse(&source[2..], with_position(source, 'b')),
+            Err(Error::expect('b').but_found(' '))
+        );
+        assert_eq!(
+            parse(source, with_position(&source[2..], 'a')),
+            Err(Error::invalid().with_context("input outside of source"))
+        );
+    }
+
+    #[test]
+    fn test_preceded_by() {
+        let source = "a_b c";
+
+        assert_eq!(
+            parse(&source[4..], preceded_by(source, char::is_whitespace)),
+            Ok(((), "c"))
+        );
+        assert_eq!(
+            parse(&source[2..], preceded_by(source, char::is_whitespace)),
+            Err(Error::invalid().into_pass().with_context("preceded by"))
+        );
+        assert_eq!(
+            parse(source, preceded_by(source, char::is_whitespace)),
+            Err(Error::invalid().into_pass().with_context("preceded by"))
+        );
+        assert_eq!(
+            parse(&source[4..], (preceded_by(source, ' '), 'c')),
+            Ok((((), 'c'), ""))
+        );
+        assert_eq!(
+            parse(source, preceded_by(&source[2..], ' ')),
+            Err(Error::invalid().with_context("input outside of source"))
+        );
+    }
+
+    #[test]
+    fn test_not_preceded_by() {
+        let source = "ab_c d";
+        let word = |ch: char| ch.is_alphanumeric() || ch == '_';
+        let words = (0..source.len())
+            .filter(|idx| {
+                parse(&source[*idx..], (not_preceded_by(source, word), take(word))).is_ok()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(words, vec![0, 5]);
+        assert_eq!(
+            parse(&source[1..], not_preceded_by(source, 'a')),
+            Err(Error::invalid().into_pass().with_context("not preceded by"))
+        );
+    }
+
+    #[test]
+    fn test_at_line_start() {
+        let source = "# a\n# b\r\n#c\r# d";
+        let starts = |options| {
+            (0..source.len())
+                .filter(|idx| {
+                    parse_with(&source[*idx..], (at_line_start(source), '#'), options).is_ok()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(starts(ParseOptions::new()), vec![0, 4, 9]);
+        assert_eq!(
+            starts(ParseOptions::new().newline(NewlinePolicy::Any)),
+            vec![0, 4, 9, 12]
+        );
+        assert_eq!(
+            parse(&source[2..], at_line_start(source)),
+            Err(Error::invalid().into_pass().with_context("line start"))
+        );
+        assert_eq!(
+            parse(source, at_line_start(&source[4..])),
+            Err(Error::invalid().with_context("input outside of source"))
+        );
+    }
+
+    #[test]
+    fn test_lexeme() {
+        assert_eq!(parse("let  \n x", lexeme("let")), Ok(("let", "x")));
+        assert_eq!(parse("let", lexeme("let")), Ok(("let", "")));
+        assert_eq!(
+            parse(" let", lexeme("let")),
+            Err(Error::expect('l').but_found(' '))
+        );
+    }
+
+    #[test]
+    fn test_boxed() {
+        let parsers = [boxed("a"), boxed(consume(pair('b', 'c'))), boxed(rule("d"))];
+
+        assert_eq!(parsers[0].parse("ab"), Ok(("a", "b")));
+        assert_eq!(parsers[1].parse("bcd"), Ok(("bc", "d")));
+        assert_eq!(parsers[2].parse("d"), Ok(("d", "")));
+        assert_eq!(
+            parse("1", rule(boxed(alphabetic))),
+            Err(Error::expect(Sequence::Alphabetic).but_found('1'))
+        );
+    }
 }
@@ -0,0 +1,288 @@
+use crate::combinator::lexeme;
+use crate::combinator::series::delimited;
+use crate::error::Error;
+use crate::parser::Parser;
+
+pub fn block<'a, O>(
+    open: char,
+    body: impl Parser<'a, O>,
+    close: char,
+) -> impl Parser<'a, Result<O, Error>> {
+    move |input: &'a str| {
+        let (_, inner) = open.parse(input)?;
+        let (err, from) = match body.parse(inner) {
+            Ok((out, rem)) => match close.parse(rem) {
+                Ok((_, rem)) => return Ok((Ok(out), rem)),
+                Err(err) => (err, rem),
+            },
+            Err(err) => (err, inner),
+        };
+
+        if err.get_limit().is_some() {
+            return Err(err);
+        }
+
+        let idx = scan(from, &[(open, close)], "").map_err(Error::into_fail)?;
+
+        match from[idx..].strip_prefix(close) {
+            Some(rem) => Ok((Err(err), rem)),
+            None => Err(Error::expect(close).but_found_end().into_fail()),
+        }
+    }
+}
+
+pub fn parens<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    delimited(lexeme('('), lexeme(parser), ')')
+}
+
+pub fn brackets<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    delimited(lexeme('['), lexeme(parser), ']')
+}
+
+pub fn braces<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    delimited(lexeme('{'), lexeme(parser), '}')
+}
+
+pub fn balanced<'a>(open: char, close: char) -> impl Parser<'a, &'a str> {
+    balanced_quoted(open, close, "")
+}
+
+pub fn balanced_quoted<'a, Q>(open: char, close: char, quotes: Q) -> impl Parser<'a, &'a str>
+where
+    Q: Into<String>,
+{
+    let quotes = quotes.into();
+
+    move |input: &'a str| {
+        let (_, inner) = open.parse(input)?;
+
+        let idx = scan(inner, &[(open, close)], &quotes).map_err(Error::into_fail)?;
+
+        match inner[idx..].strip_prefix(close) {
+            Some(rem) => Ok((&inner[..idx], rem)),
+            None => Err(Error::expect(close).but_found_end().into_fail()),
+        }
+    }
+}
+
+pub fn until_balanced<'a, I>(pairs: I) -> impl Parser<'a, &'a str>
+where
+    I: IntoIterator<Item = (char, char)>,
+{
+    until_balanced_quoted(pairs, "")
+}
+
+pub fn until_balanced_quoted<'a, I, Q>(pairs: I, quotes: Q) -> impl Parser<'a, &'a str>
+where
+    I: IntoIterator<Item = (char, char)>,
+    Q: Into<String>,
+{
+    let pairs = pairs.into_iter().collect::<Vec<_>>();
+    let quotes = quotes.into();
+
+    move |input: &'a str| {
+        let idx = scan(input, &pairs, &quotes).map_err(Error::into_fail)?;
+
+        Ok(input.split_at(idx))
+    }
+}
+
+fn scan(input: &str, pairs: &[(char, char)], quotes: &str) -> Result<usize, Error> {
+    let mut iter = input.char_indices();
+    let mut stack = Vec::new();
+
+    while let Some((idx, ch)) = iter.next() {
+        let closes = pairs.iter().any(|(_, close)| *close == ch);
+
+        if stack.is_empty() && closes {
+            return Ok(idx);
+        } else if quotes.contains(ch) {
+            skip_quoted(&mut iter, ch)?;
+        } else if stack.last() == Some(&ch) {
+            stack.pop();
+        } else if let Some((_, close)) = pairs.iter().find(|(open, _)| *open == ch) {
+            stack.push(*close);
+        } else if closes {
+            return Err(Error::expect(stack[stack.len() - 1]).but_found(ch));
+        }
+    }
+
+    match stack.last() {
+        Some(close) => Err(Error::expect(*close).but_found_end()),
+        None => Ok(input.len()),
+    }
+}
+
+fn skip_quoted(iter: &mut impl Iterator<Item = (usize, char)>, quote: char) -> Result<(), Error> {
+    while let Some((_, ch)) = iter.next() {
+        if ch == quote {
+            return Ok(());
+        } else if ch == '\\' && iter.next().is_none() {
+            break;
+        }
+    }
+
+    Err(Error::expect(quote).but_found_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::series::{list, trailing};
+    use crate::limit::{Limit, ParseBudget};
+    use crate::options::ParseOptions;
+    use crate::parser::{parse, parse_with};
+    use crate::sequence::decimal;
+    use crate::sequence::{alphabetic, Sequence};
+
+    #[test]
+    fn test_block() {
+        let statements = || list(trailing(alphabetic, ';'), ' ');
+
+        assert_eq!(
+            parse("{a; b;} rest", block('{', statements(), '}')),
+            Ok((Ok(vec!["a", "b"]), " rest"))
+        );
+        assert_eq!(
+            parse("{a; 1; {c;}} rest", block('{', statements(), '}')),
+            Ok((Err(Error::expect('}').but_found(' ')), " rest"))
+        );
+        assert_eq!(
+            parse("{1 {x} {y}} rest", block('{', statements(), '}')),
+            Ok((
+                Err(Error::expect(Sequence::Alphabetic).but_found('1')),
+                " rest"
+            ))
+        );
+        assert_eq!(
+            parse("{a; {b;}", block('{', statements(), '}')),
+            Err(Error::expect('}').but_found_end().into_fail())
+        );
+        assert_eq!(
+            parse("a;}", block('{', statements(), '}')),
+            Err(Error::expect('{').but_found('a'))
+        );
+    }
+
+    #[test]
+    fn test_block_budget() {
+        let words = || block('{', list(alphabetic, ' '), '}');
+        let options = |steps| ParseOptions::new().fuel(ParseBudget::new(steps));
+        let err = parse_with("{a b c d e f g h i j}x", words(), options(6)).unwrap_err();
+
+        assert_eq!(err.get_limit(), Some(Limit::Budget(6)));
+        assert!(err.is_fail());
+        assert_eq!(
+            parse_with("{a b}x", words(), options(100)),
+            Ok((Ok(vec!["a", "b"]), "x"))
+        );
+    }
+
+    #[test]
+    fn test_parens() {
+        assert_eq!(parse("(a)", parens(alphabetic)), Ok(("a", "")));
+        assert_eq!(parse("( a\n) b", parens(alphabetic)), Ok(("a", " b")));
+        assert_eq!(
+            parse("(1 a)", parens(decimal)),
+            Err(Error::expect(')').but_found('a'))
+        );
+        assert_eq!(
+            parse("a", parens(alphabetic)),
+            Err(Error::expect('(').but_found('a'))
+        );
+    }
+
+    #[test]
+    fn test_brackets() {
+        assert_eq!(
+            parse("[ 1 , 2 ]", brackets(list(lexeme(decimal), lexeme(',')))),
+            Ok((vec!["1", "2"], ""))
+        );
+        assert_eq!(parse("[]", brackets(())), Ok(((), "")));
+    }
+
+    #[test]
+    fn test_braces() {
+        assert_eq!(parse("{ x }", braces(alphabetic)), Ok(("x", "")));
+        assert_eq!(
+            parse("{ x", braces(alphabetic)),
+            Err(Error::expect('}').but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_balanced() {
+        assert_eq!(
+            parse("(a, (b, c), d) rest", balanced('(', ')')),
+            Ok(("a, (b, c), d", " rest"))
+        );
+        assert_eq!(parse("()", balanced('(', ')')), Ok(("", "")));
+        assert_eq!(
+            parse("(a, \")\")", balanced('(', ')')),
+            Ok(("a, \"", "\")"))
+        );
+        assert_eq!(
+            parse("(a, (b)", balanced('(', ')')),
+            Err(Error::expect(')').but_found_end().into_fail())
+        );
+        assert_eq!(
+            parse("a)", balanced('(', ')')),
+            Err(Error::expect('(').but_found('a'))
+        );
+    }
+
+    #[test]
+    fn test_balanced_quoted() {
+        assert_eq!(
+            parse(
+                r#"(a, ")", '(', "\")") rest"#,
+                balanced_quoted('(', ')', "\"'")
+            ),
+            Ok((r#"a, ")", '(', "\")""#, " rest"))
+        );
+        assert_eq!(
+            parse(r#"(a, ")"#, balanced_quoted('(', ')', "\"")),
+            Err(Error::expect('"').but_found_end().into_fail())
+        );
+        assert_eq!(
+            parse("[x, 'it''s'] rest", balanced_quoted('[', ']', "'")),
+            Ok(("x, 'it''s'", " rest"))
+        );
+    }
+
+    #[test]
+    fn test_until_balanced() {
+        let pairs = || vec![('(', ')'), ('[', ']'), ('{', '}')];
+
+        assert_eq!(
+            parse("a + f(b[1], {c}) ) rest", until_balanced(pairs())),
+            Ok(("a + f(b[1], {c}) ", ") rest"))
+        );
+        assert_eq!(parse("x]", until_balanced(pairs())), Ok(("x", "]")));
+        assert_eq!(parse(")", until_balanced(pairs())), Ok(("", ")")));
+        assert_eq!(parse("a (b)", until_balanced(pairs())), Ok(("a (b)", "")));
+        assert_eq!(
+            parse("f(a]", until_balanced(pairs())),
+            Err(Error::expect(')').but_found(']').into_fail())
+        );
+        assert_eq!(
+            parse("f(a", until_balanced(pairs())),
+            Err(Error::expect(')').but_found_end().into_fail())
+        );
+    }
+
+    #[test]
+    fn test_until_balanced_quoted() {
+        assert_eq!(
+            parse(
+                r#"f(")") + "}"} rest"#,
+                until_balanced_quoted(vec![('(', ')'), ('{', '}')], "\"")
+            ),
+            Ok((r#"f(")") + "}""#, "} rest"))
+        );
+        assert_eq!(
+            parse(r#"a "\"}"#, until_balanced_quoted(vec![('{', '}')], "\"")),
+            Err(Error::expect('"').but_found_end().into_fail())
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::error::Error;
+use crate::parser::{Output, Parser};
+use crate::sequence::whitespace_opt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fixity {
+    Prefix,
+    Postfix,
+    InfixLeft,
+    InfixRight,
+    InfixNone,
+}
+
+impl Fixity {
+    fn is_infix(self) -> bool {
+        !matches!(self, Self::Prefix | Self::Postfix)
+    }
+
+    fn same_slot(self, other: Self) -> bool {
+        self == other || self.is_infix() && other.is_infix()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Operator {
+    pub symbol: String,
+    pub fixity: Fixity,
+    pub precedence: u32,
+}
+
+impl Operator {
+    pub fn new<T>(symbol: T, fixity: Fixity, precedence: u32) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            symbol: symbol.into(),
+            fixity,
+            precedence,
+        }
+    }
+
+    fn binding(&self) -> (u32, u32) {
+        let base = self.precedence * 2;
+
+        match self.fixity {
+            Fixity::InfixRight => (base + 1, base),
+            Fixity::InfixNone => (base + 1, base + 2),
+            _ => (base, base + 1),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Operation<O> {
+    Prefix(String, O),
+    Infix(O, String, O),
+    Postfix(O, String),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Operators {
+    table: Rc<RefCell<Vec<Operator>>>,
+}
+
+impl Operators {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prefix<T>(self, symbol: T, precedence: u32) -> Self
+    where
+        T: Into<String>,
+    {
+        self.insert(Operator::new(symbol, Fixity::Prefix, precedence));
+        self
+    }
+
+    pub fn postfix<T>(self, symbol: T, precedence: u32) -> Self
+    where
+        T: Into<String>,
+    {
+        self.insert(Operator::new(symbol, Fixity::Postfix, precedence));
+        self
+    }
+
+    pub fn infix<T>(self, symbol: T, fixity: Fixity, precedence: u32) -> Self
+    where
+        T: Into<String>,
+    {
+        self.insert(Operator::new(symbol, fixity, precedence));
+        self
+    }
+
+    pub fn insert(&self, operator: Operator) -> Option<Operator> {
+        let mut table = self.table.borrow_mut();
+        let idx = table
+            .iter()
+            .position(|op| op.symbol == operator.symbol && op.fixity.same_slot(operator.fixity));
+
+        match idx {
+            Some(idx) => Some(std::mem::replace(&mut table[idx], operator)),
+            None => {
+                table.push(operator);
+                None
+            }
+        }
+    }
+
+    pub fn remove(&self, symbol: &str) -> Vec<Operator> {
+        let mut table = self.table.borrow_mut();
+        let (removed, kept) = table.drain(..).partition(|op| op.symbol == symbol);
+
+        *table = kept;
+
+        removed
+    }
+
+    pub fn set_precedence(&self, symbol: &str, precedence: u32) -> bool {
+        let mut found = false;
+
+        for op in self.table.borrow_mut().iter_mut() {
+            if op.symbol == symbol {
+                op.precedence = precedence;
+                found = true;
+            }
+        }
+
+        found
+    }
+
+    pub fn operators(&self) -> Vec<Operator> {
+        self.table.borrow().clone()
+    }
+
+    fn find<'a>(&self, input: &'a str, infix: bool) -> Option<(Operator, &'a str)> {
+        self.table
+            .borrow()
+            .iter()
+            .filter(|op| match infix {
+                true => op.fixity != Fixity::Prefix,
+                false => op.fixity == Fixity::Prefix,
+            })
+            .filter(|op| !op.symbol.is_empty() && input.starts_with(op.symbol.as_str()))
+            .max_by_key(|op| op.symbol.len())
+            .map(|op| (op.clone(), &input[op.symbol.len()..]))
+    }
+}
+
+pub fn pratt<'a, O, B>(
+    operators: &Operators,
+    atom: impl Parser<'a, O>,
+    build: B,
+) -> impl Parser<'a, O>
+where
+    B: Fn(Operation<O>) -> O,
+{
+    let operators = operators.clone();
+
+    move |input| expression(&operators, &atom, &build, 0, input)
+}
+
+fn expression<'a, O, B>(
+    operators: &Operators,
+    atom: &impl Parser<'a, O>,
+    build: &B,
+    min: u32,
+    input: &'a str,
+) -> Output<'a, O>
+where
+    B: Fn(Operation<O>) -> O,
+{
+    let (mut lhs, mut rem) = match operators.find(input, false) {
+        Some((op, rem)) => {
+            let (_, rem) = whitespace_opt(rem)?;
+            let (rhs, rem) = expression(operators, atom, build, op.binding().1, rem)?;
+
+            (build(Operation::Prefix(op.symbol, rhs)), rem)
+        }
+        None => atom.parse(input)?,
+    };
+    let mut chained: Option<u32> = None;
+
+    loop {
+        let (_, next) = whitespace_opt(rem)?;
+        let (op, next) = match operators.find(next, true) {
+            Some(found) => found,
+            None => return Ok((lhs, rem)),
+        };
+        let (left, right) = op.binding();
+
+        if left < min {
+            return Ok((lhs, rem));
+        }
+
+        if op.fixity == Fixity::Postfix {
+            lhs = build(Operation::Postfix(lhs, op.symbol));
+            rem = next;
+            continue;
+        }
+
+        if op.fixity == Fixity::InfixNone {
+            if chained == Some(op.precedence) {
+                return Err(Error::invalid().with_context(format!(
+                    "non-associative operator `{}` cannot be chained",
+                    op.symbol
+                )));
+            }
+
+            chained = Some(op.precedence);
+        }
+
+        let (_, next) = whitespace_opt(next)?;
+        let (rhs, next) = expression(operators, atom, build, right, next)?;
+
+        lhs = build(Operation::Infix(lhs, op.symbol, rhs));
+        rem = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::branch::branch;
+    use crate::combinator::map;
+    use crate::combinator::series::{list, trailing};
+    use crate::parser::{parse, take_while};
+    use crate::sequence::{alphabetic, decimal, Sequence};
+
+    fn show(op: Operation<String>) -> String {
+        match op {
+            Operation::Prefix(op, rhs) => format!("({}{})", op, rhs),
+            Operation::Infix(lhs, op, rhs) => format!("({} {} {})", lhs, op, rhs),
+            Operation::Postfix(lhs, op) => format!("({}{})", lhs, op),
+        }
+    }
+
+    fn atom(input: &str) -> Output<String> {
+        map(branch((decimal, alphabetic)), str::to_owned).parse(input)
+    }
+
+    fn arithmetic() -> Operators {
+        Operators::new()
+            .infix("+", Fixity::InfixLeft, 6)
+            .infix("-", Fixity::InfixLeft, 6)
+            .infix("*", Fixity::InfixLeft, 7)
+            .infix("^", Fixity::InfixRight, 8)
+            .infix("==", Fixity::InfixNone, 4)
+            .prefix("-", 9)
+            .postfix("!", 10)
+    }
+
+    #[test]
+    fn test_pratt() {
+        let operators = arithmetic();
+        let expr = || pratt(&operators, atom, show);
+
+        assert_eq!(parse("1", expr()), Ok(("1".to_owned(), "")));
+        assert_eq!(
+            parse("1 + 2 * 3 - x;", expr()),
+            Ok(("((1 + (2 * 3)) - x)".to_owned(), ";"))
+        );
+        assert_eq!(parse("2^3^4", expr()), Ok(("(2 ^ (3 ^ 4))".to_owned(), "")));
+        assert_eq!(
+            parse("-a! * -b", expr()),
+            Ok(("((-(a!)) * (-b))".to_owned(), ""))
+        );
+        assert_eq!(
+            parse("a + b == c ", expr()),
+            Ok(("((a + b) == c)".to_owned(), " "))
+        );
+        assert_eq!(
+            parse("a == b == c", expr()),
+            Err(Error::invalid().with_context("non-associative operator `==` cannot be chained"))
+        );
+        assert_eq!(
+            parse("1 + ", expr()),
+            Err(Error::expect(Sequence::Alphabetic).but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_pratt_runtime_table() {
+        let operators = arithmetic();
+        let expr = || pratt(&operators, atom, show);
+
+        assert_eq!(
+            operators.insert(Operator::new("+", Fixity::InfixRight, 8)),
+            Some(Operator::new("+", Fixity::InfixLeft, 6))
+        );
+        assert_eq!(
+            parse("1 + 2 * 3 + 4", expr()),
+            Ok(("((1 + 2) * (3 + 4))".to_owned(), ""))
+        );
+        assert!(operators.set_precedence("*", 9));
+        assert!(!operators.set_precedence("%", 9));
+        assert_eq!(
+            parse("1 + 2 * 3", expr()),
+            Ok(("(1 + (2 * 3))".to_owned(), ""))
+        );
+        assert_eq!(
+            operators.remove("-"),
+            vec![
+                Operator::new("-", Fixity::InfixLeft, 6),
+                Operator::new("-", Fixity::Prefix, 9)
+            ]
+        );
+        assert_eq!(parse("1 - 2", expr()), Ok(("1".to_owned(), " - 2")));
+    }
+
+    #[test]
+    fn test_pratt_fixity_declarations() {
+        let operators = Operators::new().infix("+", Fixity::InfixLeft, 6);
+        let declare = |input| {
+            let (fixity, rem) = trailing(
+                branch((
+                    map("infixl", |_| Fixity::InfixLeft),
+                    map("infixr", |_| Fixity::InfixRight),
+                )),
+                ' ',
+            )
+            .parse(input)?;
+            let (precedence, rem) = trailing(decimal, ' ').parse(rem)?;
+            let (symbol, rem) = take_while(|ch: char| "+-*<>|&".contains(ch)).parse(rem)?;
+
+            operators.insert(Operator::new(symbol, fixity, precedence.parse().unwrap()));
+
+            Ok((format!("infix {}", symbol), rem))
+        };
+        let statement = branch((declare, pratt(&operators, atom, show)));
+
+        assert_eq!(
+            parse("a + b\ninfixr 5 <>\na + b <> c <> d", list(statement, '\n')),
+            Ok((
+                vec![
+                    "(a + b)".to_owned(),
+                    "infix <>".to_owned(),
+                    "((a + b) <> (c <> d))".to_owned()
+                ],
+                ""
+            ))
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::character::CharPredicate;
+use crate::error::Error;
+use crate::parser::{take_till, Output, Parser};
+use crate::position::Span;
+use crate::state;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Partial<O> {
+    Parsed(O),
+    Missing,
+}
+
+impl<O> Partial<O> {
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Self::Missing)
+    }
+
+    pub fn parsed(self) -> Option<O> {
+        match self {
+            Self::Parsed(out) => Some(out),
+            Self::Missing => None,
+        }
+    }
+}
+
+pub fn partial<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, Partial<O>> {
+    move |input: &'a str| match parser.parse(input) {
+        Ok((out, rem)) => Ok((Partial::Parsed(out), rem)),
+        Err(err) => recover(err, input, skip_char(input)).map(|rem| (Partial::Missing, rem)),
+    }
+}
+
+pub fn partial_or<'a, O, F>(parser: impl Parser<'a, O>, placeholder: F) -> impl Parser<'a, O>
+where
+    F: Fn() -> O,
+{
+    move |input: &'a str| match parser.parse(input) {
+        Ok(out) => Ok(out),
+        Err(err) => recover(err, input, skip_char(input)).map(|rem| (placeholder(), rem)),
+    }
+}
+
+pub fn partial_until<'a, O, P>(parser: impl Parser<'a, O>, sync: P) -> impl Parser<'a, Partial<O>>
+where
+    P: CharPredicate,
+{
+    let skip = take_till(sync);
+
+    move |input: &'a str| match parser.parse(input) {
+        Ok((out, rem)) => Ok((Partial::Parsed(out), rem)),
+        Err(err) => {
+            let (skipped, _) = skip.parse(input)?;
+
+            recover(err, input, skipped.len()).map(|rem| (Partial::Missing, rem))
+        }
+    }
+}
+
+fn recover(err: Error, input: &str, skip: usize) -> Result<&str, Error> {
+    match err {
+        Error::Pass(_) if skip > 0 => state::recover(err, input).map(|_| &input[skip..]),
+        err => Err(err),
+    }
+}
+
+fn skip_char(input: &str) -> usize {
+    input.chars().next().map_or(0, char::len_utf8)
+}
+
+pub fn parse_partial<'a, P, O>(input: &'a str, parser: P) -> (Output<'a, O>, Vec<Error>)
+where
+    P: Parser<'a, O>,
+{
+    let (out, errors) = state::with_recovery(|| parser.parse(input));
+    let start = input.as_ptr() as usize;
+    let errors = errors
+        .into_iter()
+        .map(|(err, pos)| match pos.checked_sub(start) {
+            Some(offset) if offset <= input.len() && err.get_span().is_none() => {
+                err.with_span(Span::new(input, offset, offset))
+            }
+            _ => err,
+        })
+        .collect();
+
+    (out, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::branch::branch;
+    use crate::combinator::series::{list, trailing};
+    use crate::combinator::{fail, map};
+    use crate::parser::parse;
+    use crate::sequence::{alphabetic, decimal, Sequence};
+
+    fn statement<'a>() -> impl Parser<'a, Partial<(&'a str, char, &'a str)>> {
+        partial_until((alphabetic, '=', decimal), ';')
+    }
+
+    #[test]
+    fn test_parse_partial() {
+        let input = "a=1;b=x;c=3;";
+        let (out, errors) = parse_partial(input, list(trailing(statement(), ';'), ()));
+
+        assert_eq!(
+            out,
+            Ok((
+                vec![
+                    Partial::Parsed(("a", '=', "1")),
+                    Partial::Missing,
+                    Partial::Parsed(("c", '=', "3")),
+                ],
+                ""
+            ))
+        );
+        assert_eq!(
+            errors,
+            vec![Error::expect(Sequence::Decimal)
+                .but_found('x')
+                .with_span(Span::new(input, 4, 4))]
+        );
+    }
+
+    #[test]
+    fn test_partial_outside_recovery() {
+        assert_eq!(
+            parse("a=x;", statement()),
+            Err(Error::expect(Sequence::Decimal).but_found('x'))
+        );
+        assert_eq!(
+            parse("a=1;", statement()),
+            Ok((Partial::Parsed(("a", '=', "1")), ";"))
+        );
+    }
+
+    #[test]
+    fn test_partial_or() {
+        let (out, errors) = parse_partial("?", partial_or(alphabetic, || "<missing>"));
+
+        assert_eq!(out, Ok(("<missing>", "")));
+        assert_eq!(errors.len(), 1);
+
+        let (out, errors) = parse_partial("1", partial(alphabetic));
+
+        assert!(out.unwrap().0.is_missing());
+        assert_eq!(
+            errors,
+            vec![Error::expect(Sequence::Alphabetic)
+                .but_found('1')
+                .with_span(Span::new("1", 0, 0))]
+        );
+
+        let (out, errors) = parse_partial("", partial(alphabetic));
+
+        assert_eq!(
+            out,
+            Err(Error::expect(Sequence::Alphabetic).but_found_end())
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_partial_progress() {
+        let (out, errors) = parse_partial("12", list(partial(alphabetic), ()));
+
+        assert_eq!(out, Ok((vec![Partial::Missing, Partial::Missing], "")));
+        assert_eq!(errors.len(), 2);
+
+        let (out, errors) = parse_partial("x;", partial_until(decimal, ';'));
+
+        assert_eq!(out, Ok((Partial::Missing, ";")));
+        assert_eq!(errors.len(), 1);
+
+        let (out, errors) = parse_partial(";", partial_until(decimal, ';'));
+
+        assert_eq!(out, Err(Error::expect(Sequence::Decimal).but_found(';')));
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_partial_backtrack() {
+        let parser = branch((
+            map((partial(alphabetic), 'z'), |_| "alphabetic"),
+            map(decimal, |_| "decimal"),
+        ));
+        let (out, errors) = parse_partial("1", parser);
+
+        assert_eq!(out, Ok(("decimal", "")));
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_partial_fail() {
+        let (out, errors) = parse_partial("1", partial(fail(alphabetic)));
+
+        assert_eq!(
+            out,
+            Err(Error::expect(Sequence::Alphabetic)
+                .but_found('1')
+                .into_fail())
+        );
+        assert!(errors.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::combinator::branch::either;
+use crate::combinator::{escaped, fail, map, not, peek, unescape, EscapeRules};
 use crate::error::Error;
 use crate::parser::{Output, Parser};
 
@@ -50,6 +52,53 @@ pub fn delimited<'a, A, B, C>(
     leading(a, trailing(b, c))
 }
 
+pub fn surrounded_by<'a, O, Q>(
+    surround: impl Parser<'a, Q>,
+    parser: impl Parser<'a, O>,
+) -> impl Parser<'a, O> {
+    move |input| {
+        let (_, rem) = surround.parse(input)?;
+        let (out, rem) = parser.parse(rem)?;
+        let (_, rem) = surround.parse(rem)?;
+
+        Ok((out, rem))
+    }
+}
+
+pub fn quoted<'a>(quote: char, rules: EscapeRules) -> impl Parser<'a, String> {
+    let rules = rules.rule(quote, quote);
+
+    surrounded_by(
+        quote,
+        either(
+            map(peek(quote), |_| String::new()),
+            fail(unescape(escaped(not(quote), rules.clone()), rules)),
+        ),
+    )
+}
+
+pub fn delimited_keep<'a, A, B, C>(
+    a: impl Parser<'a, A>,
+    b: impl Parser<'a, B>,
+    c: impl Parser<'a, C>,
+) -> impl Parser<'a, (A, B, C)> {
+    trio(a, b, c)
+}
+
+pub fn delimited_outer<'a, A, B, C>(
+    a: impl Parser<'a, A>,
+    b: impl Parser<'a, B>,
+    c: impl Parser<'a, C>,
+) -> impl Parser<'a, (B, &'a str)> {
+    let parser = delimited(a, b, c);
+
+    move |input: &'a str| {
+        let (out, rem) = parser.parse(input)?;
+
+        Ok(((out, &input[..input.len() - rem.len()]), rem))
+    }
+}
+
 pub fn repeat<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, Vec<O>> {
     move |input| {
         parser.parse(input).and_then(|(out, mut rem)| {
@@ -69,6 +118,55 @@ pub fn repeat<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, Vec<O>> {
     }
 }
 
+pub fn skip_many<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, ()> {
+    move |input| skip(&parser, input).map(|(_, rem)| ((), rem))
+}
+
+pub fn skip_many1<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, ()> {
+    move |input| {
+        let (_, rem) = parser.parse(input)?;
+        let (_, rem) = skip(&parser, rem)?;
+
+        Ok(((), rem))
+    }
+}
+
+pub fn repeat_consume<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let (_, rem) = parser.parse(input)?;
+        let (_, rem) = skip(&parser, rem)?;
+
+        Ok(input.split_at(input.len() - rem.len()))
+    }
+}
+
+pub fn consume_many<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let (_, rem) = skip(&parser, input)?;
+
+        Ok(input.split_at(input.len() - rem.len()))
+    }
+}
+
+pub fn count_matches<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, usize> {
+    move |input| skip(&parser, input)
+}
+
+fn skip<'a, O>(parser: &impl Parser<'a, O>, mut rem: &'a str) -> Output<'a, usize> {
+    let mut count = 0;
+
+    loop {
+        match parser.parse(rem) {
+            Ok((_, next)) if next.len() < rem.len() => {
+                count += 1;
+                rem = next;
+            }
+            Ok(_) | Err(Error::Pass(_)) => return Ok((count, rem)),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
 pub fn list<'a, T, S>(
     parser: impl Parser<'a, T>,
     separator: impl Parser<'a, S>,
@@ -97,6 +195,204 @@ pub fn list<'a, T, S>(
     }
 }
 
+pub fn fold_list<'a, A, T, S, F>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+    init: A,
+    fold: F,
+) -> impl Parser<'a, A>
+where
+    A: Clone,
+    F: Fn(A, T) -> A,
+{
+    move |input| {
+        parser.parse(input).and_then(|(out, mut rem)| {
+            let mut acc = fold(init.clone(), out);
+
+            loop {
+                match separator.parse(rem) {
+                    Ok((_, next)) => match parser.parse(next) {
+                        Ok((item, next)) => {
+                            acc = fold(acc, item);
+                            rem = next;
+                        }
+                        Err(Error::Pass(_)) => return Ok((acc, rem)),
+                        Err(err) => return Err(err),
+                    },
+                    Err(Error::Pass(_)) => return Ok((acc, rem)),
+                    Err(err) => return Err(err),
+                }
+            }
+        })
+    }
+}
+
+pub fn punctuated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Punctuated<T, S>> {
+    punctuated_with(parser, separator, false)
+}
+
+pub fn punctuated_trailing<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Punctuated<T, S>> {
+    punctuated_with(parser, separator, true)
+}
+
+fn punctuated_with<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+    trailing: bool,
+) -> impl Parser<'a, Punctuated<T, S>> {
+    move |input| {
+        parser.parse(input).and_then(|(out, mut rem)| {
+            let mut out = Punctuated {
+                items: vec![out],
+                separators: Vec::new(),
+            };
+
+            loop {
+                match separator.parse(rem) {
+                    Ok((sep, next)) => match parser.parse(next) {
+                        Ok((item, next)) => {
+                            out.separators.push(sep);
+                            out.items.push(item);
+                            rem = next;
+                        }
+                        Err(Error::Pass(_)) if trailing => {
+                            out.separators.push(sep);
+
+                            return Ok((out, next));
+                        }
+                        Err(Error::Pass(_)) => return Ok((out, rem)),
+                        Err(err) => return Err(err),
+                    },
+                    Err(Error::Pass(_)) => return Ok((out, rem)),
+                    Err(err) => return Err(err),
+                }
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Punctuated<T, S> {
+    items: Vec<T>,
+    separators: Vec<S>,
+}
+
+impl<T, S> Punctuated<T, S> {
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn separators(&self) -> &[S] {
+        &self.separators
+    }
+
+    pub fn pairs(&self) -> impl Iterator<Item = (&T, Option<&S>)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(move |(idx, item)| (item, self.separators.get(idx)))
+    }
+
+    pub fn has_trailing(&self) -> bool {
+        self.separators.len() == self.items.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    pub fn into_parts(self) -> (Vec<T>, Vec<S>) {
+        (self.items, self.separators)
+    }
+}
+
+pub fn delimited_list<'a, T, S, A, B>(
+    open: impl Parser<'a, A>,
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+    close: impl Parser<'a, B>,
+) -> impl Parser<'a, Vec<T>> {
+    delimited_list_with(open, item, separator, close, ListOptions::default())
+}
+
+pub fn delimited_list_with<'a, T, S, A, B>(
+    open: impl Parser<'a, A>,
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+    close: impl Parser<'a, B>,
+    options: ListOptions,
+) -> impl Parser<'a, Vec<T>> {
+    move |input| {
+        let (_, mut rem) = open.parse(input)?;
+        let mut out = Vec::new();
+
+        loop {
+            match item.parse(rem) {
+                Ok((item, next)) => {
+                    out.push(item);
+                    rem = next;
+                }
+                Err(Error::Pass(_)) if out.is_empty() && options.allow_empty => break,
+                Err(Error::Pass(_)) if !out.is_empty() && options.allow_trailing => break,
+                Err(err) => return Err(err),
+            }
+
+            match separator.parse(rem) {
+                Ok((_, next)) => rem = next,
+                Err(Error::Pass(_)) => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        close.parse(rem).map(|(_, rem)| (out, rem))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ListOptions {
+    allow_empty: bool,
+    allow_trailing: bool,
+}
+
+impl ListOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow_empty(mut self, allow_empty: bool) -> Self {
+        self.allow_empty = allow_empty;
+        self
+    }
+
+    pub fn allow_trailing(mut self, allow_trailing: bool) -> Self {
+        self.allow_trailing = allow_trailing;
+        self
+    }
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        Self {
+            allow_empty: true,
+            allow_trailing: false,
+        }
+    }
+}
+
 pub trait Series<'a, O> {
     fn parse_series(&self, input: &'a str) -> Output<'a, O>;
 }
@@ -112,21 +408,46 @@ where
     T: Parser<'a, O>,
 {
     fn parse_series(&self, input: &'a str) -> Output<'a, Vec<O>> {
-        let mut out = Vec::new();
-        let mut rem = input;
+        parse_slice(self, input)
+    }
+}
 
-        for parser in self {
-            match parser.parse(rem) {
-                Ok((item, next)) => {
-                    out.push(item);
-                    rem = next;
-                }
-                Err(err) => return Err(err),
+impl<'a, T, O> Series<'a, Vec<O>> for &[T]
+where
+    T: Parser<'a, O>,
+{
+    fn parse_series(&self, input: &'a str) -> Output<'a, Vec<O>> {
+        parse_slice(self, input)
+    }
+}
+
+impl<'a, T, O, const N: usize> Series<'a, Vec<O>> for [T; N]
+where
+    T: Parser<'a, O>,
+{
+    fn parse_series(&self, input: &'a str) -> Output<'a, Vec<O>> {
+        parse_slice(self, input)
+    }
+}
+
+fn parse_slice<'a, T, O>(parsers: &[T], input: &'a str) -> Output<'a, Vec<O>>
+where
+    T: Parser<'a, O>,
+{
+    let mut out = Vec::with_capacity(parsers.len());
+    let mut rem = input;
+
+    for parser in parsers {
+        match parser.parse(rem) {
+            Ok((item, next)) => {
+                out.push(item);
+                rem = next;
             }
+            Err(err) => return Err(err),
         }
-
-        Ok((out, rem))
     }
+
+    Ok((out, rem))
 }
 
 macro_rules! impl_series {
@@ -155,7 +476,7 @@ macro_rules! impl_series {
     };
 
     (@start $self:expr; $input:expr; $($idx:tt,)+) => {
-        impl_series!(@inner $self; $input; a, b, c, d, e, f, g, h, i, j, k, l,;; $($idx,)+);
+        impl_series!(@inner $self; $input; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u,;; $($idx,)+)
     };
 
     (@inner $self:expr; $input:expr; $out:ident, $($arg:ident,)*; $($acc:ident,)*; $i:tt,) => {
@@ -174,27 +495,37 @@ macro_rules! impl_series {
 }
 
 impl_series! {
-    (0, A, N),
-    (1, B, O),
-    (2, C, P),
-    (3, D, Q),
-    (4, E, R),
-    (5, F, S),
-    (6, G, T),
-    (7, H, U),
-    (8, I, V),
-    (9, J, W),
-    (10, K, X),
-    (11, L, Y),
+    (0, A, OA),
+    (1, B, OB),
+    (2, C, OC),
+    (3, D, OD),
+    (4, E, OE),
+    (5, F, OF),
+    (6, G, OG),
+    (7, H, OH),
+    (8, I, OI),
+    (9, J, OJ),
+    (10, K, OK),
+    (11, L, OL),
+    (12, M, OM),
+    (13, N, ON),
+    (14, P, OP),
+    (15, Q, OQ),
+    (16, R, OR),
+    (17, S, OS),
+    (18, T, OT),
+    (19, U, OU),
+    (20, V, OV),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::combinator::branch::branch;
     use crate::combinator::fail;
     use crate::error::Error;
     use crate::parser::parse;
-    use crate::sequence::{alphabetic, whitespace};
+    use crate::sequence::{alphabetic, decimal, whitespace, whitespace_opt, Sequence};
 
     #[test]
     fn test_series() {
@@ -523,4 +854,381 @@ mod tests {
             Err(Error::expect('a').but_found('b').into_fail())
         );
     }
+
+    #[test]
+    fn test_surrounded_by() {
+        assert_eq!(
+            parse("|abc| rest", surrounded_by('|', alphabetic)),
+            Ok(("abc", " rest"))
+        );
+        assert_eq!(
+            parse("**bold**", surrounded_by("**", alphabetic)),
+            Ok(("bold", ""))
+        );
+        assert_eq!(
+            parse("|abc", surrounded_by('|', alphabetic)),
+            Err(Error::expect('|').but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_quoted() {
+        let rules = || EscapeRules::new().rule('n', '\n');
+
+        assert_eq!(
+            parse("'it\\'s' ok", quoted('\'', EscapeRules::new())),
+            Ok(("it's".to_owned(), " ok"))
+        );
+        assert_eq!(
+            parse("\"a\\\\b\\n\"", quoted('"', rules())),
+            Ok(("a\\b\n".to_owned(), ""))
+        );
+        assert_eq!(
+            parse("``", quoted('`', EscapeRules::new())),
+            Ok((String::new(), ""))
+        );
+        assert_eq!(
+            parse("'open", quoted('\'', rules())),
+            Err(Error::expect('\'').but_found_end())
+        );
+        assert_eq!(
+            parse("'open\\", quoted('\'', rules())),
+            Err(Error::found('\\').into_fail())
+        );
+        assert_eq!(
+            parse("'a\\tb'", quoted('\'', rules())),
+            Err(Error::expect(Sequence::IsA("\\n'".to_owned()))
+                .but_found('t')
+                .into_fail())
+        );
+        assert_eq!(
+            parse("open'", quoted('\'', rules())),
+            Err(Error::expect('\'').but_found('o'))
+        );
+    }
+
+    #[test]
+    fn test_delimited_keep() {
+        assert_eq!(
+            parse("( hello )!", delimited_keep("( ", "hello", " )")),
+            Ok((("( ", "hello", " )"), "!"))
+        );
+        assert_eq!(
+            parse(
+                "[1]",
+                delimited_keep(branch(('(', '[')), decimal, branch((')', ']')))
+            ),
+            Ok((('[', "1", ']'), ""))
+        );
+        assert_eq!(
+            parse("(1", delimited_keep('(', decimal, ')')),
+            Err(Error::expect(')').but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_delimited_outer() {
+        assert_eq!(
+            parse("\"hi\" there", delimited_outer('"', "hi", '"')),
+            Ok((("hi", "\"hi\""), " there"))
+        );
+        assert_eq!(
+            parse("\"hi", delimited_outer('"', "hi", '"')),
+            Err(Error::expect('"').but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_delimited_list() {
+        let args = || delimited_list('(', alphabetic, ", ", ')');
+
+        assert_eq!(parse("()", args()), Ok((vec![], "")));
+        assert_eq!(parse("(a)", args()), Ok((vec!["a"], "")));
+        assert_eq!(
+            parse("(a, b, c) d", args()),
+            Ok((vec!["a", "b", "c"], " d"))
+        );
+        assert_eq!(
+            parse("(a, b, )", args()),
+            Err(Error::expect(Sequence::Alphabetic).but_found(')'))
+        );
+        assert_eq!(
+            parse("(a b)", args()),
+            Err(Error::expect(')').but_found(' '))
+        );
+        assert_eq!(
+            parse("a, b)", args()),
+            Err(Error::expect('(').but_found('a'))
+        );
+    }
+
+    #[test]
+    fn test_delimited_list_with() {
+        let trailing = ListOptions::new().allow_trailing(true);
+        let non_empty = ListOptions::new().allow_empty(false);
+
+        assert_eq!(
+            parse(
+                "[a, b, ]",
+                delimited_list_with('[', alphabetic, ", ", ']', trailing)
+            ),
+            Ok((vec!["a", "b"], ""))
+        );
+        assert_eq!(
+            parse(
+                "[]",
+                delimited_list_with('[', alphabetic, ", ", ']', trailing)
+            ),
+            Ok((vec![], ""))
+        );
+        assert_eq!(
+            parse(
+                "[]",
+                delimited_list_with('[', alphabetic, ", ", ']', non_empty)
+            ),
+            Err(Error::expect(Sequence::Alphabetic).but_found(']'))
+        );
+        assert_eq!(
+            parse(
+                "[a]",
+                delimited_list_with('[', alphabetic, ", ", ']', non_empty)
+            ),
+            Ok((vec!["a"], ""))
+        );
+        assert_eq!(
+            parse(
+                "[a, 1]",
+                delimited_list_with('[', alphabetic, ", ", ']', trailing)
+            ),
+            Err(Error::expect(']').but_found('1'))
+        );
+        assert_eq!(
+            parse(
+                "[a, 1]",
+                delimited_list_with('[', alphabetic, ", ", fail(']'), trailing)
+            ),
+            Err(Error::expect(']').but_found('1').into_fail())
+        );
+    }
+
+    #[test]
+    fn test_series_arity() {
+        assert_eq!(
+            parse(
+                "abcdefghijklmnopqrstu!",
+                series((
+                    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+                    'q', 'r', 's', 't', 'u'
+                ))
+            )
+            .map(|(out, rem)| ((out.0, out.10, out.20), rem)),
+            Ok((('a', 'k', 'u'), "!"))
+        );
+        assert_eq!(
+            parse(
+                "abcdefghijklmnopqrstu!",
+                (
+                    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+                    'q', 'r', 's', 't', 'u'
+                )
+            )
+            .map(|(out, rem)| ((out.0, out.10, out.20), rem)),
+            Ok((('a', 'k', 'u'), "!"))
+        );
+        assert_eq!(
+            parse(
+                "abc",
+                series((
+                    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+                    'q', 'r', 's', 't', 'u'
+                ))
+            )
+            .map(|(out, rem)| (out.0, rem)),
+            Err(Error::expect('d').but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_series_array() {
+        const GREETING: [&str; 3] = ["hello", " ", "world"];
+
+        assert_eq!(
+            parse("hello world!", series(GREETING)),
+            Ok((vec!["hello", " ", "world"], "!"))
+        );
+        assert_eq!(
+            parse("hello world!", series(&GREETING[..2])),
+            Ok((vec!["hello", " "], "world!"))
+        );
+        assert_eq!(
+            parse("hello there", series(GREETING)),
+            Err(Error::expect('w').but_found('t'))
+        );
+        assert_eq!(
+            parse("hello", series([] as [&str; 0])),
+            Ok((vec![], "hello"))
+        );
+    }
+
+    #[test]
+    fn test_fold_list() {
+        use crate::combinator::map;
+        use std::collections::HashMap;
+
+        let sum = || {
+            fold_list(
+                map(decimal, |n: &str| n.parse::<u32>().unwrap()),
+                '+',
+                0,
+                |acc, n| acc + n,
+            )
+        };
+
+        assert_eq!(parse("1+2+39", sum()), Ok((42, "")));
+        assert_eq!(parse("7+", sum()), Ok((7, "+")));
+        assert_eq!(parse("7 + 1", sum()), Ok((7, " + 1")));
+        assert_eq!(
+            parse("+1", sum()),
+            Err(Error::expect(Sequence::Decimal).but_found('+'))
+        );
+
+        let (map, rem) = parse(
+            "a=1;b=2;a=3",
+            fold_list(
+                pair(alphabetic, leading('=', decimal)),
+                ';',
+                HashMap::new(),
+                |mut map, (key, value)| {
+                    map.insert(key, value);
+                    map
+                },
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], "3");
+        assert_eq!(
+            parse("1+2", fold_list(decimal, fail('-'), 0, |acc, _| acc + 1)),
+            Err(Error::expect('-').but_found('+').into_fail())
+        );
+    }
+
+    #[test]
+    fn test_punctuated() {
+        use crate::combinator::consume;
+
+        let (args, rem) = parse(
+            "a, b ,c)",
+            punctuated(
+                alphabetic,
+                consume(trio(whitespace_opt, ',', whitespace_opt)),
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(rem, ")");
+        assert_eq!(args.items(), &["a", "b", "c"]);
+        assert_eq!(args.separators(), &[", ", " ,"]);
+        assert_eq!(
+            args.pairs().collect::<Vec<_>>(),
+            vec![(&"a", Some(&", ")), (&"b", Some(&" ,")), (&"c", None)]
+        );
+        assert!(!args.has_trailing());
+        assert_eq!(args.len(), 3);
+        assert_eq!(
+            parse("a,b,", punctuated(alphabetic, ',')).map(|(out, rem)| (out.into_items(), rem)),
+            Ok((vec!["a", "b"], ","))
+        );
+        assert_eq!(
+            parse("1", punctuated(alphabetic, ',')),
+            Err(Error::expect(Sequence::Alphabetic).but_found('1'))
+        );
+    }
+
+    #[test]
+    fn test_punctuated_trailing() {
+        let (args, rem) = parse("a,b,)", punctuated_trailing(alphabetic, ',')).unwrap();
+
+        assert_eq!(rem, ")");
+        assert!(args.has_trailing());
+        assert_eq!(args.clone().into_parts(), (vec!["a", "b"], vec![',', ',']));
+        assert_eq!(
+            args.pairs().collect::<Vec<_>>(),
+            vec![(&"a", Some(&',')), (&"b", Some(&','))]
+        );
+        assert_eq!(
+            parse("a)", punctuated_trailing(alphabetic, ','))
+                .map(|(out, rem)| (out.has_trailing(), rem)),
+            Ok((false, ")"))
+        );
+    }
+
+    #[test]
+    fn test_skip_many() {
+        use crate::sequence::linebreak;
+
+        assert_eq!(parse("\n\n\nx", skip_many(linebreak)), Ok(((), "x")));
+        assert_eq!(parse("x", skip_many(linebreak)), Ok(((), "x")));
+        assert_eq!(parse("aaab", skip_many('a')), Ok(((), "b")));
+        assert_eq!(parse("aaab", skip_many("")), Ok(((), "aaab")));
+        assert_eq!(
+            parse("#a#1", skip_many(pair('#', fail(alphabetic)))),
+            Err(Error::expect(Sequence::Alphabetic)
+                .but_found('1')
+                .into_fail())
+        );
+    }
+
+    #[test]
+    fn test_skip_many1() {
+        assert_eq!(parse("aaab", skip_many1('a')), Ok(((), "b")));
+        assert_eq!(parse("ab", skip_many1('a')), Ok(((), "b")));
+        assert_eq!(
+            parse("b", skip_many1('a')),
+            Err(Error::expect('a').but_found('b'))
+        );
+    }
+
+    #[test]
+    fn test_repeat_consume() {
+        assert_eq!(parse("aaab", repeat_consume('a')), Ok(("aaa", "b")));
+        assert_eq!(
+            parse("ab12ab3", repeat_consume(pair(alphabetic, decimal))),
+            Ok(("ab12ab3", ""))
+        );
+        assert_eq!(
+            parse("b", repeat_consume('a')),
+            Err(Error::expect('a').but_found('b'))
+        );
+        assert_eq!(
+            parse("#a#1", repeat_consume(pair('#', fail(alphabetic)))),
+            Err(Error::expect(Sequence::Alphabetic)
+                .but_found('1')
+                .into_fail())
+        );
+    }
+
+    #[test]
+    fn test_consume_many() {
+        assert_eq!(parse("aaab", consume_many('a')), Ok(("aaa", "b")));
+        assert_eq!(parse("b", consume_many('a')), Ok(("", "b")));
+        assert_eq!(parse("aaab", consume_many("")), Ok(("", "aaab")));
+    }
+
+    #[test]
+    fn test_count_matches() {
+        assert_eq!(parse("### Title", count_matches('#')), Ok((3, " Title")));
+        assert_eq!(parse("Title", count_matches('#')), Ok((0, "Title")));
+        assert_eq!(parse("\t\t  x", count_matches(' ')), Ok((0, "\t\t  x")));
+        assert_eq!(parse("    x", count_matches("  ")), Ok((2, "x")));
+        assert_eq!(parse("aaab", count_matches("")), Ok((0, "aaab")));
+        assert_eq!(
+            parse("#a#1", count_matches(pair('#', fail(alphabetic)))),
+            Err(Error::expect(Sequence::Alphabetic)
+                .but_found('1')
+                .into_fail())
+        );
+    }
 }
@@ -0,0 +1,210 @@
+use std::ops::{Add, BitOr, Bound, Mul, RangeBounds};
+
+use crate::error::Error;
+use crate::parser::{Output, Parser};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Sugar<P>(pub P);
+
+pub fn sugar<P>(parser: P) -> Sugar<P> {
+    Sugar(parser)
+}
+
+impl<P> Sugar<P> {
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<'a, O, P> Parser<'a, O> for Sugar<P>
+where
+    P: Parser<'a, O>,
+{
+    fn parse(&self, input: &'a str) -> Output<'a, O> {
+        self.0.parse(input)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Seq<A, B>(A, B);
+
+impl<'a, A, B, OA, OB> Parser<'a, (OA, OB)> for Seq<A, B>
+where
+    A: Parser<'a, OA>,
+    B: Parser<'a, OB>,
+{
+    fn parse(&self, input: &'a str) -> Output<'a, (OA, OB)> {
+        let (a, rem) = self.0.parse(input)?;
+        let (b, rem) = self.1.parse(rem)?;
+
+        Ok(((a, b), rem))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Alt<A, B>(A, B);
+
+impl<'a, A, B, O> Parser<'a, O> for Alt<A, B>
+where
+    A: Parser<'a, O>,
+    B: Parser<'a, O>,
+{
+    fn parse(&self, input: &'a str) -> Output<'a, O> {
+        match self.0.parse(input) {
+            Err(Error::Pass(_)) => self.1.parse(input),
+            res => res,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rep<P> {
+    parser: P,
+    min: usize,
+    max: Option<usize>,
+}
+
+impl<'a, P, O> Parser<'a, Vec<O>> for Rep<P>
+where
+    P: Parser<'a, O>,
+{
+    fn parse(&self, input: &'a str) -> Output<'a, Vec<O>> {
+        let mut out = Vec::new();
+        let mut rem = input;
+
+        while !matches!(self.max, Some(max) if out.len() >= max) {
+            match self.parser.parse(rem) {
+                Ok((item, next)) if next.len() < rem.len() => {
+                    out.push(item);
+                    rem = next;
+                }
+                Ok((item, next)) => {
+                    out.push(item);
+                    rem = next;
+                    break;
+                }
+                Err(Error::Pass(_)) if out.len() >= self.min => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        match out.len() < self.min {
+            true => Err(Error::invalid().into_pass().with_context(format!(
+                "expected at least {} repetitions, found {}",
+                self.min,
+                out.len()
+            ))),
+            false => Ok((out, rem)),
+        }
+    }
+}
+
+impl<A, B> Add<B> for Sugar<A> {
+    type Output = Sugar<Seq<A, B>>;
+
+    fn add(self, rhs: B) -> Self::Output {
+        Sugar(Seq(self.0, rhs))
+    }
+}
+
+impl<A, B> BitOr<B> for Sugar<A> {
+    type Output = Sugar<Alt<A, B>>;
+
+    fn bitor(self, rhs: B) -> Self::Output {
+        Sugar(Alt(self.0, rhs))
+    }
+}
+
+impl<P, R> Mul<R> for Sugar<P>
+where
+    R: RangeBounds<usize>,
+{
+    type Output = Sugar<Rep<P>>;
+
+    fn mul(self, range: R) -> Self::Output {
+        let min = match range.start_bound() {
+            Bound::Included(min) => *min,
+            Bound::Excluded(min) => min.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let max = match range.end_bound() {
+            Bound::Included(max) => Some(*max),
+            Bound::Excluded(max) => Some(max.saturating_sub(1)),
+            Bound::Unbounded => None,
+        };
+
+        Sugar(Rep {
+            parser: self.0,
+            min,
+            max,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::{fail, map};
+    use crate::parser::parse;
+    use crate::sequence::{alphabetic, decimal, Sequence};
+
+    #[test]
+    fn test_sugar_seq() {
+        let assign = sugar(alphabetic) + '=' + decimal;
+
+        assert_eq!(parse("x=1;", assign), Ok(((("x", '='), "1"), ";")));
+        assert_eq!(
+            parse("x=y", sugar(alphabetic) + '=' + decimal),
+            Err(Error::expect(Sequence::Decimal).but_found('y'))
+        );
+    }
+
+    #[test]
+    fn test_sugar_alt() {
+        let atom = || sugar(decimal) | alphabetic | "()";
+
+        assert_eq!(parse("12+", atom()), Ok(("12", "+")));
+        assert_eq!(parse("ab1", atom()), Ok(("ab", "1")));
+        assert_eq!(parse("()", atom()), Ok(("()", "")));
+        assert_eq!(parse("[", atom()), Err(Error::expect('(').but_found('[')));
+        assert_eq!(
+            parse("1", sugar(fail("(")) | decimal),
+            Err(Error::expect('(').but_found('1').into_fail())
+        );
+    }
+
+    #[test]
+    fn test_sugar_rep() {
+        assert_eq!(parse("aaab", sugar('a') * (1..)), Ok((vec!['a'; 3], "b")));
+        assert_eq!(parse("b", sugar('a') * ..), Ok((vec![], "b")));
+        assert_eq!(parse("aaa", sugar('a') * (..=2)), Ok((vec!['a'; 2], "a")));
+        assert_eq!(parse("aaa", sugar('a') * (1..3)), Ok((vec!['a'; 2], "a")));
+        assert_eq!(
+            parse("ab", sugar('a') * (2..)),
+            Err(Error::expect('a').but_found('b'))
+        );
+        assert_eq!(
+            parse("ab", sugar("") * (2..)),
+            Err(Error::invalid()
+                .into_pass()
+                .with_context("expected at least 2 repetitions, found 1"))
+        );
+        assert_eq!(
+            parse("b", sugar('a') * (1..)),
+            Err(Error::expect('a').but_found('b'))
+        );
+    }
+
+    #[test]
+    fn test_sugar_grammar() {
+        let list = sugar('[')
+            + (sugar(map(sugar(decimal) + ',', |(n, _)| n)) * ..)
+            + (sugar(decimal) * (..=1))
+            + ']';
+
+        assert_eq!(
+            parse("[1,2,3]", list).map(|((((_, init), last), _), rem)| (init, last, rem)),
+            Ok((vec!["1", "2"], vec!["3"], ""))
+        );
+    }
+}
@@ -0,0 +1,519 @@
+use crate::parser::{offset, Parser};
+use crate::position::Span;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SyntaxElement<'a, K> {
+    Node(SyntaxNode<'a, K>),
+    Token(SyntaxToken<'a, K>),
+    Trivia(SyntaxTrivia<'a, K>),
+    Text(&'a str),
+}
+
+impl<'a, K> SyntaxElement<'a, K> {
+    pub fn text(&self) -> &'a str {
+        match self {
+            Self::Node(node) => node.text,
+            Self::Token(token) => token.text,
+            Self::Trivia(trivia) => trivia.text,
+            Self::Text(text) => text,
+        }
+    }
+
+    pub fn span(&self, source: &str) -> Option<Span> {
+        span(source, self.text())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxNode<'a, K> {
+    pub kind: K,
+    pub text: &'a str,
+    pub children: Vec<SyntaxElement<'a, K>>,
+}
+
+impl<'a, K> SyntaxNode<'a, K> {
+    pub fn span(&self, source: &str) -> Option<Span> {
+        span(source, self.text)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode<'a, K>> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            _ => None,
+        })
+    }
+
+    pub fn tokens(&self) -> impl Iterator<Item = &SyntaxToken<'a, K>> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Token(token) => Some(token),
+            _ => None,
+        })
+    }
+
+    pub fn attached(&self, policy: TriviaPolicy) -> Vec<Attached<'_, 'a, K>> {
+        let mut out: Vec<Attached<K>> = Vec::new();
+        let mut pending = Vec::new();
+
+        for child in &self.children {
+            let trivia = match child {
+                SyntaxElement::Trivia(trivia) => trivia,
+                _ => {
+                    out.push(Attached {
+                        leading: std::mem::take(&mut pending),
+                        element: child,
+                        trailing: Vec::new(),
+                    });
+                    continue;
+                }
+            };
+
+            match (policy, out.last_mut()) {
+                (TriviaPolicy::Preceding, Some(prev)) => prev.trailing.push(trivia),
+                (TriviaPolicy::SameLine, Some(prev)) if pending.is_empty() => {
+                    if !prev
+                        .trailing
+                        .iter()
+                        .any(|trivia| trivia.text.contains('\n'))
+                    {
+                        prev.trailing.push(trivia);
+                    } else {
+                        pending.push(trivia);
+                    }
+                }
+                _ => pending.push(trivia),
+            }
+        }
+
+        if let Some(last) = out.last_mut() {
+            last.trailing.append(&mut pending);
+        }
+
+        out
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity(self.text.len());
+
+        self.write_text(&mut text);
+        text
+    }
+
+    fn write_text(&self, text: &mut String) {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.write_text(text),
+                _ => text.push_str(child.text()),
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TriviaPolicy {
+    Preceding,
+    Following,
+    SameLine,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attached<'t, 'a, K> {
+    pub leading: Vec<&'t SyntaxTrivia<'a, K>>,
+    pub element: &'t SyntaxElement<'a, K>,
+    pub trailing: Vec<&'t SyntaxTrivia<'a, K>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxToken<'a, K> {
+    pub kind: K,
+    pub text: &'a str,
+}
+
+impl<'a, K> SyntaxToken<'a, K> {
+    pub fn span(&self, source: &str) -> Option<Span> {
+        span(source, self.text)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxTrivia<'a, K> {
+    pub kind: Option<K>,
+    pub text: &'a str,
+}
+
+impl<'a, K> SyntaxTrivia<'a, K> {
+    pub fn span(&self, source: &str) -> Option<Span> {
+        span(source, self.text)
+    }
+}
+
+pub fn syntax_node<'a, K, O>(
+    kind: K,
+    parser: impl Parser<'a, O>,
+) -> impl Parser<'a, SyntaxNode<'a, K>>
+where
+    K: Clone,
+    O: IntoSyntax<'a, K>,
+{
+    move |input: &'a str| {
+        let (out, rem) = parser.parse(input)?;
+        let text = &input[..input.len() - rem.len()];
+        let mut elements = Vec::new();
+
+        out.collect_into(&mut elements);
+
+        let node = SyntaxNode {
+            kind: kind.clone(),
+            text,
+            children: fill(text, elements),
+        };
+
+        Ok((node, rem))
+    }
+}
+
+pub fn syntax_token<'a, K, O>(
+    kind: K,
+    parser: impl Parser<'a, O>,
+) -> impl Parser<'a, SyntaxToken<'a, K>>
+where
+    K: Clone,
+{
+    move |input: &'a str| {
+        let (_, rem) = parser.parse(input)?;
+        let token = SyntaxToken {
+            kind: kind.clone(),
+            text: &input[..input.len() - rem.len()],
+        };
+
+        Ok((token, rem))
+    }
+}
+
+pub fn syntax_trivia<'a, K, O>(
+    kind: K,
+    parser: impl Parser<'a, O>,
+) -> impl Parser<'a, SyntaxTrivia<'a, K>>
+where
+    K: Clone,
+{
+    move |input: &'a str| {
+        let (_, rem) = parser.parse(input)?;
+        let trivia = SyntaxTrivia {
+            kind: Some(kind.clone()),
+            text: &input[..input.len() - rem.len()],
+        };
+
+        Ok((trivia, rem))
+    }
+}
+
+fn fill<'a, K>(text: &'a str, elements: Vec<SyntaxElement<'a, K>>) -> Vec<SyntaxElement<'a, K>> {
+    let mut children = Vec::with_capacity(elements.len());
+    let mut pos = 0;
+
+    for element in elements {
+        if let Some(start) = offset(text, element.text()) {
+            if start > pos {
+                gap(&text[pos..start], &mut children);
+            }
+
+            pos = pos.max(start + element.text().len());
+        }
+
+        children.push(element);
+    }
+
+    if pos < text.len() {
+        gap(&text[pos..], &mut children);
+    }
+
+    children
+}
+
+fn gap<'a, K>(mut text: &'a str, children: &mut Vec<SyntaxElement<'a, K>>) {
+    while let Some(ch) = text.chars().next() {
+        let blank = ch.is_whitespace();
+        let len = text
+            .find(|ch: char| ch.is_whitespace() != blank)
+            .unwrap_or(text.len());
+        let (part, rest) = text.split_at(len);
+
+        children.push(match blank {
+            true => SyntaxElement::Trivia(SyntaxTrivia {
+                kind: None,
+                text: part,
+            }),
+            false => SyntaxElement::Text(part),
+        });
+        text = rest;
+    }
+}
+
+fn span(source: &str, text: &str) -> Option<Span> {
+    let start = offset(source, text)?;
+
+    Some(Span::new(source, start, start + text.len()))
+}
+
+pub trait IntoSyntax<'a, K> {
+    fn collect_into(self, elements: &mut Vec<SyntaxElement<'a, K>>);
+}
+
+impl<'a, K> IntoSyntax<'a, K> for () {
+    fn collect_into(self, _: &mut Vec<SyntaxElement<'a, K>>) {}
+}
+
+impl<'a, K> IntoSyntax<'a, K> for char {
+    fn collect_into(self, _: &mut Vec<SyntaxElement<'a, K>>) {}
+}
+
+impl<'a, K> IntoSyntax<'a, K> for &'a str {
+    fn collect_into(self, _: &mut Vec<SyntaxElement<'a, K>>) {}
+}
+
+impl<'a, K> IntoSyntax<'a, K> for SyntaxElement<'a, K> {
+    fn collect_into(self, elements: &mut Vec<SyntaxElement<'a, K>>) {
+        elements.push(self);
+    }
+}
+
+impl<'a, K> IntoSyntax<'a, K> for SyntaxNode<'a, K> {
+    fn collect_into(self, elements: &mut Vec<SyntaxElement<'a, K>>) {
+        elements.push(SyntaxElement::Node(self));
+    }
+}
+
+impl<'a, K> IntoSyntax<'a, K> for SyntaxToken<'a, K> {
+    fn collect_into(self, elements: &mut Vec<SyntaxElement<'a, K>>) {
+        elements.push(SyntaxElement::Token(self));
+    }
+}
+
+impl<'a, K> IntoSyntax<'a, K> for SyntaxTrivia<'a, K> {
+    fn collect_into(self, elements: &mut Vec<SyntaxElement<'a, K>>) {
+        elements.push(SyntaxElement::Trivia(self));
+    }
+}
+
+impl<'a, K, T> IntoSyntax<'a, K> for Option<T>
+where
+    T: IntoSyntax<'a, K>,
+{
+    fn collect_into(self, elements: &mut Vec<SyntaxElement<'a, K>>) {
+        if let Some(inner) = self {
+            inner.collect_into(elements);
+        }
+    }
+}
+
+impl<'a, K, T> IntoSyntax<'a, K> for Vec<T>
+where
+    T: IntoSyntax<'a, K>,
+{
+    fn collect_into(self, elements: &mut Vec<SyntaxElement<'a, K>>) {
+        for item in self {
+            item.collect_into(elements);
+        }
+    }
+}
+
+macro_rules! impl_into_syntax {
+    ($(($idx:tt, $T:ident),)+) => {
+        impl_into_syntax!(@iter $(($idx, $T),)+;);
+    };
+
+    (@iter ($idx:tt, $T:ident),; $(($i:tt, $U:ident),)*) => {
+        impl_into_syntax!(@impl $(($i, $U),)* ($idx, $T),);
+    };
+
+    (@iter ($idx:tt, $T:ident), $(($a:tt, $b:ident),)+; $(($i:tt, $U:ident),)*) => {
+        impl_into_syntax!(@impl $(($i, $U),)* ($idx, $T),);
+        impl_into_syntax!(@iter $(($a, $b),)*; $(($i, $U),)* ($idx, $T),);
+    };
+
+    (@impl $(($idx:tt, $T:ident),)+) => {
+        impl<'a, Kind, $($T,)+> IntoSyntax<'a, Kind> for ($($T,)+)
+        where
+            $($T: IntoSyntax<'a, Kind>,)+
+        {
+            fn collect_into(self, elements: &mut Vec<SyntaxElement<'a, Kind>>) {
+                $(self.$idx.collect_into(elements);)+
+            }
+        }
+    };
+}
+
+impl_into_syntax! {
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F),
+    (6, G),
+    (7, H),
+    (8, I),
+    (9, J),
+    (10, K),
+    (11, L),
+    (12, M),
+    (13, N),
+    (14, P),
+    (15, Q),
+    (16, R),
+    (17, S),
+    (18, T),
+    (19, U),
+    (20, V),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::branch::optional;
+    use crate::combinator::series::{leading, list, trailing};
+    use crate::parser::parse;
+    use crate::sequence::{alphabetic, decimal, whitespace_opt};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Kind {
+        Root,
+        Assign,
+        Ident,
+        Number,
+        Comment,
+    }
+
+    fn assign<'a>() -> impl Parser<'a, SyntaxNode<'a, Kind>> {
+        syntax_node(
+            Kind::Assign,
+            (
+                syntax_token(Kind::Ident, alphabetic),
+                whitespace_opt,
+                '=',
+                whitespace_opt,
+                syntax_token(Kind::Number, decimal),
+                optional(leading(
+                    whitespace_opt,
+                    syntax_trivia(Kind::Comment, ("#", crate::parser::take_till('\n'))),
+                )),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_syntax_node() {
+        let source = "a = 1";
+        let (node, rem) = parse(source, assign()).unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(node.kind, Kind::Assign);
+        assert_eq!(node.text, source);
+        assert_eq!(
+            node.children,
+            vec![
+                SyntaxElement::Token(SyntaxToken {
+                    kind: Kind::Ident,
+                    text: &source[0..1],
+                }),
+                SyntaxElement::Trivia(SyntaxTrivia {
+                    kind: None,
+                    text: &source[1..2],
+                }),
+                SyntaxElement::Text(&source[2..3]),
+                SyntaxElement::Trivia(SyntaxTrivia {
+                    kind: None,
+                    text: &source[3..4],
+                }),
+                SyntaxElement::Token(SyntaxToken {
+                    kind: Kind::Number,
+                    text: &source[4..5],
+                }),
+            ]
+        );
+        assert_eq!(node.tokens().count(), 2);
+        assert_eq!(node.children[4].span(source), Some(Span::new(source, 4, 5)));
+        assert_eq!(node.children[4].span(&source[..2]), None);
+
+        let attached = node.attached(TriviaPolicy::Preceding);
+
+        assert_eq!(
+            attached
+                .iter()
+                .map(|attached| attached.element.text())
+                .collect::<Vec<_>>(),
+            vec!["a", "=", "1"]
+        );
+        assert_eq!(texts(&attached[1].trailing), vec![" "]);
+    }
+
+    #[test]
+    fn test_syntax_node_lossless() {
+        let source = "a=1 # one\n  bc = 22\n\n";
+        let root = syntax_node(
+            Kind::Root,
+            (whitespace_opt, list(trailing(assign(), whitespace_opt), ())),
+        );
+        let (node, rem) = parse(source, root).unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(node.to_text(), source);
+        assert_eq!(node.nodes().count(), 2);
+        assert_eq!(
+            node.nodes().nth(1).unwrap().span(source),
+            Some(Span::new(source, 12, 19))
+        );
+        assert_eq!(
+            node.nodes().next().unwrap().children.last(),
+            Some(&SyntaxElement::Trivia(SyntaxTrivia {
+                kind: Some(Kind::Comment),
+                text: &source[4..9],
+            }))
+        );
+    }
+
+    fn texts<'a>(trivia: &[&SyntaxTrivia<'a, Kind>]) -> Vec<&'a str> {
+        trivia.iter().map(|trivia| trivia.text).collect()
+    }
+
+    #[test]
+    fn test_attached() {
+        let source = "# header\na = 1 # one\n# two\nb = 2\n";
+        let comment = || syntax_trivia(Kind::Comment, ("#", crate::parser::take_till('\n')));
+        let item =
+            || crate::combinator::branch::branch((map_node(assign()), map_trivia(comment())));
+        let root = syntax_node(Kind::Root, list(trailing(item(), whitespace_opt), ()));
+        let (node, _) = parse(source, root).unwrap();
+        let attached = node.attached(TriviaPolicy::SameLine);
+
+        assert_eq!(attached.len(), 2);
+        assert_eq!(texts(&attached[0].leading), vec!["# header", "\n"]);
+        assert_eq!(attached[0].element.text(), "a = 1 # one");
+        assert_eq!(texts(&attached[0].trailing), vec!["\n"]);
+        assert_eq!(texts(&attached[1].leading), vec!["# two", "\n"]);
+        assert_eq!(texts(&attached[1].trailing), vec!["\n"]);
+
+        let attached = node.attached(TriviaPolicy::Following);
+
+        assert_eq!(texts(&attached[1].leading), vec!["\n", "# two", "\n"]);
+        assert_eq!(texts(&attached[1].trailing), vec!["\n"]);
+
+        let attached = node.attached(TriviaPolicy::Preceding);
+
+        assert_eq!(texts(&attached[0].leading), vec!["# header", "\n"]);
+        assert_eq!(texts(&attached[0].trailing), vec!["\n", "# two", "\n"]);
+        assert!(attached[1].leading.is_empty());
+    }
+
+    fn map_node<'a>(
+        parser: impl Parser<'a, SyntaxNode<'a, Kind>>,
+    ) -> impl Parser<'a, SyntaxElement<'a, Kind>> {
+        crate::combinator::map(parser, SyntaxElement::Node)
+    }
+
+    fn map_trivia<'a>(
+        parser: impl Parser<'a, SyntaxTrivia<'a, Kind>>,
+    ) -> impl Parser<'a, SyntaxElement<'a, Kind>> {
+        crate::combinator::map(parser, SyntaxElement::Trivia)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::error::Error;
+use crate::parser::{offset, Parser};
+use crate::position::{Position, Span};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Checkpoint<'a>(&'a str);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cursor<'a> {
+    source: &'a str,
+    rem: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            rem: source,
+        }
+    }
+
+    pub fn at(source: &'a str, input: &'a str) -> Option<Self> {
+        match offset(source, input) {
+            Some(offset) if offset + input.len() == source.len() => {
+                Some(Self { source, rem: input })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub fn remaining(&self) -> &'a str {
+        self.rem
+    }
+
+    pub fn offset(&self) -> usize {
+        self.source.len() - self.rem.len()
+    }
+
+    pub fn position(&self) -> Position {
+        Position::new(self.source, self.offset())
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.rem.is_empty()
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint<'a> {
+        Checkpoint(self.rem)
+    }
+
+    pub fn rewind(&mut self, checkpoint: Checkpoint<'a>) {
+        self.rem = checkpoint.0;
+    }
+
+    pub fn consumed_since(&self, checkpoint: Checkpoint<'a>) -> &'a str {
+        &checkpoint.0[..checkpoint.0.len().saturating_sub(self.rem.len())]
+    }
+
+    pub fn span_since(&self, checkpoint: Checkpoint<'a>) -> Span {
+        Span::new(
+            self.source,
+            self.source.len() - checkpoint.0.len(),
+            self.offset(),
+        )
+    }
+
+    pub fn expect<O>(&mut self, parser: impl Parser<'a, O>) -> Result<O, Error> {
+        let (out, rem) = parser.parse(self.rem)?;
+
+        self.rem = rem;
+
+        Ok(out)
+    }
+
+    pub fn maybe<O>(&mut self, parser: impl Parser<'a, O>) -> Result<Option<O>, Error> {
+        match parser.parse(self.rem) {
+            Ok((out, rem)) => {
+                self.rem = rem;
+                Ok(Some(out))
+            }
+            Err(Error::Pass(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn peek<O>(&self, parser: impl Parser<'a, O>) -> Result<O, Error> {
+        parser.parse(self.rem).map(|(out, _)| out)
+    }
+
+    pub fn matches<O>(&self, parser: impl Parser<'a, O>) -> bool {
+        parser.parse(self.rem).is_ok()
+    }
+}
+
+pub fn cursor<'a, O, F>(f: F) -> impl Parser<'a, O>
+where
+    F: Fn(&mut Cursor<'a>) -> Result<O, Error>,
+{
+    move |input: &'a str| {
+        let mut cursor = Cursor::new(input);
+        let out = f(&mut cursor)?;
+
+        Ok((out, cursor.rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::series::{pair, trailing};
+    use crate::parser::parse;
+    use crate::sequence::{alphabetic, decimal, whitespace_opt, Sequence};
+
+    #[test]
+    fn test_cursor() {
+        let source = "let x = 42;";
+        let mut cursor = Cursor::new(source);
+
+        assert_eq!(cursor.expect(trailing("let", ' ')), Ok("let"));
+
+        let start = cursor.checkpoint();
+
+        assert_eq!(cursor.expect(alphabetic), Ok("x"));
+        assert_eq!(cursor.consumed_since(start), "x");
+        assert_eq!(cursor.span_since(start), Span::new(source, 4, 5));
+
+        let ahead = cursor.checkpoint();
+
+        cursor.rewind(start);
+
+        assert_eq!(cursor.consumed_since(ahead), "");
+        assert_eq!(cursor.span_since(ahead), Span::new(source, 5, 5));
+
+        cursor.rewind(ahead);
+        assert_eq!(cursor.maybe(':'), Ok(None));
+        assert_eq!(cursor.expect(" = "), Ok(" = "));
+        assert_eq!(
+            cursor.expect(alphabetic),
+            Err(Error::expect(Sequence::Alphabetic).but_found('4'))
+        );
+        assert_eq!(cursor.offset(), 8);
+        assert_eq!(cursor.peek(decimal), Ok("42"));
+        assert!(cursor.matches(decimal));
+
+        cursor.rewind(start);
+
+        assert_eq!(cursor.remaining(), "x = 42;");
+        assert_eq!(cursor.position(), Position::new(source, 4));
+        assert!(!cursor.is_at_end());
+        assert!(Cursor::at(source, &source[11..]).unwrap().is_at_end());
+        assert_eq!(Cursor::at(source, &source[..3]), None);
+        assert_eq!(Cursor::at(&source[4..], source), None);
+    }
+
+    #[test]
+    fn test_cursor_parser() {
+        let call = || {
+            cursor(|cursor| {
+                let name = cursor.expect(alphabetic)?;
+                let mut args = Vec::new();
+
+                cursor.expect('(')?;
+
+                while cursor.maybe(')')?.is_none() {
+                    if !args.is_empty() {
+                        cursor.expect(pair(',', whitespace_opt))?;
+                    }
+
+                    args.push(cursor.expect(decimal)?);
+                }
+
+                Ok((name, args))
+            })
+        };
+
+        assert_eq!(
+            parse("max(1, 2,3);", call()),
+            Ok((("max", vec!["1", "2", "3"]), ";"))
+        );
+        assert_eq!(parse("nil()", call()), Ok((("nil", vec![]), "")));
+        assert_eq!(
+            parse("max(1 2)", call()),
+            Err(Error::expect(',').but_found(' '))
+        );
+    }
+}
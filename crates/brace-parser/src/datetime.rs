@@ -0,0 +1,423 @@
+use std::ops::RangeInclusive;
+
+use crate::error::Error;
+use crate::parser::{Output, Parser};
+use crate::sequence::Sequence;
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    pub year: Option<i32>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub weekday: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub second: Option<u8>,
+    pub nanosecond: Option<u32>,
+    pub offset: Option<i32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Field {
+    Year,
+    ShortYear,
+    Month,
+    MonthName,
+    Day,
+    PaddedDay,
+    Weekday,
+    Hour,
+    Hour12,
+    Meridiem,
+    Minute,
+    Second,
+    Fraction,
+    Offset,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Item {
+    Literal(String),
+    Field(Field),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    items: Vec<Item>,
+}
+
+pub fn pattern(format: &str) -> Result<Pattern, Error> {
+    let mut items = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            literal.push(ch);
+            continue;
+        }
+
+        let fields: &[Field] = match chars.next() {
+            Some('%') => {
+                literal.push('%');
+                continue;
+            }
+            Some('Y') => &[Field::Year],
+            Some('y') => &[Field::ShortYear],
+            Some('m') => &[Field::Month],
+            Some('b') | Some('B') | Some('h') => &[Field::MonthName],
+            Some('d') => &[Field::Day],
+            Some('e') => &[Field::PaddedDay],
+            Some('a') | Some('A') => &[Field::Weekday],
+            Some('H') => &[Field::Hour],
+            Some('I') => &[Field::Hour12],
+            Some('p') => &[Field::Meridiem],
+            Some('M') => &[Field::Minute],
+            Some('S') => &[Field::Second],
+            Some('f') => &[Field::Fraction],
+            Some('z') => &[Field::Offset],
+            Some('F') => return compose(format, "%Y-%m-%d", items, literal, chars.as_str()),
+            Some('T') => return compose(format, "%H:%M:%S", items, literal, chars.as_str()),
+            Some('R') => return compose(format, "%H:%M", items, literal, chars.as_str()),
+            Some('D') => return compose(format, "%m/%d/%y", items, literal, chars.as_str()),
+            Some(ch) => {
+                return Err(Error::invalid()
+                    .with_context(format!("unknown specifier `%{}` in `{}`", ch, format)))
+            }
+            None => {
+                return Err(
+                    Error::invalid().with_context(format!("incomplete specifier in `{}`", format))
+                )
+            }
+        };
+
+        if !literal.is_empty() {
+            items.push(Item::Literal(std::mem::take(&mut literal)));
+        }
+
+        items.extend(fields.iter().copied().map(Item::Field));
+    }
+
+    if !literal.is_empty() {
+        items.push(Item::Literal(literal));
+    }
+
+    Ok(Pattern { items })
+}
+
+fn compose(
+    format: &str,
+    expansion: &str,
+    mut items: Vec<Item>,
+    literal: String,
+    rest: &str,
+) -> Result<Pattern, Error> {
+    let rest = pattern(&format!("{}{}", expansion, rest))
+        .map_err(|_| Error::invalid().with_context(format!("invalid specifier in `{}`", format)))?;
+
+    if !literal.is_empty() {
+        items.push(Item::Literal(literal));
+    }
+
+    for item in rest.items {
+        match (items.last_mut(), item) {
+            (Some(Item::Literal(last)), Item::Literal(lit)) => last.push_str(&lit),
+            (_, item) => items.push(item),
+        }
+    }
+
+    Ok(Pattern { items })
+}
+
+impl<'a> Parser<'a, DateTime> for Pattern {
+    fn parse(&self, input: &'a str) -> Output<'a, DateTime> {
+        let mut out = DateTime::default();
+        let mut pm = None;
+        let mut rem = input;
+
+        for item in &self.items {
+            let field = match item {
+                Item::Literal(lit) => {
+                    rem = Parser::parse(&lit.as_str(), rem)?.1;
+                    continue;
+                }
+                Item::Field(field) => *field,
+            };
+
+            rem = match field {
+                Field::Year => store(&mut out.year, number(rem, 4, 0..=9999, "year")?),
+                Field::ShortYear => {
+                    let (year, rem) = number(rem, 2, 0..=99, "year")?;
+                    let year = match year {
+                        69..=99 => 1900 + year,
+                        _ => 2000 + year,
+                    };
+
+                    store(&mut out.year, (year, rem))
+                }
+                Field::Month => store(&mut out.month, number(rem, 2, 1..=12, "month")?),
+                Field::MonthName => store(&mut out.month, name(rem, &MONTHS, "month name")?),
+                Field::Day => store(&mut out.day, number(rem, 2, 1..=31, "day")?),
+                Field::PaddedDay => {
+                    let rem = rem.strip_prefix(' ').unwrap_or(rem);
+
+                    store(&mut out.day, number(rem, 2, 1..=31, "day")?)
+                }
+                Field::Weekday => store(&mut out.weekday, name(rem, &WEEKDAYS, "weekday name")?),
+                Field::Hour => store(&mut out.hour, number(rem, 2, 0..=23, "hour")?),
+                Field::Hour12 => store(&mut out.hour, number(rem, 2, 1..=12, "hour")?),
+                Field::Meridiem => store(&mut pm, meridiem(rem)?),
+                Field::Minute => store(&mut out.minute, number(rem, 2, 0..=59, "minute")?),
+                Field::Second => store(&mut out.second, number(rem, 2, 0..=60, "second")?),
+                Field::Fraction => store(&mut out.nanosecond, fraction(rem)?),
+                Field::Offset => store(&mut out.offset, offset(rem)?),
+            };
+        }
+
+        out.hour = match (out.hour, pm) {
+            (Some(12), Some(false)) => Some(0),
+            (Some(hour), Some(true)) if hour < 12 => Some(hour + 12),
+            (hour, _) => hour,
+        };
+
+        if let (Some(month), Some(day)) = (out.month, out.day) {
+            if day > days_in_month(out.year, month) {
+                return Err(Error::invalid().with_context("day"));
+            }
+        }
+
+        Ok((out, rem))
+    }
+}
+
+fn store<'a, T>(slot: &mut Option<T>, (value, rem): (T, &'a str)) -> &'a str {
+    *slot = Some(value);
+    rem
+}
+
+fn missing(input: &str, expect: Sequence) -> Error {
+    match input.chars().next() {
+        Some(ch) => Error::expect(expect).but_found(ch),
+        None => Error::expect(expect).but_found_end(),
+    }
+}
+
+fn number<'a, T>(
+    input: &'a str,
+    width: usize,
+    range: RangeInclusive<u32>,
+    name: &str,
+) -> Output<'a, T>
+where
+    T: std::convert::TryFrom<u32>,
+{
+    let len = input
+        .bytes()
+        .take(width)
+        .take_while(u8::is_ascii_digit)
+        .count();
+
+    if len == 0 {
+        return Err(missing(input, Sequence::Decimal));
+    }
+
+    match input[..len].parse::<u32>() {
+        Ok(value) if range.contains(&value) => match T::try_from(value) {
+            Ok(value) => Ok((value, &input[len..])),
+            Err(_) => Err(Error::invalid().with_context(name)),
+        },
+        _ => Err(Error::invalid().with_context(name)),
+    }
+}
+
+fn name<'a>(input: &'a str, names: &[&str], expected: &str) -> Output<'a, u8> {
+    for (idx, name) in names.iter().enumerate() {
+        for len in &[name.len(), 3] {
+            if let Some(prefix) = input.get(..*len) {
+                if prefix.eq_ignore_ascii_case(&name[..*len]) {
+                    return Ok((idx as u8 + 1, &input[*len..]));
+                }
+            }
+        }
+    }
+
+    Err(missing(input, Sequence::custom(expected)))
+}
+
+fn meridiem(input: &str) -> Output<bool> {
+    match input.get(..2) {
+        Some(text) if text.eq_ignore_ascii_case("am") => Ok((false, &input[2..])),
+        Some(text) if text.eq_ignore_ascii_case("pm") => Ok((true, &input[2..])),
+        _ => Err(missing(input, Sequence::custom("AM or PM"))),
+    }
+}
+
+fn fraction(input: &str) -> Output<u32> {
+    let len = input.bytes().take_while(u8::is_ascii_digit).count();
+
+    if len == 0 {
+        return Err(missing(input, Sequence::Decimal));
+    }
+
+    let digits = &input[..len.min(9)];
+    let nanos = digits.parse::<u32>().unwrap_or(0) * 10u32.pow(9 - digits.len() as u32);
+
+    Ok((nanos, &input[len..]))
+}
+
+fn offset(input: &str) -> Output<i32> {
+    let (sign, rem) = match input.chars().next() {
+        Some('Z') | Some('z') => return Ok((0, &input[1..])),
+        Some('+') => (1, &input[1..]),
+        Some('-') => (-1, &input[1..]),
+        _ => return Err(missing(input, Sequence::custom("UTC offset"))),
+    };
+    let (hours, rem) = number::<i32>(rem, 2, 0..=23, "offset")?;
+    let rem = rem.strip_prefix(':').unwrap_or(rem);
+    let (minutes, rem) = number::<i32>(rem, 2, 0..=59, "offset")?;
+
+    Ok((sign * (hours * 3600 + minutes * 60), rem))
+}
+
+fn days_in_month(year: Option<i32>, month: u8) -> u8 {
+    match month {
+        2 => match year {
+            Some(year) if year % 4 != 0 || year % 100 == 0 && year % 400 != 0 => 28,
+            _ => 29,
+        },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_pattern() {
+        let iso = || pattern("%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            parse("2024-02-29 13:05:09 ok", iso()),
+            Ok((
+                DateTime {
+                    year: Some(2024),
+                    month: Some(2),
+                    day: Some(29),
+                    hour: Some(13),
+                    minute: Some(5),
+                    second: Some(9),
+                    ..DateTime::default()
+                },
+                " ok"
+            ))
+        );
+        assert_eq!(
+            parse("2023-02-29 13:05:09", iso()),
+            Err(Error::invalid().with_context("day"))
+        );
+        assert_eq!(
+            parse("2024-13-01 00:00:00", iso()),
+            Err(Error::invalid().with_context("month"))
+        );
+        assert_eq!(
+            parse("2024-01-01T00:00:00", iso()),
+            Err(Error::expect(' ').but_found('T'))
+        );
+        assert_eq!(
+            parse("24-01-01", iso()),
+            Err(Error::expect(' ').but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_pattern_names() {
+        let clf = || pattern("%d/%b/%Y:%T %z").unwrap();
+
+        assert_eq!(
+            parse("10/Oct/2000:13:55:36 -0700]", clf()),
+            Ok((
+                DateTime {
+                    year: Some(2000),
+                    month: Some(10),
+                    day: Some(10),
+                    hour: Some(13),
+                    minute: Some(55),
+                    second: Some(36),
+                    offset: Some(-7 * 3600),
+                    ..DateTime::default()
+                },
+                "]"
+            ))
+        );
+        assert_eq!(
+            parse("10/Foo/2000:13:55:36 -0700", clf()),
+            Err(Error::expect(Sequence::custom("month name")).but_found('F'))
+        );
+
+        let (out, rem) = parse(
+            "Tuesday, march 5 07:30:00.25 PM+05:30",
+            pattern("%A, %B %e %I:%M:%S.%f %p%z").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(out.weekday, Some(2));
+        assert_eq!(out.month, Some(3));
+        assert_eq!(out.day, Some(5));
+        assert_eq!(out.hour, Some(19));
+        assert_eq!(out.nanosecond, Some(250_000_000));
+        assert_eq!(out.offset, Some(5 * 3600 + 30 * 60));
+        assert_eq!(
+            parse("12:00 am", pattern("%I:%M %p").unwrap()).map(|(out, _)| out.hour),
+            Ok(Some(0))
+        );
+        assert_eq!(
+            parse("01/02/70 10%", pattern("%D %H%%").unwrap()).map(|(out, _)| out.year),
+            Ok(Some(1970))
+        );
+    }
+
+    #[test]
+    fn test_pattern_error() {
+        assert_eq!(
+            pattern("%Y-%Q"),
+            Err(Error::invalid().with_context("unknown specifier `%Q` in `%Y-%Q`"))
+        );
+        assert_eq!(
+            pattern("%H%"),
+            Err(Error::invalid().with_context("incomplete specifier in `%H%`"))
+        );
+        assert_eq!(
+            pattern("%F%"),
+            Err(Error::invalid().with_context("invalid specifier in `%F%`"))
+        );
+    }
+}
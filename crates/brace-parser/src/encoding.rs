@@ -0,0 +1,240 @@
+use std::char;
+
+use crate::error::Error;
+use crate::parser::offset;
+use crate::position::{Position, Span};
+
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Latin1,
+    Windows1252,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl Encoding {
+    pub fn sniff(input: &[u8]) -> Option<Self> {
+        match input {
+            [0xFF, 0xFE, ..] => Some(Self::Utf16Le),
+            [0xFE, 0xFF, ..] => Some(Self::Utf16Be),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Latin1 => "ISO-8859-1",
+            Self::Windows1252 => "windows-1252",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decoded<'a> {
+    original: &'a [u8],
+    encoding: Encoding,
+    text: String,
+    offsets: Vec<(usize, usize)>,
+}
+
+impl<'a> Decoded<'a> {
+    pub fn new(original: &'a [u8], encoding: Encoding) -> Result<Self, Error> {
+        let mut text = String::with_capacity(original.len());
+        let mut offsets = Vec::with_capacity(original.len());
+
+        match encoding {
+            Encoding::Latin1 | Encoding::Windows1252 => {
+                for (idx, byte) in original.iter().enumerate() {
+                    let ch = match (encoding, byte) {
+                        (Encoding::Windows1252, 0x80..=0x9F) => WINDOWS_1252[*byte as usize - 0x80],
+                        _ => char::from(*byte),
+                    };
+
+                    offsets.push((text.len(), idx));
+                    text.push(ch);
+                }
+            }
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                let bom = match Encoding::sniff(original) {
+                    Some(sniffed) if sniffed == encoding => 2,
+                    _ => 0,
+                };
+                let units = original[bom..]
+                    .chunks(2)
+                    .map(|pair| match (encoding, pair) {
+                        (_, [byte]) => Err(*byte),
+                        (Encoding::Utf16Le, [lo, hi]) => Ok(u16::from_le_bytes([*lo, *hi])),
+                        (_, pair) => Ok(u16::from_be_bytes([pair[0], pair[1]])),
+                    });
+                let mut idx = bom;
+
+                for unit in char::decode_utf16(units.map_while(Result::ok)) {
+                    let ch = unit.map_err(|_| invalid(encoding, idx))?;
+
+                    offsets.push((text.len(), idx));
+                    text.push(ch);
+                    idx += ch.len_utf16() * 2;
+                }
+
+                if idx < original.len() {
+                    return Err(invalid(encoding, idx));
+                }
+            }
+        }
+
+        Ok(Self {
+            original,
+            encoding,
+            text,
+            offsets,
+        })
+    }
+
+    pub fn sniffed(original: &'a [u8], fallback: Encoding) -> Result<Self, Error> {
+        Self::new(original, Encoding::sniff(original).unwrap_or(fallback))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn original(&self) -> &'a [u8] {
+        self.original
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn original_offset(&self, offset: usize) -> usize {
+        match self
+            .offsets
+            .binary_search_by_key(&offset, |(decoded, _)| *decoded)
+        {
+            Ok(idx) => self.offsets[idx].1,
+            Err(idx) => match self.offsets.get(idx) {
+                Some((_, original)) => *original,
+                None => self.original.len(),
+            },
+        }
+    }
+
+    pub fn offset(&self, remainder: &str) -> Option<usize> {
+        offset(&self.text, remainder).map(|offset| self.original_offset(offset))
+    }
+
+    pub fn position(&self, remainder: &str) -> Option<Position> {
+        let offset = offset(&self.text, remainder)?;
+
+        Some(self.map_position(Position::new(&self.text, offset)))
+    }
+
+    pub fn map_position(&self, position: Position) -> Position {
+        Position {
+            offset: self.original_offset(position.offset),
+            ..position
+        }
+    }
+
+    pub fn map_span(&self, span: Span) -> Span {
+        Span {
+            start: self.map_position(span.start),
+            end: self.map_position(span.end),
+        }
+    }
+
+    pub fn map_error(&self, err: Error) -> Error {
+        match err.get_span() {
+            Some(span) => {
+                let span = self.map_span(*span);
+
+                err.with_span(span)
+            }
+            None => err,
+        }
+    }
+}
+
+fn invalid(encoding: Encoding, offset: usize) -> Error {
+    Error::invalid().with_context(format!("{} input at byte {}", encoding.name(), offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::spanned;
+    use crate::parser::{parse, take_while, Parser};
+
+    #[test]
+    fn test_decoded_single_byte() {
+        let bytes = b"caf\xE9 \x80 \x93ok\x94";
+        let latin1 = Decoded::new(bytes, Encoding::Latin1).unwrap();
+        let windows = Decoded::new(bytes, Encoding::Windows1252).unwrap();
+
+        assert_eq!(latin1.as_str(), "café \u{80} \u{93}ok\u{94}");
+        assert_eq!(windows.as_str(), "café € “ok”");
+        assert_eq!(windows.encoding(), Encoding::Windows1252);
+        assert_eq!(windows.original(), &bytes[..]);
+
+        let (_, rem) = parse(windows.as_str(), "café € “").unwrap();
+
+        assert_eq!(windows.offset(rem), Some(8));
+        assert_eq!(windows.original_offset(windows.as_str().len()), bytes.len());
+    }
+
+    #[test]
+    fn test_decoded_utf16() {
+        let mut le = vec![0xFF, 0xFE];
+        let mut be = Vec::new();
+
+        for unit in "a😀b".encode_utf16() {
+            le.extend_from_slice(&unit.to_le_bytes());
+            be.extend_from_slice(&unit.to_be_bytes());
+        }
+
+        let decoded = Decoded::sniffed(&le, Encoding::Latin1).unwrap();
+
+        assert_eq!(decoded.encoding(), Encoding::Utf16Le);
+        assert_eq!(decoded.as_str(), "a😀b");
+        assert_eq!(decoded.offset(&decoded.as_str()[5..]), Some(8));
+        assert_eq!(
+            Decoded::new(&be, Encoding::Utf16Be).map(|decoded| decoded.text),
+            Ok("a😀b".to_owned())
+        );
+        assert_eq!(
+            Decoded::new(&[0x61, 0x00, 0x00, 0xD8, 0x62, 0x00], Encoding::Utf16Le),
+            Err(Error::invalid().with_context("UTF-16LE input at byte 2"))
+        );
+        assert_eq!(
+            Decoded::new(&[0x00, 0x61, 0x00], Encoding::Utf16Be),
+            Err(Error::invalid().with_context("UTF-16BE input at byte 2"))
+        );
+    }
+
+    #[test]
+    fn test_decoded_map_error() {
+        let bytes = b"\xAB\xAB\nna\xEFve 1";
+        let decoded = Decoded::new(bytes, Encoding::Latin1).unwrap();
+        let input = decoded.as_str();
+        let (_, rem) = parse(input, "««\n").unwrap();
+        let span = spanned(input, take_while(char::is_alphabetic))
+            .parse(rem)
+            .unwrap()
+            .0
+            .span;
+        let err = decoded.map_error(Error::invalid().with_span(span));
+
+        assert_eq!(err.get_span().map(|span| span.start.offset), Some(3));
+        assert_eq!(err.get_span().map(|span| span.end.offset), Some(8));
+        assert_eq!(err.get_span().map(|span| span.start.line), Some(2));
+        assert_eq!(decoded.position(rem).unwrap().offset, 3);
+        assert_eq!(decoded.position(rem).unwrap().column, 1);
+    }
+}
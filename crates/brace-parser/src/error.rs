@@ -2,6 +2,9 @@ use std::error;
 use std::fmt;
 
 use crate::character::Character;
+use crate::limit::Limit;
+use crate::number::Constraint;
+use crate::position::{Position, Span};
 use crate::sequence::Sequence;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -12,32 +15,131 @@ pub enum Error {
 
 impl Error {
     pub fn invalid() -> Self {
-        Self::Fail(InnerError(Some(Expect::Valid), None, None))
+        Self::Fail(InnerError(
+            Some(Expect::Valid),
+            None,
+            None,
+            None,
+            Vec::new(),
+        ))
     }
 
     pub fn context<T>(ctx: T) -> Self
     where
         T: Into<String>,
     {
-        Self::Pass(InnerError(None, None, Some(ctx.into())))
+        Self::Pass(InnerError(None, None, Some(ctx.into()), None, Vec::new()))
     }
 
     pub fn expect<T>(expect: T) -> Self
     where
         T: Into<Expect>,
     {
-        Self::Pass(InnerError(Some(expect.into()), None, None))
+        Self::Pass(InnerError(
+            Some(expect.into()),
+            None,
+            None,
+            None,
+            Vec::new(),
+        ))
+    }
+
+    pub fn expected_any<I, T>(items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Expect>,
+    {
+        let mut expect = Vec::new();
+
+        for item in items {
+            Expect::push_unique(&mut expect, item.into());
+        }
+
+        match expect.len() {
+            0 => Self::Pass(InnerError(None, None, None, None, Vec::new())),
+            1 => Self::expect(expect.remove(0)),
+            _ => Self::expect(Expect::OneOf(expect)),
+        }
+    }
+
+    pub fn merge(a: Self, b: Self) -> Self {
+        let fail = a.is_fail() || b.is_fail();
+        let (InnerError(ea, fa, ca, sa, mut xa), InnerError(eb, fb, cb, sb, xb)) =
+            (a.into_inner(), b.into_inner());
+        let expect = match (ea, eb) {
+            (Some(ea), Some(eb)) => {
+                let mut items = Vec::new();
+
+                for item in ea.into_items().into_iter().chain(eb.into_items()) {
+                    Expect::push_unique(&mut items, item);
+                }
+
+                match items.len() {
+                    1 => items.pop(),
+                    _ => Some(Expect::OneOf(items)),
+                }
+            }
+            (ea, eb) => ea.or(eb),
+        };
+
+        for suggestion in xb {
+            if !xa.contains(&suggestion) {
+                xa.push(suggestion);
+            }
+        }
+
+        let inner = InnerError(expect, fa.or(fb), ca.or(cb), sa.or(sb), xa);
+
+        match fail {
+            true => Self::Fail(inner),
+            false => Self::Pass(inner),
+        }
+    }
+
+    pub fn get_expected(&self) -> Vec<&Expect> {
+        match self {
+            Self::Pass(inner) | Self::Fail(inner) => match &inner.0 {
+                Some(Expect::OneOf(items)) => items.iter().collect(),
+                Some(expect) => vec![expect],
+                None => Vec::new(),
+            },
+        }
+    }
+
+    fn into_inner(self) -> InnerError {
+        match self {
+            Self::Pass(inner) => inner,
+            Self::Fail(inner) => inner,
+        }
     }
 
     pub fn found<T>(found: T) -> Self
     where
         T: Into<Expect>,
     {
-        Self::Pass(InnerError(None, Some(found.into()), None))
+        Self::Pass(InnerError(None, Some(found.into()), None, None, Vec::new()))
     }
 
     pub fn found_end() -> Self {
-        Self::Pass(InnerError(None, Some(Expect::End), None))
+        Self::Pass(InnerError(None, Some(Expect::End), None, None, Vec::new()))
+    }
+
+    pub fn exceeded(limit: Limit) -> Self {
+        Self::Fail(InnerError(
+            None,
+            Some(Expect::Limit(limit)),
+            None,
+            None,
+            Vec::new(),
+        ))
+    }
+
+    pub fn get_limit(&self) -> Option<Limit> {
+        match self {
+            Self::Pass(InnerError(_, Some(Expect::Limit(limit)), _, _, _)) => Some(*limit),
+            Self::Fail(InnerError(_, Some(Expect::Limit(limit)), _, _, _)) => Some(*limit),
+            _ => None,
+        }
     }
 
     pub fn but_expect<T>(mut self, expect: T) -> Self
@@ -104,6 +206,56 @@ impl Error {
         self
     }
 
+    pub fn with_span(mut self, span: Span) -> Self {
+        match self {
+            Self::Pass(ref mut inner) => inner.3 = Some(Box::new(span)),
+            Self::Fail(ref mut inner) => inner.3 = Some(Box::new(span)),
+        }
+
+        self
+    }
+
+    pub fn get_span(&self) -> Option<&Span> {
+        match self {
+            Self::Pass(inner) => inner.3.as_deref(),
+            Self::Fail(inner) => inner.3.as_deref(),
+        }
+    }
+
+    pub fn with_suggestion<T>(mut self, suggestion: T) -> Self
+    where
+        T: Into<String>,
+    {
+        match self {
+            Self::Pass(ref mut inner) => inner.4.push(suggestion.into()),
+            Self::Fail(ref mut inner) => inner.4.push(suggestion.into()),
+        }
+
+        self
+    }
+
+    pub fn get_suggestions(&self) -> &[String] {
+        match self {
+            Self::Pass(inner) => &inner.4,
+            Self::Fail(inner) => &inner.4,
+        }
+    }
+
+    pub fn render<R>(&self, renderer: &R) -> Result<String, fmt::Error>
+    where
+        R: Renderer + ?Sized,
+    {
+        let mut out = String::new();
+        let inner = match self {
+            Self::Pass(inner) => inner,
+            Self::Fail(inner) => inner,
+        };
+
+        renderer.render_error(&mut out, inner)?;
+
+        Ok(out)
+    }
+
     pub fn is_pass(&self) -> bool {
         match self {
             Self::Pass(_) => true,
@@ -159,43 +311,175 @@ impl fmt::Display for Error {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct InnerError(Option<Expect>, Option<Expect>, Option<String>);
+pub struct InnerError(
+    Option<Expect>,
+    Option<Expect>,
+    Option<String>,
+    Option<Box<Span>>,
+    Vec<String>,
+);
+
+impl InnerError {
+    pub fn get_expected(&self) -> Option<&Expect> {
+        self.0.as_ref()
+    }
+
+    pub fn get_found(&self) -> Option<&Expect> {
+        self.1.as_ref()
+    }
+
+    pub fn get_context(&self) -> Option<&str> {
+        self.2.as_deref()
+    }
+
+    pub fn get_span(&self) -> Option<&Span> {
+        self.3.as_deref()
+    }
+
+    pub fn get_suggestions(&self) -> &[String] {
+        &self.4
+    }
+}
 
 impl fmt::Display for InnerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Error:")?;
+        English.render_error(f, self)
+    }
+}
+
+pub trait Renderer {
+    fn render_error(&self, f: &mut dyn fmt::Write, error: &InnerError) -> fmt::Result {
+        self.render_header(f)?;
+
+        if let Some(ctx) = error.get_context() {
+            self.render_context(f, ctx)?;
+        }
 
-        if let Some(ctx) = &self.2 {
-            write!(f, " in {}", ctx)?;
+        if let Some(span) = error.get_span() {
+            self.render_position(f, &span.start)?;
         }
 
-        if let Some(expect) = &self.0 {
-            write!(f, "\nExpected {}", expect)?;
+        if let Some(expect) = error.get_expected() {
+            self.render_expected(f, expect)?;
         }
 
-        if let Some(found) = &self.1 {
-            write!(f, "\nFound {}", found)?;
+        if let Some(found) = error.get_found() {
+            self.render_found(f, found)?;
         }
 
-        Ok(())
+        match error.get_suggestions() {
+            [] => Ok(()),
+            suggestions => self.render_suggestions(f, suggestions),
+        }
+    }
+
+    fn render_header(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "Error:")
+    }
+
+    fn render_context(&self, f: &mut dyn fmt::Write, ctx: &str) -> fmt::Result {
+        write!(f, " in {}", ctx)
+    }
+
+    fn render_position(&self, f: &mut dyn fmt::Write, position: &Position) -> fmt::Result {
+        write!(f, " at {}", position)
+    }
+
+    fn render_expected(&self, f: &mut dyn fmt::Write, expect: &Expect) -> fmt::Result {
+        write!(f, "\nExpected ")?;
+        self.render_expect(f, expect)
+    }
+
+    fn render_found(&self, f: &mut dyn fmt::Write, found: &Expect) -> fmt::Result {
+        write!(f, "\nFound ")?;
+        self.render_expect(f, found)
+    }
+
+    fn render_expect(&self, f: &mut dyn fmt::Write, expect: &Expect) -> fmt::Result {
+        match expect {
+            Expect::End => write!(f, "end of input"),
+            Expect::Valid => write!(f, "valid parser"),
+            Expect::Character(ch) => write!(f, "character: {}", ch),
+            Expect::Sequence(seq) => write!(f, "sequence: {}", seq),
+            Expect::Limit(limit) => write!(f, "limit: {}", limit),
+            Expect::Constraint(constraint) => write!(f, "{}", constraint),
+            Expect::OneOf(items) => {
+                write!(f, "one of: ")?;
+
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    match item {
+                        Expect::Character(ch) => write!(f, "{}", ch)?,
+                        Expect::Sequence(seq) => write!(f, "{}", seq)?,
+                        item => self.render_expect(f, item)?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn render_suggestions(&self, f: &mut dyn fmt::Write, suggestions: &[String]) -> fmt::Result {
+        let (last, rest) = match suggestions.split_last() {
+            Some(split) => split,
+            None => return Ok(()),
+        };
+
+        write!(f, "\nDid you mean ")?;
+
+        for (idx, suggestion) in rest.iter().enumerate() {
+            match idx {
+                0 => write!(f, "`{}`", suggestion)?,
+                _ => write!(f, ", `{}`", suggestion)?,
+            }
+        }
+
+        match rest.is_empty() {
+            true => write!(f, "`{}`?", last),
+            false => write!(f, " or `{}`?", last),
+        }
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct English;
+
+impl Renderer for English {}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Expect {
     End,
     Valid,
     Character(Character),
     Sequence(Sequence),
+    Limit(Limit),
+    Constraint(Constraint),
+    OneOf(Vec<Expect>),
 }
 
 impl fmt::Display for Expect {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        English.render_expect(f, self)
+    }
+}
+
+impl Expect {
+    fn into_items(self) -> Vec<Expect> {
         match self {
-            Self::End => write!(f, "end of input"),
-            Self::Valid => write!(f, "valid parser"),
-            Self::Character(ch) => write!(f, "character: {}", ch),
-            Self::Sequence(seq) => write!(f, "sequence: {}", seq),
+            Self::OneOf(items) => items,
+            item => vec![item],
+        }
+    }
+
+    fn push_unique(items: &mut Vec<Expect>, item: Expect) {
+        for item in item.into_items() {
+            if !items.contains(&item) {
+                items.push(item);
+            }
         }
     }
 }
@@ -235,3 +519,154 @@ impl From<Sequence> for Expect {
         Self::Sequence(from)
     }
 }
+
+impl From<Constraint> for Expect {
+    fn from(from: Constraint) -> Self {
+        Self::Constraint(from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequence::Sequence;
+
+    struct German;
+
+    impl Renderer for German {
+        fn render_header(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+            write!(f, "Fehler:")
+        }
+
+        fn render_expected(&self, f: &mut dyn fmt::Write, expect: &Expect) -> fmt::Result {
+            write!(f, "\nErwartet: ")?;
+            self.render_expect(f, expect)
+        }
+
+        fn render_found(&self, f: &mut dyn fmt::Write, found: &Expect) -> fmt::Result {
+            write!(f, "\nGefunden: ")?;
+            self.render_expect(f, found)
+        }
+
+        fn render_expect(&self, f: &mut dyn fmt::Write, expect: &Expect) -> fmt::Result {
+            match expect {
+                Expect::End => write!(f, "Ende der Eingabe"),
+                Expect::Character(ch) => write!(f, "Zeichen {}", ch),
+                Expect::Sequence(Sequence::Decimal) => write!(f, "Dezimalzahl"),
+                _ => English.render_expect(f, expect),
+            }
+        }
+    }
+
+    #[test]
+    fn test_expected_any() {
+        let err = Error::expected_any(vec!["identifier", "number", "("]).but_found('}');
+
+        assert_eq!(
+            err,
+            Error::expect(Expect::OneOf(vec![
+                Expect::from("identifier"),
+                Expect::from("number"),
+                Expect::from("("),
+            ]))
+            .but_found('}')
+        );
+        assert_eq!(
+            err.to_string(),
+            "Error:\nExpected one of: \"identifier\", \"number\", \"(\"\nFound character: '}'"
+        );
+        assert_eq!(Error::expected_any(vec!['a', 'a']), Error::expect('a'));
+        assert_eq!(err.get_expected().len(), 3);
+        assert!(Error::expected_any(Vec::<char>::new())
+            .get_expected()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = Error::expect(Sequence::Decimal).but_found('x');
+        let b = Error::expected_any(vec!['(', '-'])
+            .but_found('y')
+            .with_context("term");
+
+        assert_eq!(
+            Error::merge(a.clone(), b.clone()),
+            Error::expect(Expect::OneOf(vec![
+                Expect::from(Sequence::Decimal),
+                Expect::from('('),
+                Expect::from('-'),
+            ]))
+            .but_found('x')
+            .with_context("term")
+        );
+        assert_eq!(Error::merge(a.clone(), Error::expect(Sequence::Decimal)), a);
+        assert!(Error::merge(a.clone(), Error::invalid()).is_fail());
+        assert_eq!(
+            Error::merge(Error::found_end(), a.clone()),
+            Error::expect(Sequence::Decimal).but_found_end()
+        );
+        assert_eq!(
+            Error::merge(a.clone().with_suggestion("1"), b.with_suggestion("1")).get_suggestions(),
+            &["1".to_owned()][..]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let err = Error::expect(Sequence::Decimal)
+            .but_found_end()
+            .with_context("number");
+
+        assert_eq!(err.render(&English), Ok(err.to_string()));
+        assert_eq!(
+            err.render(&English).unwrap(),
+            "Error: in number\nExpected sequence: decimal\nFound end of input"
+        );
+        assert_eq!(
+            err.render(&German).unwrap(),
+            "Fehler: in number\nErwartet: Dezimalzahl\nGefunden: Ende der Eingabe"
+        );
+        assert_eq!(
+            Error::expect(Sequence::Decimal)
+                .but_found('x')
+                .render(&German as &dyn Renderer)
+                .unwrap(),
+            "Fehler:\nErwartet: Dezimalzahl\nGefunden: Zeichen 'x'"
+        );
+    }
+
+    #[test]
+    fn test_render_custom() {
+        struct Terse;
+
+        impl Renderer for Terse {
+            fn render_error(&self, f: &mut dyn fmt::Write, error: &InnerError) -> fmt::Result {
+                match (error.get_expected(), error.get_found()) {
+                    (Some(expect), Some(found)) => write!(f, "{} != {}", expect, found),
+                    _ => write!(f, "{}", error.get_context().unwrap_or("?")),
+                }
+            }
+        }
+
+        struct Broken;
+
+        impl Renderer for Broken {
+            fn render_context(&self, _: &mut dyn fmt::Write, _: &str) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+
+        let err = Error::expect('a')
+            .but_found('b')
+            .with_context("letter")
+            .with_suggestion("a");
+        let inner = err.as_pass().unwrap();
+
+        assert_eq!(inner.get_context(), Some("letter"));
+        assert_eq!(inner.get_suggestions(), &["a".to_owned()][..]);
+        assert_eq!(inner.get_span(), None);
+        assert!(err.render(&Terse).unwrap().contains("!="));
+        assert_eq!(err.render(&Broken), Err(fmt::Error));
+        assert!(Error::expect('a').render(&Broken).is_ok());
+    }
+}
@@ -0,0 +1,740 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::number::decimal_parts;
+use crate::parser::{Output, Parser};
+use crate::sequence::Sequence;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DurationUnits {
+    units: Vec<(String, Duration)>,
+}
+
+impl DurationUnits {
+    pub fn new() -> Self {
+        Self { units: Vec::new() }
+    }
+
+    pub fn unit<T>(mut self, suffix: T, length: Duration) -> Self
+    where
+        T: Into<String>,
+    {
+        let suffix = suffix.into();
+
+        self.units.retain(|(unit, _)| *unit != suffix);
+        self.units.push((suffix, length));
+        self.units
+            .sort_by_key(|(unit, _)| std::cmp::Reverse(unit.len()));
+        self
+    }
+
+    fn find<'a>(&self, input: &'a str) -> Option<(Duration, &'a str)> {
+        self.units
+            .iter()
+            .find_map(|(unit, length)| Some((*length, input.strip_prefix(unit.as_str())?)))
+    }
+}
+
+impl Default for DurationUnits {
+    fn default() -> Self {
+        Self::new()
+            .unit("ns", Duration::from_nanos(1))
+            .unit("us", Duration::from_micros(1))
+            .unit("µs", Duration::from_micros(1))
+            .unit("ms", Duration::from_millis(1))
+            .unit("s", Duration::from_secs(1))
+            .unit("m", Duration::from_secs(60))
+            .unit("h", Duration::from_secs(60 * 60))
+            .unit("d", Duration::from_secs(24 * 60 * 60))
+            .unit("w", Duration::from_secs(7 * 24 * 60 * 60))
+    }
+}
+
+pub fn duration(input: &str) -> Output<Duration> {
+    duration_with(DurationUnits::default()).parse(input)
+}
+
+pub fn duration_with<'a>(units: DurationUnits) -> impl Parser<'a, Duration> {
+    move |input: &'a str| {
+        let mut out = Duration::default();
+        let mut rem = input;
+
+        loop {
+            let ((mantissa, scale), next) = match rem.chars().next() {
+                Some(ch) if ch.is_ascii_digit() => decimal_parts(rem)?,
+                Some(_) | None if rem.len() < input.len() => return Ok((out, rem)),
+                Some(ch) => return Err(Error::expect(Sequence::Decimal).but_found(ch)),
+                None => return Err(Error::expect(Sequence::Decimal).but_found_end()),
+            };
+            let number = &rem[..rem.len() - next.len()];
+            let (length, next) = match units.find(next) {
+                Some(unit) => unit,
+                None => {
+                    let err = Error::expect(Sequence::custom("duration unit"));
+
+                    return Err(match next.chars().next() {
+                        Some(ch) => err.but_found(ch),
+                        None => err.but_found_end(),
+                    });
+                }
+            };
+            let divisor = 10u128.checked_pow(scale).ok_or_else(|| {
+                Error::invalid().with_context(format!("{} has too many fractional digits", number))
+            })?;
+
+            out = (mantissa as u128)
+                .checked_mul(length.as_nanos())
+                .map(|nanos| nanos / divisor)
+                .and_then(|nanos| {
+                    let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+
+                    out.checked_add(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+                })
+                .ok_or_else(|| Error::invalid().with_context("duration overflow"))?;
+            rem = next;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SizeBase {
+    Decimal,
+    Binary,
+}
+
+pub fn byte_size(input: &str) -> Output<u64> {
+    byte_size_with(SizeBase::Decimal).parse(input)
+}
+
+pub fn byte_size_with<'a>(base: SizeBase) -> impl Parser<'a, u64> {
+    move |input: &'a str| {
+        let ((mantissa, scale), rem) = match input.chars().next() {
+            Some(ch) if ch.is_ascii_digit() => decimal_parts(input)?,
+            Some(ch) => return Err(Error::expect(Sequence::Decimal).but_found(ch)),
+            None => return Err(Error::expect(Sequence::Decimal).but_found_end()),
+        };
+        let suffix = rem.trim_start_matches(' ');
+        let len = suffix.bytes().take_while(u8::is_ascii_alphabetic).count();
+        let (multiplier, rem) = match len {
+            0 => (1, rem),
+            _ => match size_unit(&suffix[..len], base) {
+                Some(multiplier) => (multiplier, &suffix[len..]),
+                None if suffix.len() < rem.len() => (1, rem),
+                None => {
+                    return Err(Error::expect(Sequence::custom("byte size unit"))
+                        .but_found(suffix.chars().next().unwrap_or(' ')))
+                }
+            },
+        };
+        let literal = &input[..input.len() - rem.len()];
+        let bytes = (mantissa as u128).checked_mul(multiplier);
+        let divisor = 10u128.checked_pow(scale);
+
+        match (bytes, divisor) {
+            (Some(0), _) => Ok((0, rem)),
+            (Some(bytes), Some(divisor)) if bytes % divisor == 0 => {
+                match u64::try_from(bytes / divisor) {
+                    Ok(bytes) => Ok((bytes, rem)),
+                    Err(_) => {
+                        Err(Error::invalid()
+                            .with_context(format!("{} does not fit in u64", literal)))
+                    }
+                }
+            }
+            (None, _) => {
+                Err(Error::invalid().with_context(format!("{} does not fit in u64", literal)))
+            }
+            _ => Err(Error::invalid()
+                .with_context(format!("{} is not a whole number of bytes", literal))),
+        }
+    }
+}
+
+fn size_unit(suffix: &str, base: SizeBase) -> Option<u128> {
+    if suffix.ends_with('b') {
+        return None;
+    }
+
+    let suffix = suffix.to_ascii_lowercase();
+    let (prefix, binary) = match suffix.as_str() {
+        "b" => return Some(1),
+        _ => match suffix.strip_suffix("ib") {
+            Some(prefix) => (prefix, true),
+            None => match suffix.strip_suffix('i') {
+                Some(prefix) => (prefix, true),
+                None => (
+                    suffix.strip_suffix('b').unwrap_or(&suffix),
+                    base == SizeBase::Binary,
+                ),
+            },
+        },
+    };
+    let exp = match prefix {
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        "p" => 5,
+        "e" => 6,
+        _ => return None,
+    };
+
+    match binary {
+        true => Some(1024u128.pow(exp)),
+        false => Some(1000u128.pow(exp)),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FilePath<'a> {
+    pub prefix: Option<&'a str>,
+    pub absolute: bool,
+    pub components: Vec<&'a str>,
+}
+
+pub fn posix_path(input: &str) -> Output<FilePath> {
+    let len = input.find(char::is_control).unwrap_or(input.len());
+    let path = &input[..len];
+
+    if path.is_empty() {
+        return Err(missing(input, "path"));
+    }
+
+    Ok((
+        FilePath {
+            prefix: None,
+            absolute: path.starts_with('/'),
+            components: components(path, &['/']),
+        },
+        &input[len..],
+    ))
+}
+
+pub fn windows_path(input: &str) -> Output<FilePath> {
+    let len = input
+        .find(|ch: char| ch.is_control() || matches!(ch, '<' | '>' | '"' | '|' | '?' | '*'))
+        .unwrap_or(input.len());
+    let path = &input[..len];
+    let (prefix, unc) = match path.as_bytes() {
+        [b'\\', b'\\', ..] | [b'/', b'/', ..] => {
+            let mut parts = path[2..].splitn(3, &['\\', '/'][..]);
+
+            match (parts.next(), parts.next()) {
+                (Some(server), Some(share)) if !server.is_empty() && !share.is_empty() => {
+                    (Some(&path[..2 + server.len() + 1 + share.len()]), true)
+                }
+                _ => return Err(Error::invalid().with_context("UNC path needs a server and share")),
+            }
+        }
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => (Some(&path[..2]), false),
+        _ => (None, false),
+    };
+    let rest = &path[prefix.map_or(0, str::len)..];
+    let rest = &rest[..rest.find(':').unwrap_or(rest.len())];
+
+    if prefix.is_none() && rest.is_empty() {
+        return Err(missing(input, "path"));
+    }
+
+    Ok((
+        FilePath {
+            prefix,
+            absolute: unc || rest.starts_with(&['\\', '/'][..]),
+            components: components(rest, &['\\', '/']),
+        },
+        &input[prefix.map_or(0, str::len) + rest.len()..],
+    ))
+}
+
+fn components<'a>(path: &'a str, separators: &[char]) -> Vec<&'a str> {
+    path.split(separators)
+        .filter(|component| !component.is_empty())
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GlobToken {
+    Literal(String),
+    AnyChar,
+    AnyRun,
+    AnyPath,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Glob {
+    pub tokens: Vec<GlobToken>,
+}
+
+impl Glob {
+    pub fn matches(&self, path: &str) -> bool {
+        glob_matches(&self.tokens, path)
+    }
+}
+
+pub fn glob(input: &str) -> Output<Glob> {
+    let mut tokens = Vec::new();
+    let mut rem = input;
+
+    loop {
+        let mut chars = rem.chars();
+        let token = match chars.next() {
+            Some(ch) if ch.is_whitespace() || ch.is_control() => break,
+            None => break,
+            Some('*') => match chars.as_str().strip_prefix('*') {
+                Some(rest) => {
+                    chars = rest.chars();
+                    GlobToken::AnyPath
+                }
+                None => GlobToken::AnyRun,
+            },
+            Some('?') => GlobToken::AnyChar,
+            Some('[') => {
+                let (class, rest) = glob_class(chars.as_str())?;
+
+                chars = rest.chars();
+                class
+            }
+            Some('\\') => match chars.next() {
+                Some(ch) => GlobToken::Literal(ch.to_string()),
+                None => {
+                    return Err(Error::expect(Sequence::custom("escaped character"))
+                        .but_found_end()
+                        .into_fail()
+                        .with_context("glob"))
+                }
+            },
+            Some(ch) => GlobToken::Literal(ch.to_string()),
+        };
+
+        match (tokens.last_mut(), token) {
+            (Some(GlobToken::Literal(last)), GlobToken::Literal(lit)) => last.push_str(&lit),
+            (_, token) => tokens.push(token),
+        }
+
+        rem = chars.as_str();
+    }
+
+    match tokens.is_empty() {
+        true => Err(missing(input, "glob")),
+        false => Ok((Glob { tokens }, rem)),
+    }
+}
+
+fn glob_class(input: &str) -> Output<GlobToken> {
+    let (negated, mut rem) = match input.strip_prefix(&['!', '^'][..]) {
+        Some(rest) => (true, rest),
+        None => (false, input),
+    };
+    let mut ranges = Vec::new();
+
+    loop {
+        let mut chars = rem.chars();
+        let lo = match chars.next() {
+            Some(']') if !ranges.is_empty() => {
+                return Ok((GlobToken::Class { negated, ranges }, chars.as_str()))
+            }
+            Some('\\') => chars.next(),
+            ch => ch,
+        };
+        let lo = match lo {
+            Some(ch) => ch,
+            None => {
+                return Err(Error::expect(']')
+                    .but_found_end()
+                    .into_fail()
+                    .with_context("glob"))
+            }
+        };
+        let mut hi = lo;
+
+        if let Some(rest) = chars.as_str().strip_prefix('-') {
+            let mut rest = rest.chars();
+
+            if let Some(ch) = rest.next().filter(|ch| *ch != ']') {
+                hi = ch;
+                chars = rest;
+            }
+        }
+
+        if hi < lo {
+            return Err(
+                Error::invalid().with_context(format!("invalid glob range `{}-{}`", lo, hi))
+            );
+        }
+
+        ranges.push((lo, hi));
+        rem = chars.as_str();
+    }
+}
+
+fn glob_matches(tokens: &[GlobToken], text: &str) -> bool {
+    let bounds: Vec<usize> = text
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let at = |offset: usize| bounds.binary_search(&offset).ok();
+    let mut table = vec![vec![false; bounds.len()]; tokens.len() + 1];
+
+    table[tokens.len()][bounds.len() - 1] = true;
+
+    for (idx, token) in tokens.iter().enumerate().rev() {
+        let (head, tail) = table.split_at_mut(idx + 1);
+        let (row, next) = (&mut head[idx], &tail[0]);
+        let mut run = false;
+
+        for pos in (0..bounds.len()).rev() {
+            let rest = &text[bounds[pos]..];
+            let ch = rest.chars().next();
+
+            row[pos] = match token {
+                GlobToken::Literal(lit) => match rest.starts_with(lit.as_str()) {
+                    true => matches!(at(bounds[pos] + lit.len()), Some(end) if next[end]),
+                    false => false,
+                },
+                GlobToken::AnyChar | GlobToken::Class { .. } => match ch {
+                    Some(ch) if ch != '/' && glob_class_matches(token, ch) => next[pos + 1],
+                    _ => false,
+                },
+                GlobToken::AnyRun => {
+                    run = next[pos] || ch.is_some() && ch != Some('/') && run;
+                    run
+                }
+                GlobToken::AnyPath => {
+                    run = next[pos] || ch.is_some() && run;
+                    run || glob_skips_slash(&tokens[idx + 1..], &tail[1..], rest, |offset| {
+                        at(bounds[pos] + offset)
+                    })
+                }
+            };
+        }
+    }
+
+    table[0][0]
+}
+
+fn glob_skips_slash(
+    tokens: &[GlobToken],
+    table: &[Vec<bool>],
+    text: &str,
+    at: impl Fn(usize) -> Option<usize>,
+) -> bool {
+    match (tokens.first(), table.first()) {
+        (Some(GlobToken::Literal(lit)), Some(after)) => match lit.strip_prefix('/') {
+            Some(lit) if text.starts_with(lit) => matches!(at(lit.len()), Some(end) if after[end]),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn glob_class_matches(token: &GlobToken, ch: char) -> bool {
+    match token {
+        GlobToken::Class { negated, ranges } => {
+            ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&ch)) != *negated
+        }
+        _ => true,
+    }
+}
+
+fn missing(input: &str, expect: &str) -> Error {
+    match input.chars().next() {
+        Some(ch) => Error::expect(Sequence::custom(expect)).but_found(ch),
+        None => Error::expect(Sequence::custom(expect)).but_found_end(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(parse("45s", duration), Ok((Duration::from_secs(45), "")));
+        assert_eq!(
+            parse("1h30m rest", duration),
+            Ok((Duration::from_secs(90 * 60), " rest"))
+        );
+        assert_eq!(
+            parse("1.5d", duration),
+            Ok((Duration::from_secs(36 * 60 * 60), ""))
+        );
+        assert_eq!(
+            parse("2w", duration),
+            Ok((Duration::from_secs(14 * 24 * 60 * 60), ""))
+        );
+        assert_eq!(
+            parse("1m500ms250us", duration),
+            Ok((Duration::from_micros(60_500_250), ""))
+        );
+        assert_eq!(
+            parse("0.25µs", duration),
+            Ok((Duration::from_nanos(250), ""))
+        );
+        assert_eq!(
+            parse("10", duration),
+            Err(Error::expect(Sequence::custom("duration unit")).but_found_end())
+        );
+        assert_eq!(
+            parse("1h30x", duration),
+            Err(Error::expect(Sequence::custom("duration unit")).but_found('x'))
+        );
+        assert_eq!(
+            parse("-1s", duration),
+            Err(Error::expect(Sequence::Decimal).but_found('-'))
+        );
+        assert_eq!(
+            parse("99999999999999w", duration),
+            Err(Error::invalid().with_context("duration overflow"))
+        );
+        assert_eq!(
+            parse("0.000000000001s", duration),
+            Ok((Duration::from_nanos(0), ""))
+        );
+
+        let tiny = format!("0.{}1s", "0".repeat(40));
+
+        assert_eq!(
+            parse(&tiny, duration),
+            Err(Error::invalid().with_context(format!(
+                "{} has too many fractional digits",
+                &tiny[..tiny.len() - 1]
+            )))
+        );
+    }
+
+    #[test]
+    fn test_duration_with() {
+        let units = DurationUnits::new()
+            .unit("sec", Duration::from_secs(1))
+            .unit("min", Duration::from_secs(60));
+
+        assert_eq!(
+            parse("2min30sec", duration_with(units.clone())),
+            Ok((Duration::from_secs(150), ""))
+        );
+        assert_eq!(
+            parse("2m", duration_with(units)),
+            Err(Error::expect(Sequence::custom("duration unit")).but_found('m'))
+        );
+
+        let units = DurationUnits::new().unit("eon", Duration::MAX);
+
+        assert_eq!(
+            parse("999999999999999999eon", duration_with(units)),
+            Err(Error::invalid().with_context("duration overflow"))
+        );
+    }
+
+    #[test]
+    fn test_byte_size() {
+        assert_eq!(parse("10MiB", byte_size), Ok((10 * 1024 * 1024, "")));
+        assert_eq!(parse("1.5GB", byte_size), Ok((1_500_000_000, "")));
+        assert_eq!(parse("512k,", byte_size), Ok((512_000, ",")));
+        assert_eq!(parse("4 KiB", byte_size), Ok((4096, "")));
+        assert_eq!(parse("0.5Ki", byte_size), Ok((512, "")));
+        assert_eq!(parse("100 rest", byte_size), Ok((100, " rest")));
+        assert_eq!(parse("3 parsecs", byte_size), Ok((3, " parsecs")));
+        assert_eq!(parse("64B", byte_size), Ok((64, "")));
+        assert_eq!(
+            parse("64b", byte_size),
+            Err(Error::expect(Sequence::custom("byte size unit")).but_found('b'))
+        );
+        assert_eq!(
+            parse("8Mb", byte_size),
+            Err(Error::expect(Sequence::custom("byte size unit")).but_found('M'))
+        );
+        assert_eq!(parse("0.000B", byte_size), Ok((0, "")));
+        assert_eq!(
+            parse("16EiB", byte_size),
+            Err(Error::invalid().with_context("16EiB does not fit in u64"))
+        );
+        assert_eq!(
+            parse("1.5B", byte_size),
+            Err(Error::invalid().with_context("1.5B is not a whole number of bytes"))
+        );
+
+        let tiny = format!("0.{}1KiB", "0".repeat(40));
+
+        assert_eq!(
+            parse(&tiny, byte_size),
+            Err(Error::invalid().with_context(format!("{} is not a whole number of bytes", tiny)))
+        );
+        assert_eq!(
+            parse("3parsecs", byte_size),
+            Err(Error::expect(Sequence::custom("byte size unit")).but_found('p'))
+        );
+        assert_eq!(
+            parse("MB", byte_size),
+            Err(Error::expect(Sequence::Decimal).but_found('M'))
+        );
+    }
+
+    #[test]
+    fn test_byte_size_with() {
+        let binary = || byte_size_with(SizeBase::Binary);
+
+        assert_eq!(parse("512k", binary()), Ok((512 * 1024, "")));
+        assert_eq!(parse("2GB", binary()), Ok((2 << 30, "")));
+        assert_eq!(parse("1MiB", binary()), Ok((1 << 20, "")));
+        assert_eq!(
+            parse("1kB", byte_size_with(SizeBase::Decimal)),
+            Ok((1000, ""))
+        );
+    }
+
+    #[test]
+    fn test_posix_path() {
+        assert_eq!(
+            parse("/usr//local/./bin/\n", posix_path),
+            Ok((
+                FilePath {
+                    prefix: None,
+                    absolute: true,
+                    components: vec!["usr", "local", ".", "bin"],
+                },
+                "\n"
+            ))
+        );
+        assert_eq!(
+            parse("../my docs/a.txt", posix_path).map(|(path, _)| path.components),
+            Ok(vec!["..", "my docs", "a.txt"])
+        );
+        assert_eq!(
+            parse("/", posix_path).map(|(path, _)| (path.absolute, path.components)),
+            Ok((true, vec![]))
+        );
+        assert_eq!(
+            parse("\n", posix_path),
+            Err(Error::expect(Sequence::custom("path")).but_found('\n'))
+        );
+    }
+
+    #[test]
+    fn test_windows_path() {
+        assert_eq!(
+            parse("C:\\Program Files\\app.exe\"", windows_path),
+            Ok((
+                FilePath {
+                    prefix: Some("C:"),
+                    absolute: true,
+                    components: vec!["Program Files", "app.exe"],
+                },
+                "\""
+            ))
+        );
+        assert_eq!(
+            parse("\\\\server\\share\\dir/file", windows_path),
+            Ok((
+                FilePath {
+                    prefix: Some("\\\\server\\share"),
+                    absolute: true,
+                    components: vec!["dir", "file"],
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("d:docs\\a.txt", windows_path).map(|(path, _)| (path.prefix, path.absolute)),
+            Ok((Some("d:"), false))
+        );
+        assert_eq!(
+            parse("a\\b:stream", windows_path).map(|(path, rem)| (path.components, rem)),
+            Ok((vec!["a", "b"], ":stream"))
+        );
+        assert_eq!(
+            parse("\\\\server", windows_path),
+            Err(Error::invalid().with_context("UNC path needs a server and share"))
+        );
+        assert_eq!(
+            parse("|", windows_path),
+            Err(Error::expect(Sequence::custom("path")).but_found('|'))
+        );
+    }
+
+    #[test]
+    fn test_glob() {
+        assert_eq!(
+            parse("src/**/*.[ch]? rest", glob),
+            Ok((
+                Glob {
+                    tokens: vec![
+                        GlobToken::Literal("src/".to_owned()),
+                        GlobToken::AnyPath,
+                        GlobToken::Literal("/".to_owned()),
+                        GlobToken::AnyRun,
+                        GlobToken::Literal(".".to_owned()),
+                        GlobToken::Class {
+                            negated: false,
+                            ranges: vec![('c', 'c'), ('h', 'h')],
+                        },
+                        GlobToken::AnyChar,
+                    ],
+                },
+                " rest"
+            ))
+        );
+        assert_eq!(
+            parse("[!]a-z]\\*", glob).map(|(glob, _)| glob.tokens),
+            Ok(vec![
+                GlobToken::Class {
+                    negated: true,
+                    ranges: vec![(']', ']'), ('a', 'z')],
+                },
+                GlobToken::Literal("*".to_owned()),
+            ])
+        );
+        assert_eq!(
+            parse("[a-", glob),
+            Err(Error::expect(']')
+                .but_found_end()
+                .into_fail()
+                .with_context("glob"))
+        );
+        assert_eq!(
+            parse("[z-a]", glob),
+            Err(Error::invalid().with_context("invalid glob range `z-a`"))
+        );
+        assert_eq!(
+            parse(" ", glob),
+            Err(Error::expect(Sequence::custom("glob")).but_found(' '))
+        );
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        let (rust, _) = parse("src/**/*.rs", glob).unwrap();
+        let (header, _) = parse("*.[!c]", glob).unwrap();
+
+        assert!(rust.matches("src/main.rs"));
+        assert!(rust.matches("src/a/b/lib.rs"));
+        assert!(!rust.matches("src/a/b/lib.rsx"));
+        assert!(!rust.matches("tests/lib.rs"));
+        assert!(header.matches("x.h"));
+        assert!(!header.matches("x.c"));
+        assert!(!header.matches("dir/x.h"));
+        assert!(parse("a?c", glob).unwrap().0.matches("abc"));
+        assert!(!parse("a?c", glob).unwrap().0.matches("a/c"));
+        assert!(parse("**", glob).unwrap().0.matches("any/depth/file"));
+    }
+
+    #[test]
+    fn test_glob_matches_backtracking() {
+        let (stars, _) = parse("*a*a*a*a*a*a*a*a*b", glob).unwrap();
+        let text = "a".repeat(40);
+
+        assert!(!stars.matches(&text));
+        assert!(stars.matches(&(text.clone() + "b")));
+        assert!(!stars.matches(&(text + "/b")));
+        assert!(parse("a/**/b", glob).unwrap().0.matches("a/b"));
+        assert!(parse("a/**/b", glob).unwrap().0.matches("a/x/y/b"));
+        assert!(!parse("a/**/b", glob).unwrap().0.matches("a/xb"));
+        assert!(!parse("a/**", glob).unwrap().0.matches("b/c"));
+    }
+}
@@ -0,0 +1,529 @@
+use std::fmt;
+
+use super::{Expr, Grammar};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CharSet {
+    ranges: Vec<(char, char)>,
+    end: bool,
+}
+
+impl CharSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, ch: char) -> bool {
+        self.ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&ch))
+    }
+
+    pub fn contains_end(&self) -> bool {
+        self.end
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty() && !self.end
+    }
+
+    pub fn ranges(&self) -> &[(char, char)] {
+        &self.ranges
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut out = Self {
+            ranges: Vec::new(),
+            end: self.end && other.end,
+        };
+
+        for (lo, hi) in &self.ranges {
+            for (other_lo, other_hi) in &other.ranges {
+                let lo = *lo.max(other_lo);
+                let hi = *hi.min(other_hi);
+
+                if lo <= hi {
+                    out.insert(lo, hi);
+                }
+            }
+        }
+
+        out
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.intersection(other) == *self
+    }
+
+    fn insert(&mut self, lo: char, hi: char) {
+        let mut ranges: Vec<(char, char)> = Vec::with_capacity(self.ranges.len() + 1);
+
+        self.ranges.push((lo, hi));
+        self.ranges.sort_unstable();
+
+        for (lo, hi) in self.ranges.drain(..) {
+            match ranges.last_mut() {
+                Some(last) if lo as u32 <= last.1 as u32 + 1 => last.1 = last.1.max(hi),
+                _ => ranges.push((lo, hi)),
+            }
+        }
+
+        self.ranges = ranges;
+    }
+
+    fn union(&mut self, other: &Self) -> bool {
+        let before = self.clone();
+
+        for (lo, hi) in &other.ranges {
+            self.insert(*lo, *hi);
+        }
+
+        self.end |= other.end;
+
+        *self != before
+    }
+}
+
+impl fmt::Display for CharSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut items = self
+            .ranges
+            .iter()
+            .map(|(lo, hi)| match lo == hi {
+                true => format!("{:?}", lo),
+                false => format!("{:?}..{:?}", lo, hi),
+            })
+            .collect::<Vec<_>>();
+
+        if self.end {
+            items.push("EOI".to_owned());
+        }
+
+        write!(f, "[{}]", items.join(", "))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConflictKind {
+    Backtrack,
+    Dead,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    pub rule: String,
+    pub choice: String,
+    pub kind: ConflictKind,
+    pub arm: usize,
+    pub other: usize,
+    pub overlap: CharSet,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ConflictKind::Backtrack => write!(
+                f,
+                "rule `{}`: alternatives {} and {} of `{}` both start with {}",
+                self.rule,
+                self.other + 1,
+                self.arm + 1,
+                self.choice,
+                self.overlap
+            ),
+            ConflictKind::Dead => write!(
+                f,
+                "rule `{}`: alternative {} of `{}` can never match, alternative {} always matches first",
+                self.rule,
+                self.arm + 1,
+                self.choice,
+                self.other + 1
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Sets {
+    nullable: bool,
+    infallible: bool,
+    first: CharSet,
+    follow: CharSet,
+}
+
+#[derive(Clone, Debug)]
+pub struct Analysis<'g> {
+    grammar: &'g Grammar,
+    sets: Vec<Sets>,
+    conflicts: Vec<Conflict>,
+    left_recursive: Vec<String>,
+}
+
+impl<'g> Analysis<'g> {
+    pub(super) fn new(grammar: &'g Grammar) -> Self {
+        let mut analysis = Self {
+            grammar,
+            sets: vec![Sets::default(); grammar.rules.len()],
+            conflicts: Vec::new(),
+            left_recursive: Vec::new(),
+        };
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for (idx, rule) in grammar.rules.iter().enumerate() {
+                let nullable = analysis.nullable(&rule.expr);
+                let infallible = analysis.infallible(&rule.expr);
+                let first = analysis.first_of(&rule.expr);
+                let sets = &mut analysis.sets[idx];
+
+                changed |= sets.nullable != nullable || sets.infallible != infallible;
+                changed |= sets.first.union(&first);
+                sets.nullable = nullable;
+                sets.infallible = infallible;
+            }
+        }
+
+        changed = true;
+
+        while changed {
+            changed = false;
+
+            for (idx, rule) in grammar.rules.iter().enumerate() {
+                let follow = analysis.sets[idx].follow.clone();
+
+                changed |= analysis.follow_of(&rule.expr, &follow);
+            }
+        }
+
+        for (idx, rule) in grammar.rules.iter().enumerate() {
+            let follow = analysis.sets[idx].follow.clone();
+
+            analysis.check(&rule.name, &rule.expr, &follow);
+        }
+
+        let calls = grammar
+            .rules
+            .iter()
+            .map(|rule| {
+                let mut calls = Vec::new();
+
+                analysis.left_calls(&rule.expr, &mut calls);
+                calls
+            })
+            .collect::<Vec<_>>();
+
+        for (idx, rule) in grammar.rules.iter().enumerate() {
+            if reaches(&calls, idx) {
+                analysis.left_recursive.push(rule.name.clone());
+            }
+        }
+
+        analysis
+    }
+
+    pub fn first(&self, rule: &str) -> Option<&CharSet> {
+        self.sets(rule).map(|sets| &sets.first)
+    }
+
+    pub fn follow(&self, rule: &str) -> Option<&CharSet> {
+        self.sets(rule).map(|sets| &sets.follow)
+    }
+
+    pub fn is_nullable(&self, rule: &str) -> bool {
+        matches!(self.sets(rule), Some(sets) if sets.nullable)
+    }
+
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    pub fn left_recursive(&self) -> &[String] {
+        &self.left_recursive
+    }
+
+    pub fn is_ll1(&self) -> bool {
+        self.conflicts.is_empty() && self.left_recursive.is_empty()
+    }
+
+    fn sets(&self, rule: &str) -> Option<&Sets> {
+        self.grammar.index.get(rule).map(|idx| &self.sets[*idx])
+    }
+
+    fn nullable(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Str(lit) | Expr::Insensitive(lit) => lit.is_empty(),
+            Expr::Range(_, _) => false,
+            Expr::Ident(name) => match self.sets(name) {
+                Some(sets) => sets.nullable,
+                None => name == "SOI",
+            },
+            Expr::Seq(exprs) => exprs.iter().all(|expr| self.nullable(expr)),
+            Expr::Choice(exprs) => exprs.iter().any(|expr| self.nullable(expr)),
+            Expr::Opt(_) | Expr::Pos(_) | Expr::Neg(_) => true,
+            Expr::Rep(expr, min, _) => *min == 0 || self.nullable(expr),
+        }
+    }
+
+    fn left_calls(&self, expr: &Expr, out: &mut Vec<usize>) {
+        match expr {
+            Expr::Ident(name) => out.extend(self.grammar.index.get(name)),
+            Expr::Seq(exprs) => {
+                for expr in exprs {
+                    self.left_calls(expr, out);
+
+                    if !self.nullable(expr) {
+                        break;
+                    }
+                }
+            }
+            Expr::Choice(exprs) => {
+                for expr in exprs {
+                    self.left_calls(expr, out);
+                }
+            }
+            Expr::Opt(expr) | Expr::Rep(expr, _, _) | Expr::Pos(expr) | Expr::Neg(expr) => {
+                self.left_calls(expr, out)
+            }
+            Expr::Str(_) | Expr::Insensitive(_) | Expr::Range(_, _) => {}
+        }
+    }
+
+    fn infallible(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Str(lit) | Expr::Insensitive(lit) => lit.is_empty(),
+            Expr::Range(_, _) | Expr::Neg(_) => false,
+            Expr::Ident(name) => matches!(self.sets(name), Some(sets) if sets.infallible),
+            Expr::Seq(exprs) => exprs.iter().all(|expr| self.infallible(expr)),
+            Expr::Choice(exprs) => exprs.iter().any(|expr| self.infallible(expr)),
+            Expr::Opt(_) => true,
+            Expr::Rep(expr, min, _) => *min == 0 || self.infallible(expr),
+            Expr::Pos(expr) => self.infallible(expr),
+        }
+    }
+
+    fn first_of(&self, expr: &Expr) -> CharSet {
+        let mut out = CharSet::new();
+
+        match expr {
+            Expr::Str(lit) => {
+                if let Some(ch) = lit.chars().next() {
+                    out.insert(ch, ch);
+                }
+            }
+            Expr::Insensitive(lit) => {
+                if let Some(ch) = lit.chars().next() {
+                    for ch in ch.to_lowercase().chain(ch.to_uppercase()) {
+                        out.insert(ch, ch);
+                    }
+                }
+            }
+            Expr::Range(lo, hi) => out.insert(*lo, *hi),
+            Expr::Ident(name) => match self.sets(name) {
+                Some(sets) => out = sets.first.clone(),
+                None => out = builtin(name),
+            },
+            Expr::Seq(exprs) => {
+                for expr in exprs {
+                    out.union(&self.first_of(expr));
+
+                    if !self.nullable(expr) {
+                        break;
+                    }
+                }
+            }
+            Expr::Choice(exprs) => {
+                for expr in exprs {
+                    out.union(&self.first_of(expr));
+                }
+            }
+            Expr::Opt(expr) | Expr::Rep(expr, _, _) => out = self.first_of(expr),
+            Expr::Pos(_) | Expr::Neg(_) => {}
+        }
+
+        out
+    }
+
+    fn lookahead(&self, expr: &Expr, follow: &CharSet) -> CharSet {
+        let mut out = self.first_of(expr);
+
+        if self.nullable(expr) {
+            out.union(follow);
+        }
+
+        out
+    }
+
+    fn follow_of(&mut self, expr: &Expr, follow: &CharSet) -> bool {
+        match expr {
+            Expr::Ident(name) => match self.grammar.index.get(name) {
+                Some(idx) => self.sets[*idx].follow.union(follow),
+                None => false,
+            },
+            Expr::Seq(exprs) => {
+                let mut after = follow.clone();
+                let mut changed = false;
+
+                for expr in exprs.iter().rev() {
+                    changed |= self.follow_of(expr, &after);
+                    after = self.lookahead(expr, &after);
+                }
+
+                changed
+            }
+            Expr::Choice(exprs) => {
+                let mut changed = false;
+
+                for expr in exprs {
+                    changed |= self.follow_of(expr, follow);
+                }
+
+                changed
+            }
+            Expr::Rep(expr, _, _) => {
+                let mut after = self.first_of(expr);
+
+                after.union(follow);
+
+                self.follow_of(expr, &after)
+            }
+            Expr::Opt(expr) | Expr::Pos(expr) | Expr::Neg(expr) => self.follow_of(expr, follow),
+            _ => false,
+        }
+    }
+
+    fn check(&mut self, rule: &str, expr: &Expr, follow: &CharSet) {
+        match expr {
+            Expr::Seq(exprs) => {
+                let mut after = follow.clone();
+
+                for expr in exprs.iter().rev() {
+                    self.check(rule, expr, &after);
+                    after = self.lookahead(expr, &after);
+                }
+            }
+            Expr::Choice(arms) => {
+                let lookahead = arms
+                    .iter()
+                    .map(|arm| self.lookahead(arm, follow))
+                    .collect::<Vec<_>>();
+
+                for (idx, arm) in arms.iter().enumerate() {
+                    let conflicts = match (0..idx).find(|other| self.shadows(&arms[*other], arm, 0))
+                    {
+                        Some(other) => vec![(ConflictKind::Dead, other, lookahead[idx].clone())],
+                        None => (0..idx)
+                            .map(|other| {
+                                let overlap = lookahead[other].intersection(&lookahead[idx]);
+
+                                (ConflictKind::Backtrack, other, overlap)
+                            })
+                            .filter(|(_, _, overlap)| !overlap.is_empty())
+                            .collect(),
+                    };
+
+                    for (kind, other, overlap) in conflicts {
+                        self.conflicts.push(Conflict {
+                            rule: rule.to_owned(),
+                            choice: expr.to_ebnf(0),
+                            kind,
+                            arm: idx,
+                            other,
+                            overlap,
+                        });
+                    }
+
+                    self.check(rule, arm, follow);
+                }
+            }
+            Expr::Rep(expr, _, _) => {
+                let mut after = self.first_of(expr);
+
+                after.union(follow);
+
+                self.check(rule, expr, &after);
+            }
+            Expr::Opt(expr) | Expr::Pos(expr) | Expr::Neg(expr) => self.check(rule, expr, follow),
+            _ => {}
+        }
+    }
+
+    fn shadows(&self, earlier: &Expr, later: &Expr, depth: usize) -> bool {
+        if self.infallible(earlier) {
+            return true;
+        }
+
+        if let (Expr::Str(prefix), Some(lit)) = (earlier, leading_literal(later)) {
+            return lit.starts_with(prefix.as_str());
+        }
+
+        self.is_single_char(earlier, depth)
+            && !self.nullable(later)
+            && self.first_of(later).is_subset(&self.first_of(earlier))
+    }
+
+    fn is_single_char(&self, expr: &Expr, depth: usize) -> bool {
+        match expr {
+            Expr::Str(lit) => lit.chars().count() == 1,
+            Expr::Range(_, _) => true,
+            Expr::Ident(name) => match self.grammar.index.get(name) {
+                Some(idx) if depth < self.grammar.rules.len() => {
+                    self.is_single_char(&self.grammar.rules[*idx].expr, depth + 1)
+                }
+                Some(_) => false,
+                None => !matches!(name.as_str(), "SOI" | "EOI" | "NEWLINE"),
+            },
+            Expr::Choice(exprs) => exprs.iter().all(|expr| self.is_single_char(expr, depth)),
+            _ => false,
+        }
+    }
+}
+
+fn leading_literal(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Str(lit) => Some(lit),
+        Expr::Seq(exprs) => exprs.first().and_then(leading_literal),
+        _ => None,
+    }
+}
+
+fn reaches(calls: &[Vec<usize>], start: usize) -> bool {
+    let mut seen = vec![false; calls.len()];
+    let mut stack = calls[start].clone();
+
+    while let Some(idx) = stack.pop() {
+        if idx == start {
+            return true;
+        }
+
+        if !seen[idx] {
+            seen[idx] = true;
+            stack.extend(&calls[idx]);
+        }
+    }
+
+    false
+}
+
+fn builtin(name: &str) -> CharSet {
+    let ranges: &[(char, char)] = match name {
+        "ANY" => &[('\0', char::MAX)],
+        "NEWLINE" => &[('\n', '\n'), ('\r', '\r')],
+        "ASCII" => &[('\0', '\x7f')],
+        "ASCII_DIGIT" => &[('0', '9')],
+        "ASCII_NONZERO_DIGIT" => &[('1', '9')],
+        "ASCII_HEX_DIGIT" => &[('0', '9'), ('A', 'F'), ('a', 'f')],
+        "ASCII_ALPHA" => &[('A', 'Z'), ('a', 'z')],
+        "ASCII_ALPHA_LOWER" => &[('a', 'z')],
+        "ASCII_ALPHA_UPPER" => &[('A', 'Z')],
+        "ASCII_ALPHANUMERIC" => &[('0', '9'), ('A', 'Z'), ('a', 'z')],
+        _ => &[],
+    };
+    let mut out = CharSet::new();
+
+    for (lo, hi) in ranges {
+        out.insert(*lo, *hi);
+    }
+
+    out.end = name == "EOI";
+    out
+}
@@ -0,0 +1,497 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{Atomicity, Eval, Expr, Grammar, Modifier, Pair};
+use crate::error::Error;
+use crate::parser::Output;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Symbol {
+    Node(usize),
+    Term(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Rule(usize, Atomicity),
+    Inline,
+    Trivia,
+}
+
+struct Production {
+    lhs: usize,
+    rhs: Vec<Symbol>,
+}
+
+struct Compiler<'g> {
+    grammar: &'g Grammar,
+    kinds: Vec<Kind>,
+    productions: Vec<Production>,
+    terms: Vec<&'g Expr>,
+    rules: HashMap<(usize, Atomicity), usize>,
+    trivia: Option<usize>,
+}
+
+impl<'g> Compiler<'g> {
+    fn node(&mut self, kind: Kind) -> usize {
+        self.kinds.push(kind);
+        self.kinds.len() - 1
+    }
+
+    fn produce(&mut self, lhs: usize, rhs: Vec<Symbol>) {
+        self.productions.push(Production { lhs, rhs });
+    }
+
+    fn rule(&mut self, idx: usize, atomicity: Atomicity) -> usize {
+        if let Some(node) = self.rules.get(&(idx, atomicity)) {
+            return *node;
+        }
+
+        let node = self.node(Kind::Rule(idx, atomicity));
+        let rule = &self.grammar.rules[idx];
+        let inner = match rule.modifier {
+            Modifier::Atomic => Atomicity::Atomic,
+            Modifier::CompoundAtomic => Atomicity::Compound,
+            Modifier::NonAtomic => Atomicity::None,
+            _ => atomicity,
+        };
+
+        self.rules.insert((idx, atomicity), node);
+
+        let symbol = self.symbol(&rule.expr, inner);
+
+        self.produce(node, vec![symbol]);
+
+        node
+    }
+
+    fn trivia(&mut self) -> Option<Symbol> {
+        if let Some(node) = self.trivia {
+            return Some(Symbol::Node(node));
+        }
+
+        let rules = ["WHITESPACE", "COMMENT"]
+            .iter()
+            .filter_map(|name| self.grammar.index.get(*name).copied())
+            .collect::<Vec<_>>();
+
+        if rules.is_empty() {
+            return None;
+        }
+
+        let node = self.node(Kind::Trivia);
+
+        self.trivia = Some(node);
+        self.produce(node, Vec::new());
+
+        for idx in rules {
+            let rule = self.rule(idx, Atomicity::Atomic);
+
+            self.produce(node, vec![Symbol::Node(node), Symbol::Node(rule)]);
+        }
+
+        Some(Symbol::Node(node))
+    }
+
+    fn seq(&mut self, exprs: &[&'g Expr], atomicity: Atomicity) -> Vec<Symbol> {
+        let mut rhs = Vec::new();
+
+        for (idx, expr) in exprs.iter().enumerate() {
+            if idx > 0 && atomicity == Atomicity::None {
+                rhs.extend(self.trivia());
+            }
+
+            rhs.push(self.symbol(expr, atomicity));
+        }
+
+        rhs
+    }
+
+    fn symbol(&mut self, expr: &'g Expr, atomicity: Atomicity) -> Symbol {
+        let rhs = match expr {
+            Expr::Ident(name) => match self.grammar.index.get(name) {
+                Some(idx) => return Symbol::Node(self.rule(*idx, atomicity)),
+                None => return self.term(expr),
+            },
+            Expr::Seq(exprs) => vec![self.seq(&exprs.iter().collect::<Vec<_>>(), atomicity)],
+            Expr::Choice(exprs) => exprs
+                .iter()
+                .map(|expr| vec![self.symbol(expr, atomicity)])
+                .collect(),
+            Expr::Opt(expr) => vec![Vec::new(), vec![self.symbol(expr, atomicity)]],
+            Expr::Rep(_, min, Some(max)) if min > max => Vec::new(),
+            Expr::Rep(_, _, Some(0)) => vec![Vec::new()],
+            Expr::Rep(expr, min, Some(max)) => {
+                let item = self.symbol(expr, atomicity);
+                let skip = match atomicity {
+                    Atomicity::None => self.trivia(),
+                    _ => None,
+                };
+                let mut more = None;
+
+                for _ in (*min).max(1)..*max {
+                    let node = self.node(Kind::Inline);
+
+                    self.produce(node, Vec::new());
+                    self.produce(
+                        node,
+                        skip.into_iter().chain(Some(item)).chain(more).collect(),
+                    );
+                    more = Some(Symbol::Node(node));
+                }
+
+                let mut rhs = vec![item];
+
+                for _ in 1..*min {
+                    rhs.extend(skip);
+                    rhs.push(item);
+                }
+
+                rhs.extend(more);
+
+                match min {
+                    0 => vec![Vec::new(), rhs],
+                    _ => vec![rhs],
+                }
+            }
+            Expr::Rep(expr, min, None) => {
+                let item = self.symbol(expr, atomicity);
+                let many = self.node(Kind::Inline);
+                let skip = match atomicity {
+                    Atomicity::None => self.trivia(),
+                    _ => None,
+                };
+                let mut rhs = self.seq(&vec![&**expr; min.saturating_sub(1)], atomicity);
+
+                self.produce(many, vec![item]);
+                self.produce(
+                    many,
+                    std::iter::once(Symbol::Node(many))
+                        .chain(skip)
+                        .chain(Some(item))
+                        .collect(),
+                );
+
+                if !rhs.is_empty() {
+                    rhs.extend(skip);
+                }
+
+                rhs.push(Symbol::Node(many));
+
+                match min {
+                    0 => vec![Vec::new(), rhs],
+                    _ => vec![rhs],
+                }
+            }
+            _ => return self.term(expr),
+        };
+        let node = self.node(Kind::Inline);
+
+        for rhs in rhs {
+            self.produce(node, rhs);
+        }
+
+        Symbol::Node(node)
+    }
+
+    fn term(&mut self, expr: &'g Expr) -> Symbol {
+        self.terms.push(expr);
+
+        Symbol::Term(self.terms.len() - 1)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Item {
+    production: usize,
+    dot: usize,
+    origin: usize,
+}
+
+struct Earley<'g, 'a> {
+    compiler: Compiler<'g>,
+    eval: Eval<'g, 'a>,
+    input: &'a str,
+    nullable: Vec<bool>,
+    by_lhs: Vec<Vec<usize>>,
+    done: HashMap<(usize, usize), Vec<usize>>,
+    terms: HashMap<(usize, usize), Option<usize>>,
+}
+
+impl<'g, 'a> Earley<'g, 'a> {
+    fn new(compiler: Compiler<'g>, eval: Eval<'g, 'a>, input: &'a str) -> Self {
+        let mut by_lhs = vec![Vec::new(); compiler.kinds.len()];
+        let mut nullable = vec![false; compiler.kinds.len()];
+
+        for (idx, production) in compiler.productions.iter().enumerate() {
+            by_lhs[production.lhs].push(idx);
+        }
+
+        loop {
+            let mut changed = false;
+
+            for production in &compiler.productions {
+                if !nullable[production.lhs]
+                    && production
+                        .rhs
+                        .iter()
+                        .all(|symbol| matches!(symbol, Symbol::Node(node) if nullable[*node]))
+                {
+                    nullable[production.lhs] = true;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        Self {
+            compiler,
+            eval,
+            input,
+            nullable,
+            by_lhs,
+            done: HashMap::new(),
+            terms: HashMap::new(),
+        }
+    }
+
+    fn scan(&mut self, term: usize, pos: usize) -> Option<usize> {
+        let (compiler, eval, input) = (&self.compiler, &self.eval, self.input);
+
+        *self.terms.entry((term, pos)).or_insert_with(|| {
+            eval.eval(
+                compiler.terms[term],
+                &input[pos..],
+                Atomicity::Atomic,
+                &mut Vec::new(),
+            )
+            .ok()
+            .map(|rem| input.len() - rem.len())
+        })
+    }
+
+    fn recognize(&mut self, start: usize) -> usize {
+        let mut chart = vec![Vec::<Item>::new(); self.input.len() + 1];
+        let mut seen = vec![HashSet::<Item>::new(); self.input.len() + 1];
+        let mut furthest = 0;
+
+        for production in self.by_lhs[start].clone() {
+            let item = Item {
+                production,
+                dot: 0,
+                origin: 0,
+            };
+
+            seen[0].insert(item);
+            chart[0].push(item);
+        }
+
+        for pos in 0..chart.len() {
+            let mut idx = 0;
+
+            while idx < chart[pos].len() {
+                let item = chart[pos][idx];
+                let production = &self.compiler.productions[item.production];
+                let lhs = production.lhs;
+                let mut next = Vec::new();
+
+                furthest = pos;
+                idx += 1;
+
+                match production.rhs.get(item.dot).copied() {
+                    None => {
+                        let ends = self.done.entry((lhs, item.origin)).or_default();
+
+                        if !ends.contains(&pos) {
+                            ends.push(pos);
+                        }
+
+                        for parent in &chart[item.origin] {
+                            let rhs = &self.compiler.productions[parent.production].rhs;
+
+                            if rhs.get(parent.dot) == Some(&Symbol::Node(lhs)) {
+                                next.push((pos, advance(*parent)));
+                            }
+                        }
+                    }
+                    Some(Symbol::Node(node)) => {
+                        for production in &self.by_lhs[node] {
+                            next.push((
+                                pos,
+                                Item {
+                                    production: *production,
+                                    dot: 0,
+                                    origin: pos,
+                                },
+                            ));
+                        }
+
+                        let empty = self.done.get(&(node, pos)).map(|ends| ends.contains(&pos));
+
+                        if self.nullable[node] || empty == Some(true) {
+                            next.push((pos, advance(item)));
+                        }
+                    }
+                    Some(Symbol::Term(term)) => {
+                        if let Some(end) = self.scan(term, pos) {
+                            next.push((end, advance(item)));
+                        }
+                    }
+                }
+
+                for (at, item) in next {
+                    if seen[at].insert(item) {
+                        chart[at].push(item);
+                    }
+                }
+            }
+        }
+
+        furthest
+    }
+
+    fn build(
+        &mut self,
+        node: usize,
+        start: usize,
+        end: usize,
+        stack: &mut Vec<(usize, usize, usize)>,
+        out: &mut Vec<Pair<'a>>,
+    ) -> bool {
+        if stack.contains(&(node, start, end)) {
+            return false;
+        }
+
+        stack.push((node, start, end));
+
+        let mut children = Vec::new();
+        let found = self.by_lhs[node].clone().into_iter().any(|production| {
+            children.clear();
+            self.derive(production, 0, start, end, stack, &mut children)
+        });
+
+        stack.pop();
+
+        if !found {
+            return false;
+        }
+
+        match self.compiler.kinds[node] {
+            Kind::Inline => out.append(&mut children),
+            Kind::Trivia => {}
+            Kind::Rule(idx, atomicity) => {
+                let rule = &self.compiler.grammar.rules[idx];
+
+                if rule.modifier == Modifier::Silent {
+                    out.append(&mut children);
+                } else if atomicity != Atomicity::Atomic {
+                    if rule.modifier == Modifier::Atomic {
+                        children.clear();
+                    }
+
+                    out.push(Pair {
+                        rule: rule.name.clone(),
+                        text: &self.input[start..end],
+                        children,
+                    });
+                }
+            }
+        }
+
+        true
+    }
+
+    fn derive(
+        &mut self,
+        production: usize,
+        dot: usize,
+        pos: usize,
+        end: usize,
+        stack: &mut Vec<(usize, usize, usize)>,
+        out: &mut Vec<Pair<'a>>,
+    ) -> bool {
+        let symbol = match self.compiler.productions[production].rhs.get(dot) {
+            Some(symbol) => *symbol,
+            None => return pos == end,
+        };
+
+        match symbol {
+            Symbol::Term(term) => match self.scan(term, pos) {
+                Some(next) if next <= end => {
+                    self.derive(production, dot + 1, next, end, stack, out)
+                }
+                _ => false,
+            },
+            Symbol::Node(node) => {
+                let mut ends = self.done.get(&(node, pos)).cloned().unwrap_or_default();
+
+                ends.retain(|next| *next <= end);
+                ends.sort_unstable_by(|a, b| b.cmp(a));
+
+                for next in ends {
+                    let len = out.len();
+
+                    if self.build(node, pos, next, stack, out)
+                        && self.derive(production, dot + 1, next, end, stack, out)
+                    {
+                        return true;
+                    }
+
+                    out.truncate(len);
+                }
+
+                false
+            }
+        }
+    }
+}
+
+fn advance(item: Item) -> Item {
+    Item {
+        dot: item.dot + 1,
+        ..item
+    }
+}
+
+pub(super) fn parse<'a>(
+    grammar: &Grammar,
+    rule: usize,
+    input: &'a str,
+) -> Output<'a, Vec<Pair<'a>>> {
+    let mut compiler = Compiler {
+        grammar,
+        kinds: Vec::new(),
+        productions: Vec::new(),
+        terms: Vec::new(),
+        rules: HashMap::new(),
+        trivia: None,
+    };
+    let start = compiler.rule(rule, Atomicity::None);
+    let eval = Eval {
+        grammar,
+        source: input,
+    };
+    let mut earley = Earley::new(compiler, eval, input);
+    let furthest = earley.recognize(start);
+    let name = grammar.rules[rule].name.as_str();
+    let end = match earley
+        .done
+        .get(&(start, 0))
+        .and_then(|ends| ends.iter().max())
+    {
+        Some(end) => *end,
+        None => {
+            return Err(match input[furthest..].chars().next() {
+                Some(ch) => Error::found(ch).with_context(name),
+                None => Error::found_end().with_context(name),
+            })
+        }
+    };
+    let mut out = Vec::new();
+
+    earley.build(start, 0, end, &mut Vec::new(), &mut out);
+
+    Ok((out, &input[end..]))
+}
@@ -0,0 +1,819 @@
+use std::collections::HashMap;
+
+use crate::combinator::branch::branch;
+use crate::combinator::fold_case;
+use crate::error::Error;
+use crate::parser::{take, Output, Parser};
+use crate::state;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pair<'a> {
+    pub rule: String,
+    pub text: &'a str,
+    pub children: Vec<Pair<'a>>,
+}
+
+mod analysis;
+mod earley;
+
+pub use self::analysis::{Analysis, CharSet, Conflict, ConflictKind};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Backend {
+    #[default]
+    Peg,
+    Earley,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grammar {
+    rules: Vec<Rule>,
+    index: HashMap<String, usize>,
+    backend: Backend,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GrammarBuilder {
+    rules: Vec<Rule>,
+    backend: Backend,
+}
+
+impl GrammarBuilder {
+    pub fn rule(self, name: &str, expr: Expr) -> Self {
+        self.rule_with(name, Modifier::Normal, expr)
+    }
+
+    pub fn rule_with(mut self, name: &str, modifier: Modifier, expr: Expr) -> Self {
+        self.rules.push(Rule {
+            name: name.to_owned(),
+            modifier,
+            expr,
+        });
+        self
+    }
+
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn build(self) -> Result<Grammar, Error> {
+        let index = self
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(idx, rule)| (rule.name.clone(), idx))
+            .collect::<HashMap<_, _>>();
+
+        for rule in &self.rules {
+            rule.expr.check(&index)?;
+        }
+
+        let grammar = Grammar {
+            rules: self.rules,
+            index,
+            backend: self.backend,
+        };
+
+        if grammar.backend == Backend::Peg {
+            if let Some(name) = grammar.analyze().left_recursive().first() {
+                return Err(left_recursive(name));
+            }
+        }
+
+        Ok(grammar)
+    }
+}
+
+impl Grammar {
+    pub fn builder() -> GrammarBuilder {
+        GrammarBuilder::default()
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|rule| rule.name.as_str())
+    }
+
+    pub fn parse<'a>(&self, rule: &str, input: &'a str) -> Output<'a, Vec<Pair<'a>>> {
+        let idx = match self.index.get(rule) {
+            Some(idx) => *idx,
+            None => return Err(undefined(rule)),
+        };
+
+        if self.backend == Backend::Earley {
+            return earley::parse(self, idx, input);
+        }
+
+        let eval = Eval {
+            grammar: self,
+            source: input,
+        };
+        let mut out = Vec::new();
+        let rem = eval.call(rule, input, Atomicity::None, &mut out)?;
+
+        Ok((out, rem))
+    }
+
+    pub fn rule<'g, 'a>(&'g self, name: &'g str) -> impl Parser<'a, Vec<Pair<'a>>> + 'g {
+        move |input: &'a str| self.parse(name, input)
+    }
+
+    pub fn to_ebnf(&self) -> String {
+        let mut out = String::new();
+
+        for rule in &self.rules {
+            let note = match rule.modifier {
+                Modifier::Normal => "",
+                Modifier::Silent => " (* silent *)",
+                Modifier::Atomic => " (* atomic *)",
+                Modifier::CompoundAtomic => " (* compound atomic *)",
+                Modifier::NonAtomic => " (* non-atomic *)",
+            };
+
+            out.push_str(&format!(
+                "{} = {} ;{}\n",
+                rule.name,
+                rule.expr.to_ebnf(0),
+                note
+            ));
+        }
+
+        out
+    }
+
+    pub fn generator(&self, seed: u64) -> Generator {
+        Generator {
+            grammar: self,
+            state: seed ^ 0x9E37_79B9_7F4A_7C15,
+            max_depth: 16,
+            attempts: 64,
+        }
+    }
+
+    pub fn analyze(&self) -> Analysis {
+        Analysis::new(self)
+    }
+
+    pub(crate) fn accepts(&self, rule: &str, input: &str) -> bool {
+        matches!(self.parse(rule, input), Ok((_, "")))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Generator<'g> {
+    grammar: &'g Grammar,
+    state: u64,
+    max_depth: usize,
+    attempts: usize,
+}
+
+impl<'g> Generator<'g> {
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    pub fn valid(&mut self, rule: &str) -> Option<String> {
+        if !self.grammar.index.contains_key(rule) {
+            return None;
+        }
+
+        for _ in 0..self.attempts {
+            let mut out = String::new();
+
+            if self.call(rule, Atomicity::None, 0, &mut out).is_some()
+                && self.grammar.accepts(rule, &out)
+            {
+                return Some(out);
+            }
+        }
+
+        None
+    }
+
+    pub fn invalid(&mut self, rule: &str) -> Option<String> {
+        for _ in 0..self.attempts {
+            let mut chars = self.valid(rule)?.chars().collect::<Vec<_>>();
+            let idx = self.below(chars.len() + 1);
+
+            match self.below(4) {
+                0 if idx < chars.len() => {
+                    chars.remove(idx);
+                }
+                1 if idx < chars.len() => chars[idx] = self.printable(),
+                2 => chars.truncate(idx),
+                _ => {
+                    let ch = self.printable();
+
+                    chars.insert(idx, ch);
+                }
+            }
+
+            let out = chars.into_iter().collect::<String>();
+
+            if !self.grammar.accepts(rule, &out) {
+                return Some(out);
+            }
+        }
+
+        None
+    }
+
+    fn call(
+        &mut self,
+        name: &str,
+        atomicity: Atomicity,
+        depth: usize,
+        out: &mut String,
+    ) -> Option<()> {
+        if depth > self.max_depth {
+            return None;
+        }
+
+        let rule = match self.grammar.index.get(name) {
+            Some(idx) => &self.grammar.rules[*idx],
+            None => return self.builtin(name, out),
+        };
+        let inner = match rule.modifier {
+            Modifier::Atomic => Atomicity::Atomic,
+            Modifier::CompoundAtomic => Atomicity::Compound,
+            Modifier::NonAtomic => Atomicity::None,
+            _ => atomicity,
+        };
+
+        self.expr(&rule.expr, inner, depth + 1, out)
+    }
+
+    fn builtin(&mut self, name: &str, out: &mut String) -> Option<()> {
+        let ch = match name {
+            "SOI" | "EOI" => return Some(()),
+            "ANY" | "ASCII" => self.printable(),
+            "NEWLINE" => '\n',
+            "ASCII_DIGIT" => self.between('0', '9'),
+            "ASCII_NONZERO_DIGIT" => self.between('1', '9'),
+            "ASCII_HEX_DIGIT" => *self.pick(&['0', '7', '9', 'a', 'f', 'A', 'F'])?,
+            "ASCII_ALPHA" => *self.pick(&['a', 'z', 'A', 'Z'])?,
+            "ASCII_ALPHA_LOWER" => self.between('a', 'z'),
+            "ASCII_ALPHA_UPPER" => self.between('A', 'Z'),
+            "ASCII_ALPHANUMERIC" => *self.pick(&['a', 'Z', '0', '9'])?,
+            _ => return None,
+        };
+
+        out.push(ch);
+
+        Some(())
+    }
+
+    fn expr(
+        &mut self,
+        expr: &Expr,
+        atomicity: Atomicity,
+        depth: usize,
+        out: &mut String,
+    ) -> Option<()> {
+        match expr {
+            Expr::Str(lit) => out.push_str(lit),
+            Expr::Insensitive(lit) => {
+                for ch in lit.chars() {
+                    match self.below(2) {
+                        0 => out.extend(ch.to_lowercase()),
+                        _ => out.extend(ch.to_uppercase()),
+                    }
+                }
+            }
+            Expr::Range(lo, hi) => out.push(self.between(*lo, *hi)),
+            Expr::Ident(name) => self.call(name, atomicity, depth, out)?,
+            Expr::Seq(exprs) => {
+                for (idx, expr) in exprs.iter().enumerate() {
+                    if idx > 0 {
+                        self.skip(atomicity, depth, out)?;
+                    }
+
+                    self.expr(expr, atomicity, depth, out)?;
+                }
+            }
+            Expr::Choice(exprs) => {
+                let expr = self.pick(exprs)?;
+
+                self.expr(expr, atomicity, depth, out)?;
+            }
+            Expr::Opt(expr) => {
+                if self.below(2) == 0 {
+                    self.expr(expr, atomicity, depth, out)?;
+                }
+            }
+            Expr::Rep(expr, min, max) => {
+                let max = max.unwrap_or(min + 3).min(min + 3);
+                let count = min + self.below(max - min + 1);
+
+                for idx in 0..count {
+                    if idx > 0 {
+                        self.skip(atomicity, depth, out)?;
+                    }
+
+                    self.expr(expr, atomicity, depth, out)?;
+                }
+            }
+            Expr::Pos(_) | Expr::Neg(_) => {}
+        }
+
+        Some(())
+    }
+
+    fn skip(&mut self, atomicity: Atomicity, depth: usize, out: &mut String) -> Option<()> {
+        match atomicity == Atomicity::None && self.grammar.index.contains_key("WHITESPACE") {
+            true => self.call("WHITESPACE", Atomicity::Atomic, depth, out),
+            false => Some(()),
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound.max(1) as u64) as usize
+    }
+
+    fn pick<'e, T>(&mut self, items: &'e [T]) -> Option<&'e T> {
+        let idx = self.below(items.len());
+
+        items.get(idx)
+    }
+
+    fn between(&mut self, lo: char, hi: char) -> char {
+        let span = (hi as usize).saturating_sub(lo as usize) + 1;
+
+        std::char::from_u32(lo as u32 + self.below(span) as u32).unwrap_or(lo)
+    }
+
+    fn printable(&mut self) -> char {
+        self.between('!', '~')
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Modifier {
+    Normal,
+    Silent,
+    Atomic,
+    CompoundAtomic,
+    NonAtomic,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Atomicity {
+    None,
+    Atomic,
+    Compound,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Rule {
+    name: String,
+    modifier: Modifier,
+    expr: Expr,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Str(String),
+    Insensitive(String),
+    Range(char, char),
+    Ident(String),
+    Seq(Vec<Expr>),
+    Choice(Vec<Expr>),
+    Opt(Box<Expr>),
+    Rep(Box<Expr>, usize, Option<usize>),
+    Pos(Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+impl From<&str> for Expr {
+    fn from(lit: &str) -> Self {
+        Self::Str(lit.to_owned())
+    }
+}
+
+impl From<char> for Expr {
+    fn from(ch: char) -> Self {
+        Self::Str(ch.to_string())
+    }
+}
+
+impl Expr {
+    pub fn rule(name: &str) -> Self {
+        Self::Ident(name.to_owned())
+    }
+
+    fn to_ebnf(&self, prec: u8) -> String {
+        let (level, text) = match self {
+            Self::Str(lit) => (2, terminal(lit)),
+            Self::Insensitive(lit) => (2, format!("? case-insensitive {} ?", terminal(lit))),
+            Self::Range(lo, hi) => (2, format!("? {:?} to {:?} ?", lo, hi)),
+            Self::Ident(name) => (2, name.clone()),
+            Self::Seq(exprs) => (1, join(exprs, " , ", 1)),
+            Self::Choice(exprs) => (0, join(exprs, " | ", 1)),
+            Self::Opt(expr) => (2, format!("[ {} ]", expr.to_ebnf(0))),
+            Self::Rep(expr, 0, None) => (2, format!("{{ {} }}", expr.to_ebnf(0))),
+            Self::Rep(expr, min, max) => {
+                let item = expr.to_ebnf(2);
+                let required = match min {
+                    0 => None,
+                    1 => Some(item.clone()),
+                    _ => Some(format!("{} * {}", min, item)),
+                };
+                let rest = match max {
+                    None => Some(format!("{{ {} }}", expr.to_ebnf(0))),
+                    Some(max) if max == min => None,
+                    Some(max) if max - min == 1 => Some(format!("[ {} ]", expr.to_ebnf(0))),
+                    Some(max) => Some(format!("{} * [ {} ]", max - min, expr.to_ebnf(0))),
+                };
+
+                match (required, rest) {
+                    (Some(required), Some(rest)) => (1, format!("{} , {}", required, rest)),
+                    (Some(part), None) | (None, Some(part)) => (1, part),
+                    (None, None) => (2, "? empty ?".to_owned()),
+                }
+            }
+            Self::Pos(expr) => (2, format!("? followed by {} ?", expr.to_ebnf(2))),
+            Self::Neg(expr) => (2, format!("? not followed by {} ?", expr.to_ebnf(2))),
+        };
+
+        match level < prec {
+            true => format!("( {} )", text),
+            false => text,
+        }
+    }
+
+    fn check(&self, index: &HashMap<String, usize>) -> Result<(), Error> {
+        match self {
+            Self::Ident(name) if !index.contains_key(name) && !is_builtin(name) => {
+                Err(undefined(name))
+            }
+            Self::Seq(exprs) | Self::Choice(exprs) => {
+                exprs.iter().try_for_each(|expr| expr.check(index))
+            }
+            Self::Opt(expr) | Self::Rep(expr, _, _) | Self::Pos(expr) | Self::Neg(expr) => {
+                expr.check(index)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+struct Eval<'g, 'a> {
+    grammar: &'g Grammar,
+    source: &'a str,
+}
+
+impl<'g, 'a> Eval<'g, 'a> {
+    fn call(
+        &self,
+        name: &str,
+        input: &'a str,
+        atomicity: Atomicity,
+        out: &mut Vec<Pair<'a>>,
+    ) -> Result<&'a str, Error> {
+        let rule = match self.grammar.index.get(name) {
+            Some(idx) => &self.grammar.rules[*idx],
+            None => return self.builtin(name, input),
+        };
+        let _depth = state::enter()?;
+        let inner = match rule.modifier {
+            Modifier::Atomic => Atomicity::Atomic,
+            Modifier::CompoundAtomic => Atomicity::Compound,
+            Modifier::NonAtomic => Atomicity::None,
+            _ => atomicity,
+        };
+        let mut children = Vec::new();
+        let rem = self
+            .eval(&rule.expr, input, inner, &mut children)
+            .map_err(|err| match err.get_context() {
+                Some(_) => err,
+                None => err.with_context(name),
+            })?;
+
+        if rule.modifier == Modifier::Silent {
+            out.append(&mut children);
+        } else if atomicity != Atomicity::Atomic {
+            if inner == Atomicity::Atomic {
+                children.clear();
+            }
+
+            out.push(Pair {
+                rule: rule.name.clone(),
+                text: &input[..input.len() - rem.len()],
+                children,
+            });
+        }
+
+        Ok(rem)
+    }
+
+    fn builtin(&self, name: &str, input: &'a str) -> Result<&'a str, Error> {
+        let rem = match name {
+            "ANY" => take(|_: char| true).parse(input)?.1,
+            "SOI" if input.as_ptr() == self.source.as_ptr() => input,
+            "SOI" => return Err(Error::invalid().into_pass().with_context(name)),
+            "EOI" => crate::sequence::end.parse(input)?.1,
+            "NEWLINE" => branch(("\n", "\r\n", "\r")).parse(input)?.1,
+            "ASCII" => take(|ch: char| ch.is_ascii()).parse(input)?.1,
+            "ASCII_DIGIT" => take('0'..='9').parse(input)?.1,
+            "ASCII_NONZERO_DIGIT" => take('1'..='9').parse(input)?.1,
+            "ASCII_HEX_DIGIT" => take(|ch: char| ch.is_ascii_hexdigit()).parse(input)?.1,
+            "ASCII_ALPHA" => take(|ch: char| ch.is_ascii_alphabetic()).parse(input)?.1,
+            "ASCII_ALPHA_LOWER" => take('a'..='z').parse(input)?.1,
+            "ASCII_ALPHA_UPPER" => take('A'..='Z').parse(input)?.1,
+            "ASCII_ALPHANUMERIC" => take(|ch: char| ch.is_ascii_alphanumeric()).parse(input)?.1,
+            _ => return Err(undefined(name)),
+        };
+
+        Ok(rem)
+    }
+
+    fn eval(
+        &self,
+        expr: &Expr,
+        input: &'a str,
+        atomicity: Atomicity,
+        out: &mut Vec<Pair<'a>>,
+    ) -> Result<&'a str, Error> {
+        match expr {
+            Expr::Str(lit) => Ok(Parser::parse(&lit.as_str(), input)?.1),
+            Expr::Insensitive(lit) => Ok(fold_case(lit.as_str()).parse(input)?.1),
+            Expr::Range(lo, hi) => Ok(take(*lo..=*hi).parse(input)?.1),
+            Expr::Ident(name) => self.call(name, input, atomicity, out),
+            Expr::Seq(exprs) => {
+                let mut rem = input;
+
+                for (idx, expr) in exprs.iter().enumerate() {
+                    if idx > 0 {
+                        rem = self.skip(rem, atomicity, out);
+                    }
+
+                    rem = self.eval(expr, rem, atomicity, out)?;
+                }
+
+                Ok(rem)
+            }
+            Expr::Choice(exprs) => {
+                let len = out.len();
+                let mut last = None;
+
+                for expr in exprs {
+                    match self.eval(expr, input, atomicity, out) {
+                        Ok(rem) => return Ok(rem),
+                        Err(err) if err.is_pass() => {
+                            out.truncate(len);
+                            last = Some(err);
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+
+                Err(last.unwrap_or_else(|| Error::invalid().into_pass()))
+            }
+            Expr::Opt(expr) => {
+                let len = out.len();
+
+                match self.eval(expr, input, atomicity, out) {
+                    Ok(rem) => Ok(rem),
+                    Err(err) if err.is_pass() => {
+                        out.truncate(len);
+                        Ok(input)
+                    }
+                    Err(err) => Err(err),
+                }
+            }
+            Expr::Rep(expr, min, max) => {
+                let mut rem = input;
+                let mut count = 0;
+
+                while !matches!(max, Some(max) if count >= *max) {
+                    let len = out.len();
+                    let start = match count {
+                        0 => rem,
+                        _ => self.skip(rem, atomicity, out),
+                    };
+
+                    match self.eval(expr, start, atomicity, out) {
+                        Ok(next) if next.len() < rem.len() => {
+                            rem = next;
+                            count += 1;
+                        }
+                        Ok(_) => {
+                            out.truncate(len);
+                            break;
+                        }
+                        Err(err) if err.is_pass() && count >= *min => {
+                            out.truncate(len);
+                            break;
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+
+                Ok(rem)
+            }
+            Expr::Pos(expr) => {
+                self.eval(expr, input, atomicity, &mut Vec::new())?;
+
+                Ok(input)
+            }
+            Expr::Neg(expr) => match self.eval(expr, input, atomicity, &mut Vec::new()) {
+                Ok(_) => match input.chars().next() {
+                    Some(ch) => Err(Error::found(ch)),
+                    None => Err(Error::found_end()),
+                },
+                Err(err) if err.is_pass() => Ok(input),
+                Err(err) => Err(err),
+            },
+        }
+    }
+
+    fn skip(&self, input: &'a str, atomicity: Atomicity, out: &mut Vec<Pair<'a>>) -> &'a str {
+        if atomicity != Atomicity::None {
+            return input;
+        }
+
+        let trivia = ["WHITESPACE", "COMMENT"]
+            .iter()
+            .filter(|name| self.grammar.index.contains_key(**name))
+            .collect::<Vec<_>>();
+        let mut rem = input;
+
+        'outer: loop {
+            for name in &trivia {
+                let len = out.len();
+
+                match self.call(name, rem, Atomicity::Atomic, out) {
+                    Ok(next) if next.len() < rem.len() => {
+                        rem = next;
+                        continue 'outer;
+                    }
+                    _ => out.truncate(len),
+                }
+            }
+
+            return rem;
+        }
+    }
+}
+
+fn join(exprs: &[Expr], sep: &str, prec: u8) -> String {
+    exprs
+        .iter()
+        .map(|expr| expr.to_ebnf(prec))
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+fn terminal(lit: &str) -> String {
+    if lit.chars().any(char::is_control) || lit.contains('"') && lit.contains('\'') {
+        format!("? {:?} ?", lit)
+    } else if lit.contains('"') {
+        format!("'{}'", lit)
+    } else {
+        format!("\"{}\"", lit)
+    }
+}
+
+fn is_builtin(name: &str) -> bool {
+    matches!(
+        name,
+        "ANY"
+            | "SOI"
+            | "EOI"
+            | "NEWLINE"
+            | "ASCII"
+            | "ASCII_DIGIT"
+            | "ASCII_NONZERO_DIGIT"
+            | "ASCII_HEX_DIGIT"
+            | "ASCII_ALPHA"
+            | "ASCII_ALPHA_LOWER"
+            | "ASCII_ALPHA_UPPER"
+            | "ASCII_ALPHANUMERIC"
+    )
+}
+
+fn undefined(name: &str) -> Error {
+    Error::invalid().with_context(format!("undefined rule `{}`", name))
+}
+
+fn left_recursive(name: &str) -> Error {
+    Error::invalid().with_context(format!("rule `{}` is left-recursive", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv() -> GrammarBuilder {
+        let digit = Expr::Choice(vec![Expr::Range('0', '9'), '.'.into(), '-'.into()]);
+
+        Grammar::builder()
+            .rule("field", Expr::Rep(Box::new(digit), 1, None))
+            .rule(
+                "record",
+                Expr::Seq(vec![
+                    Expr::rule("field"),
+                    Expr::Rep(
+                        Box::new(Expr::Seq(vec![','.into(), Expr::rule("field")])),
+                        0,
+                        None,
+                    ),
+                ]),
+            )
+            .rule(
+                "file",
+                Expr::Rep(
+                    Box::new(Expr::Seq(vec![
+                        Expr::rule("record"),
+                        Expr::Choice(vec!["\r\n".into(), '\n'.into()]),
+                    ])),
+                    0,
+                    None,
+                ),
+            )
+    }
+
+    #[test]
+    fn test_builder() {
+        let grammar = csv().build().unwrap();
+        let (pairs, rem) = grammar.parse("file", "1,2.5\n-3,4\n").unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(pairs[0].children.len(), 2);
+        assert_eq!(pairs[0].children[0].children[1].text, "2.5");
+        assert_eq!(
+            grammar.rules().collect::<Vec<_>>(),
+            vec!["field", "record", "file"]
+        );
+        assert_eq!(
+            csv().rule("extra", Expr::rule("missing")).build(),
+            Err(Error::invalid().with_context("undefined rule `missing`"))
+        );
+    }
+
+    #[test]
+    fn test_builder_tools() {
+        let grammar = csv()
+            .rule_with("WHITESPACE", Modifier::Silent, ' '.into())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            grammar.to_ebnf(),
+            concat!(
+                "field = ( ? '0' to '9' ? | \".\" | \"-\" ) , { ? '0' to '9' ? | \".\" | \"-\" } ;\n",
+                "record = field , { \",\" , field } ;\n",
+                "file = { record , ( ? \"\\r\\n\" ? | ? \"\\n\" ? ) } ;\n",
+                "WHITESPACE = \" \" ; (* silent *)\n",
+            )
+        );
+
+        let mut generator = grammar.generator(3);
+
+        for _ in 0..20 {
+            assert!(grammar.accepts("record", &generator.valid("record").unwrap()));
+            assert!(!grammar.accepts("record", &generator.invalid("record").unwrap()));
+        }
+
+        let analysis = grammar.analyze();
+
+        assert!(analysis.first("record").unwrap().contains('7'));
+        assert!(analysis.is_nullable("file"));
+        assert!(analysis.left_recursive().is_empty());
+    }
+
+    #[test]
+    fn test_builder_left_recursion() {
+        let expr = Expr::Choice(vec![
+            Expr::Seq(vec![Expr::rule("expr"), '+'.into(), '1'.into()]),
+            '1'.into(),
+        ]);
+
+        assert_eq!(
+            Grammar::builder().rule("expr", expr.clone()).build(),
+            Err(Error::invalid().with_context("rule `expr` is left-recursive"))
+        );
+
+        let grammar = Grammar::builder()
+            .rule("expr", expr)
+            .backend(Backend::Earley)
+            .build()
+            .unwrap();
+
+        assert!(grammar.accepts("expr", "1+1+1"));
+        assert_eq!(grammar.analyze().left_recursive(), ["expr".to_owned()]);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::combinator::branch::{either, optional};
+use crate::combinator::series::{delimited, leading, list, pair};
+use crate::combinator::{context, fail, map};
+use crate::parser::{take_while, Output, Parser};
+use crate::sequence::indent;
+
+pub fn cookie_pairs(input: &str) -> Output<Vec<(&str, &str)>> {
+    context("cookie", list(cookie_pair, pair(';', optional(indent)))).parse(input)
+}
+
+pub fn cookie_pair(input: &str) -> Output<(&str, &str)> {
+    pair(token, leading(fail('='), cookie_value)).parse(input)
+}
+
+pub fn set_cookie(input: &str) -> Output<SetCookie> {
+    context(
+        "set-cookie",
+        pair(
+            cookie_pair,
+            map(
+                optional(leading(
+                    pair(';', optional(indent)),
+                    list(attribute, pair(';', optional(indent))),
+                )),
+                Option::unwrap_or_default,
+            ),
+        ),
+    )
+    .parse(input)
+    .map(|(((name, value), attributes), rem)| {
+        (
+            SetCookie {
+                name,
+                value,
+                attributes,
+            },
+            rem,
+        )
+    })
+}
+
+fn attribute(input: &str) -> Output<(&str, Option<&str>)> {
+    pair(
+        token,
+        optional(leading(
+            '=',
+            map(
+                optional(take_while(|ch: char| ch != ';' && !ch.is_ascii_control())),
+                Option::unwrap_or_default,
+            ),
+        )),
+    )
+    .parse(input)
+}
+
+fn token(input: &str) -> Output<&str> {
+    take_while(is_token).parse(input)
+}
+
+fn cookie_value(input: &str) -> Output<&str> {
+    either(
+        delimited(
+            '"',
+            map(
+                optional(take_while(is_cookie_octet)),
+                Option::unwrap_or_default,
+            ),
+            fail('"'),
+        ),
+        map(
+            optional(take_while(is_cookie_octet)),
+            Option::unwrap_or_default,
+        ),
+    )
+    .parse(input)
+}
+
+fn is_token(ch: char) -> bool {
+    ch.is_ascii_graphic() && !"()<>@,;:\\\"/[]?={}".contains(ch)
+}
+
+fn is_cookie_octet(ch: char) -> bool {
+    ch.is_ascii_graphic() && !"\",;\\".contains(ch)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetCookie<'a> {
+    pub name: &'a str,
+    pub value: &'a str,
+    pub attributes: Vec<(&'a str, Option<&'a str>)>,
+}
+
+impl<'a> SetCookie<'a> {
+    pub fn attribute(&self, name: &str) -> Option<Option<&'a str>> {
+        self.attributes
+            .iter()
+            .rev()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attribute(name).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_cookie_pairs() {
+        assert_eq!(parse("a=1", cookie_pairs), Ok((vec![("a", "1")], "")));
+        assert_eq!(
+            parse("SID=31d4d96e407aad42; lang=en-US", cookie_pairs),
+            Ok((vec![("SID", "31d4d96e407aad42"), ("lang", "en-US")], ""))
+        );
+        assert_eq!(
+            parse(r#"a="quoted";b=;c=3"#, cookie_pairs),
+            Ok((vec![("a", "quoted"), ("b", ""), ("c", "3")], ""))
+        );
+        assert_eq!(
+            parse("a=1; b", cookie_pairs),
+            Err(Error::expect('=')
+                .but_found_end()
+                .into_fail()
+                .with_context("cookie"))
+        );
+        assert_eq!(
+            parse(r#"a="1"#, cookie_pairs),
+            Err(Error::expect('"')
+                .but_found_end()
+                .into_fail()
+                .with_context("cookie"))
+        );
+        assert_eq!(
+            parse(r#"a="quoted value""#, cookie_pairs),
+            Err(Error::expect('"')
+                .but_found(' ')
+                .into_fail()
+                .with_context("cookie"))
+        );
+        assert_eq!(
+            parse("=1", cookie_pairs),
+            Err(Error::found('=').with_context("cookie"))
+        );
+    }
+
+    #[test]
+    fn test_set_cookie() {
+        assert_eq!(
+            parse("id=a3fWa", set_cookie),
+            Ok((
+                SetCookie {
+                    name: "id",
+                    value: "a3fWa",
+                    attributes: vec![],
+                },
+                ""
+            ))
+        );
+
+        let (cookie, rem) = parse(
+            "id=a3fWa; Expires=Wed, 21 Oct 2015 07:28:00 GMT; Secure; HttpOnly; Path=/docs",
+            set_cookie,
+        )
+        .unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(cookie.name, "id");
+        assert_eq!(cookie.value, "a3fWa");
+        assert_eq!(
+            cookie.attribute("expires"),
+            Some(Some("Wed, 21 Oct 2015 07:28:00 GMT"))
+        );
+        assert_eq!(cookie.attribute("Path"), Some(Some("/docs")));
+        assert_eq!(cookie.attribute("Secure"), Some(None));
+        assert_eq!(cookie.attribute("Domain"), None);
+        assert!(cookie.has_attribute("httponly"));
+        assert!(!cookie.has_attribute("SameSite"));
+    }
+}
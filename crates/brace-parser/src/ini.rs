@@ -0,0 +1,260 @@
+use crate::character::is_linebreak;
+use crate::combinator::branch::{branch, either, optional};
+use crate::combinator::series::{delimited, leading, pair, trailing};
+use crate::combinator::{context, fail, map};
+use crate::error::Error;
+use crate::parser::{take_while, Output, Parser};
+use crate::sequence::{end, indent, linebreak};
+
+pub fn section_header(input: &str) -> Output<&str> {
+    context(
+        "section header",
+        delimited(
+            '[',
+            fail(map(
+                take_while(|ch: char| ch != ']' && !is_linebreak(ch)),
+                str::trim,
+            )),
+            fail(']'),
+        ),
+    )
+    .parse(input)
+}
+
+pub fn key_value(input: &str) -> Output<(&str, &str)> {
+    context("key value", pair(trailing(key, fail('=')), value)).parse(input)
+}
+
+pub fn comment(input: &str) -> Output<&str> {
+    leading(either(';', '#'), rest_of_line).parse(input)
+}
+
+pub fn document(input: &str) -> Output<Document> {
+    let mut doc = Document::default();
+    let mut rem = input;
+
+    loop {
+        let (item, next) = line(rem)?;
+
+        match item {
+            Some(Line::Section(name)) => doc.sections.push(Section::new(name)),
+            Some(Line::Entry(key, value)) => match doc.sections.last_mut() {
+                Some(section) => section.entries.push((key, value)),
+                None => doc.entries.push((key, value)),
+            },
+            Some(Line::Comment) | None => {}
+        }
+
+        if next.is_empty() {
+            return Ok((doc, next));
+        }
+
+        rem = next;
+    }
+}
+
+fn key(input: &str) -> Output<&str> {
+    take_while(|ch: char| ch != '=' && !is_linebreak(ch))
+        .parse(input)
+        .and_then(|(out, rem)| match out.trim() {
+            "" => Err(Error::found('=')),
+            key => Ok((key, rem)),
+        })
+}
+
+fn value(input: &str) -> Output<&str> {
+    map(rest_of_line, str::trim).parse(input)
+}
+
+fn rest_of_line(input: &str) -> Output<&str> {
+    map(optional(take_while(|ch: char| !is_linebreak(ch))), |out| {
+        out.unwrap_or_default()
+    })
+    .parse(input)
+}
+
+fn line(input: &str) -> Output<Option<Line>> {
+    trailing(
+        leading(
+            optional(indent),
+            optional(branch((
+                map(section_header, Line::Section),
+                map(comment, |_| Line::Comment),
+                map(key_value, |(key, value)| Line::Entry(key, value)),
+            ))),
+        ),
+        (optional(indent), optional(comment), either(linebreak, end)),
+    )
+    .parse(input)
+}
+
+enum Line<'a> {
+    Comment,
+    Section(&'a str),
+    Entry(&'a str, &'a str),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Document<'a> {
+    pub entries: Vec<(&'a str, &'a str)>,
+    pub sections: Vec<Section<'a>>,
+}
+
+impl<'a> Document<'a> {
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        find(&self.entries, key)
+    }
+
+    pub fn section(&self, name: &str) -> Option<&Section<'a>> {
+        self.sections
+            .iter()
+            .rev()
+            .find(|section| section.name == name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section<'a> {
+    pub name: &'a str,
+    pub entries: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Section<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        find(&self.entries, key)
+    }
+}
+
+fn find<'a>(entries: &[(&'a str, &'a str)], key: &str) -> Option<&'a str> {
+    entries
+        .iter()
+        .rev()
+        .find(|(k, _)| *k == key)
+        .map(|(_, value)| *value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Error, Expect};
+    use crate::parser::parse;
+
+    #[test]
+    fn test_section_header() {
+        assert_eq!(parse("[server]", section_header), Ok(("server", "")));
+        assert_eq!(
+            parse("[ server ]\nport = 80", section_header),
+            Ok(("server", "\nport = 80"))
+        );
+        assert_eq!(
+            parse("server", section_header),
+            Err(Error::expect('[')
+                .but_found('s')
+                .with_context("section header"))
+        );
+        assert_eq!(
+            parse("[server", section_header),
+            Err(Error::expect(']')
+                .but_found_end()
+                .into_fail()
+                .with_context("section header"))
+        );
+        assert_eq!(
+            parse("[server\n]", section_header),
+            Err(Error::expect(']')
+                .but_found('\n')
+                .into_fail()
+                .with_context("section header"))
+        );
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(parse("port=80", key_value), Ok((("port", "80"), "")));
+        assert_eq!(
+            parse("port = 80\nhost = localhost", key_value),
+            Ok((("port", "80"), "\nhost = localhost"))
+        );
+        assert_eq!(
+            parse("name = hello world ", key_value),
+            Ok((("name", "hello world"), ""))
+        );
+        assert_eq!(parse("empty =", key_value), Ok((("empty", ""), "")));
+        assert_eq!(
+            parse("=80", key_value),
+            Err(Error::found('=').with_context("key value"))
+        );
+        assert_eq!(
+            parse("port", key_value),
+            Err(Error::expect('=')
+                .but_found_end()
+                .into_fail()
+                .with_context("key value"))
+        );
+    }
+
+    #[test]
+    fn test_comment() {
+        assert_eq!(parse("; hello", comment), Ok((" hello", "")));
+        assert_eq!(parse("# hello\nworld", comment), Ok((" hello", "\nworld")));
+        assert_eq!(parse(";", comment), Ok(("", "")));
+        assert_eq!(
+            parse("hello", comment),
+            Err(Error::expect('#').but_found('h'))
+        );
+    }
+
+    #[test]
+    fn test_document() {
+        assert_eq!(parse("", document), Ok((Document::default(), "")));
+        assert_eq!(
+            parse(
+                "; global settings\nname = example\n\n[server]\nhost = localhost ; primary\n  port=80\n\n# paths\n[paths] ; comment\nroot = /var/www\n",
+                document
+            ),
+            Ok((
+                Document {
+                    entries: vec![("name", "example")],
+                    sections: vec![
+                        Section {
+                            name: "server",
+                            entries: vec![("host", "localhost ; primary"), ("port", "80")],
+                        },
+                        Section {
+                            name: "paths",
+                            entries: vec![("root", "/var/www")],
+                        },
+                    ],
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("[a]\r\nkey = 1\r\nkey = 2", document)
+                .map(|(doc, _)| doc.section("a").and_then(|section| section.get("key"))),
+            Ok(Some("2"))
+        );
+        assert_eq!(
+            parse("name = value", document).map(|(doc, _)| doc.get("name")),
+            Ok(Some("value"))
+        );
+        assert_eq!(
+            parse("[server] port = 80", document),
+            Err(Error::expect(Expect::End).but_found('p'))
+        );
+        assert_eq!(
+            parse("[server]\nport\n", document),
+            Err(Error::expect('=')
+                .but_found('\n')
+                .into_fail()
+                .with_context("key value"))
+        );
+    }
+}
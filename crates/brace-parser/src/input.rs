@@ -0,0 +1,637 @@
+use std::marker::PhantomData;
+use std::str;
+
+use crate::error::Error;
+use crate::parser::{offset, Output, Parser};
+use crate::position::{Position, Span};
+use crate::state;
+
+const BOM: char = '\u{FEFF}';
+
+pub fn strip_bom(input: &str) -> &str {
+    input.strip_prefix(BOM).unwrap_or(input)
+}
+
+pub fn from_utf8(input: &[u8]) -> Result<&str, Error> {
+    let input = match input {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => rest,
+        [0xFF, 0xFE, ..] => return Err(Error::invalid().with_context("UTF-16LE input")),
+        [0xFE, 0xFF, ..] => return Err(Error::invalid().with_context("UTF-16BE input")),
+        _ => input,
+    };
+
+    str::from_utf8(input).map_err(|_| Error::invalid().with_context("UTF-8 input"))
+}
+
+pub fn parse_bytes<'a, P, O>(input: &'a [u8], parser: P) -> Output<'a, O>
+where
+    P: Parser<'a, O>,
+{
+    parser.parse(from_utf8(input)?)
+}
+
+pub fn parse_stripped<'a, P, O>(input: &'a str, parser: P) -> Output<'a, O>
+where
+    P: Parser<'a, O>,
+{
+    parser.parse(strip_bom(input))
+}
+
+pub fn parse_with_progress<'a, P, O, F>(input: &'a str, parser: P, callback: F) -> Output<'a, O>
+where
+    P: Parser<'a, O>,
+    F: FnMut(usize, usize),
+{
+    state::with_progress(input, callback, || parser.parse(input))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Normalized<'a> {
+    original: &'a str,
+    text: String,
+    removed: Vec<usize>,
+}
+
+impl<'a> Normalized<'a> {
+    pub fn new(original: &'a str) -> Self {
+        let mut text = String::with_capacity(original.len());
+        let mut removed = Vec::new();
+        let mut iter = original.chars().peekable();
+
+        while let Some(ch) = iter.next() {
+            if ch == '\r' {
+                if iter.peek() == Some(&'\n') {
+                    iter.next();
+                    removed.push(text.len());
+                }
+
+                text.push('\n');
+            } else {
+                text.push(ch);
+            }
+        }
+
+        Self {
+            original,
+            text,
+            removed,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn original(&self) -> &'a str {
+        self.original
+    }
+
+    pub fn original_offset(&self, offset: usize) -> usize {
+        offset + self.removed.partition_point(|removed| *removed < offset)
+    }
+
+    pub fn offset(&self, remainder: &str) -> Option<usize> {
+        offset(&self.text, remainder).map(|offset| self.original_offset(offset))
+    }
+
+    pub fn position(&self, remainder: &str) -> Option<Position> {
+        Some(Position::new(self.original, self.offset(remainder)?))
+    }
+
+    pub fn span(&self, start: &str, end: &str) -> Option<Span> {
+        Some(Span::new(
+            self.original,
+            self.offset(start)?,
+            self.offset(end)?,
+        ))
+    }
+
+    pub fn map_span(&self, span: Span) -> Span {
+        Span::new(
+            self.original,
+            self.original_offset(span.start.offset),
+            self.original_offset(span.end.offset),
+        )
+    }
+
+    pub fn map_error(&self, err: Error) -> Error {
+        match err.get_span() {
+            Some(span) => {
+                let span = self.map_span(*span);
+
+                err.with_span(span)
+            }
+            None => err,
+        }
+    }
+}
+
+pub fn normalize_newlines(input: &str) -> Normalized {
+    Normalized::new(input)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pipeline {
+    strip_bom: bool,
+    normalize_newlines: bool,
+    lowercase: bool,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn strip_bom(mut self) -> Self {
+        self.strip_bom = true;
+        self
+    }
+
+    pub fn normalize_newlines(mut self) -> Self {
+        self.normalize_newlines = true;
+        self
+    }
+
+    pub fn lowercase(mut self) -> Self {
+        self.lowercase = true;
+        self
+    }
+
+    pub fn apply<'a>(&self, original: &'a str) -> Preprocessed<'a> {
+        let input = match self.strip_bom {
+            true => strip_bom(original),
+            false => original,
+        };
+        let normalized = match self.normalize_newlines {
+            true => Some(Normalized::new(input)),
+            false => None,
+        };
+        let stage = normalized.as_ref().map_or(input, Normalized::as_str);
+        let mut text = String::with_capacity(stage.len());
+        let mut anchors = vec![(0, 0)];
+
+        for (idx, ch) in stage.char_indices() {
+            let start = text.len();
+            let end = idx + ch.len_utf8();
+
+            match self.lowercase {
+                true => text.extend(ch.to_lowercase()),
+                false => text.push(ch),
+            }
+
+            if text.len() - start != end - idx {
+                anchors.push((text.len(), end));
+            }
+        }
+
+        Preprocessed {
+            original,
+            text,
+            base: original.len() - input.len(),
+            anchors,
+            normalized,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preprocessed<'a> {
+    original: &'a str,
+    text: String,
+    base: usize,
+    anchors: Vec<(usize, usize)>,
+    normalized: Option<Normalized<'a>>,
+}
+
+impl<'a> Preprocessed<'a> {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn original(&self) -> &'a str {
+        self.original
+    }
+
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let idx = self.anchors.partition_point(|(text, _)| *text <= offset) - 1;
+        let (text, stage) = self.anchors[idx];
+        let limit = match (self.anchors.get(idx + 1), &self.normalized) {
+            (Some((_, next)), _) => *next,
+            (None, Some(normalized)) => normalized.as_str().len(),
+            (None, None) => self.original.len() - self.base,
+        };
+        let offset = (stage + (offset - text)).min(limit);
+
+        match &self.normalized {
+            Some(normalized) => self.base + normalized.original_offset(offset),
+            None => self.base + offset,
+        }
+    }
+
+    pub fn offset(&self, remainder: &str) -> Option<usize> {
+        offset(&self.text, remainder).map(|offset| self.original_offset(offset))
+    }
+
+    pub fn position(&self, remainder: &str) -> Option<Position> {
+        Some(Position::new(self.original, self.offset(remainder)?))
+    }
+
+    pub fn span(&self, start: &str, end: &str) -> Option<Span> {
+        Some(Span::new(
+            self.original,
+            self.offset(start)?,
+            self.offset(end)?,
+        ))
+    }
+
+    pub fn map_span(&self, span: Span) -> Span {
+        Span::new(
+            self.original,
+            self.original_offset(span.start.offset),
+            self.original_offset(span.end.offset),
+        )
+    }
+
+    pub fn map_error(&self, err: Error) -> Error {
+        match err.get_span() {
+            Some(span) => {
+                let span = self.map_span(*span);
+
+                err.with_span(span)
+            }
+            None => err,
+        }
+    }
+}
+
+pub fn parse_chunks<'c, I, P, O>(chunks: I, parser: P) -> ParseChunks<I::IntoIter, P, O>
+where
+    I: IntoIterator<Item = &'c str>,
+    P: for<'b> Parser<'b, O>,
+{
+    ParseChunks {
+        chunks: chunks.into_iter(),
+        parser,
+        buffer: String::new(),
+        consumed: 0,
+        exhausted: false,
+        done: false,
+        output: PhantomData,
+    }
+}
+
+pub struct ParseChunks<I, P, O> {
+    chunks: I,
+    parser: P,
+    buffer: String,
+    consumed: usize,
+    exhausted: bool,
+    done: bool,
+    output: PhantomData<fn() -> O>,
+}
+
+impl<'c, I, P, O> ParseChunks<I, P, O>
+where
+    I: Iterator<Item = &'c str>,
+{
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    pub fn pending(&self) -> &str {
+        &self.buffer
+    }
+
+    fn pull(&mut self) -> bool {
+        if self.exhausted {
+            return false;
+        }
+
+        match self.chunks.next() {
+            Some(chunk) => {
+                self.buffer.push_str(chunk);
+                true
+            }
+            None => {
+                self.exhausted = true;
+                false
+            }
+        }
+    }
+}
+
+impl<'c, I, P, O> Iterator for ParseChunks<I, P, O>
+where
+    I: Iterator<Item = &'c str>,
+    P: for<'b> Parser<'b, O>,
+{
+    type Item = Result<O, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            if self.buffer.is_empty() && !self.pull() {
+                self.done = true;
+
+                return None;
+            }
+
+            let len = self.buffer.len();
+            let output = self
+                .parser
+                .parse(&self.buffer)
+                .map(|(out, rem)| (out, len - rem.len()));
+
+            match output {
+                Ok((out, used)) if used > 0 && (used < len || self.exhausted) => {
+                    self.buffer.drain(..used);
+                    self.consumed += used;
+
+                    return Some(Ok(out));
+                }
+                _ if !self.exhausted => {
+                    self.pull();
+                }
+                Ok(_) => {
+                    self.done = true;
+
+                    return self
+                        .buffer
+                        .chars()
+                        .next()
+                        .map(|ch| Err(Error::expect(()).but_found(ch)));
+                }
+                Err(err) => {
+                    self.done = true;
+
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_bom() {
+        assert_eq!(strip_bom("\u{FEFF}key=value"), "key=value");
+        assert_eq!(strip_bom("key=value"), "key=value");
+        assert_eq!(strip_bom("\u{FEFF}"), "");
+        assert_eq!(strip_bom("a\u{FEFF}"), "a\u{FEFF}");
+    }
+
+    #[test]
+    fn test_from_utf8() {
+        assert_eq!(from_utf8(b"\xEF\xBB\xBFabc"), Ok("abc"));
+        assert_eq!(from_utf8(b"abc"), Ok("abc"));
+        assert_eq!(
+            from_utf8(b"\xFF\xFEa\x00"),
+            Err(Error::invalid().with_context("UTF-16LE input"))
+        );
+        assert_eq!(
+            from_utf8(b"\xFE\xFF\x00a"),
+            Err(Error::invalid().with_context("UTF-16BE input"))
+        );
+        assert_eq!(
+            from_utf8(b"a\xFFb"),
+            Err(Error::invalid().with_context("UTF-8 input"))
+        );
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(parse_bytes(b"\xEF\xBB\xBFab", 'a'), Ok(('a', "b")));
+        assert_eq!(
+            parse_bytes(b"\xFF\xFEa\x00", 'a'),
+            Err(Error::invalid().with_context("UTF-16LE input"))
+        );
+        assert_eq!(parse_stripped("\u{FEFF}ab", 'a'), Ok(('a', "b")));
+        assert_eq!(
+            crate::parser::parse("\u{FEFF}ab", 'a'),
+            Err(Error::expect('a').but_found('\u{FEFF}'))
+        );
+    }
+
+    #[test]
+    fn test_parse_with_progress() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let input = "a".repeat(10_000);
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let log = calls.clone();
+        let output = parse_with_progress(&input, crate::combinator::series::repeat('a'), {
+            move |consumed, total| log.borrow_mut().push((consumed, total))
+        });
+
+        assert_eq!(output.map(|(out, rem)| (out.len(), rem)), Ok((10_000, "")));
+
+        let calls = calls.borrow();
+
+        assert!(calls.len() > 2);
+        assert!(calls.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(calls.iter().all(|(_, total)| *total == 10_000));
+        assert_eq!(calls.last(), Some(&(10_000, 10_000)));
+    }
+
+    #[test]
+    fn test_parse_with_progress_short() {
+        let mut calls = 0;
+        let callback = |consumed, total| {
+            assert_eq!((consumed, total), (1, 2));
+            calls += 1;
+        };
+
+        assert_eq!(parse_with_progress("ab", 'a', callback), Ok(('a', "b")));
+        assert_eq!(calls, 1);
+        assert_eq!(
+            parse_with_progress("b", 'a', |_, _| panic!()),
+            Err(Error::expect('a').but_found('b'))
+        );
+    }
+
+    #[test]
+    fn test_normalize_newlines() {
+        let input = "a\r\nbc\rd\ne\r\n";
+        let normalized = normalize_newlines(input);
+        let text = normalized.as_str();
+
+        assert_eq!(text, "a\nbc\nd\ne\n");
+        assert_eq!(normalized.original(), input);
+        assert_eq!(normalized.original_offset(0), 0);
+        assert_eq!(normalized.original_offset(1), 1);
+        assert_eq!(normalized.original_offset(2), 3);
+        assert_eq!(normalized.original_offset(5), 6);
+        assert_eq!(normalized.original_offset(7), 8);
+        assert_eq!(normalized.original_offset(8), 9);
+        assert_eq!(normalized.original_offset(9), 11);
+        assert_eq!(normalized.offset(&text[7..]), Some(8));
+        assert_eq!(normalized.offset(input), None);
+        assert_eq!(
+            normalized.position(&text[7..]),
+            Some(Position::new(input, 8))
+        );
+        assert_eq!(
+            normalized
+                .span(&text[2..], &text[4..])
+                .unwrap()
+                .slice(input),
+            "bc"
+        );
+        assert_eq!(
+            normalized.map_span(Span::new(text, 0, 5)),
+            Span::new(input, 0, 6)
+        );
+    }
+
+    #[test]
+    fn test_normalized_error() {
+        let input = "a\r\nb";
+        let normalized = normalize_newlines(input);
+        let text = normalized.as_str();
+        let err = crate::combinator::spanned(text, "a\nc")
+            .parse(text)
+            .unwrap_err();
+
+        assert_eq!(
+            normalized.map_error(err),
+            Error::expect('c')
+                .but_found('b')
+                .with_span(Span::new(input, 0, 0))
+        );
+        assert_eq!(normalize_newlines("").as_str(), "");
+        assert_eq!(normalize_newlines("\r\r\n").as_str(), "\n\n");
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_parse_chunks() {
+        use crate::json::{value, JsonValue};
+
+        let chunks = ["[1, 2", "3] {\"a\"", ": \"b\"", "} nu", "", "ll"];
+        let mut values = parse_chunks(chunks.iter().copied(), value);
+
+        assert_eq!(
+            values.next(),
+            Some(Ok(JsonValue::Array(vec![
+                JsonValue::Number(1.0),
+                JsonValue::Number(23.0)
+            ])))
+        );
+        assert_eq!(values.consumed(), 8);
+        assert_eq!(values.pending(), "{\"a\"");
+        assert_eq!(
+            values.next(),
+            Some(Ok(JsonValue::Object(vec![(
+                "a".to_owned(),
+                JsonValue::String("b".to_owned())
+            )])))
+        );
+        assert_eq!(values.next(), Some(Ok(JsonValue::Null)));
+        assert_eq!(values.consumed(), 23);
+        assert_eq!(values.next(), None);
+        assert_eq!(values.next(), None);
+    }
+
+    #[test]
+    fn test_parse_chunks_error() {
+        use crate::combinator::branch::optional;
+        use crate::combinator::owned;
+        use crate::combinator::series::trailing;
+        use crate::sequence::{alphabetic, Sequence};
+
+        fn word(input: &str) -> Output<'_, String> {
+            owned(trailing(alphabetic, ';')).parse(input)
+        }
+
+        fn semicolon(input: &str) -> Output<'_, Option<char>> {
+            optional(';').parse(input)
+        }
+
+        let words = parse_chunks(vec!["ab", "c;d", "e;1", "f;"], word);
+
+        assert_eq!(
+            words.collect::<Vec<_>>(),
+            vec![
+                Ok("abc".to_owned()),
+                Ok("de".to_owned()),
+                Err(Error::expect(Sequence::Alphabetic).but_found('1')),
+            ]
+        );
+        assert_eq!(
+            parse_chunks(vec!["ab"], word).collect::<Vec<_>>(),
+            vec![Err(Error::expect(';').but_found_end())]
+        );
+        assert_eq!(
+            parse_chunks(vec![";", "a", ";"], semicolon).collect::<Vec<_>>(),
+            vec![Ok(Some(';')), Err(Error::expect(()).but_found('a'))]
+        );
+        assert_eq!(parse_chunks(Vec::new(), word).next(), None);
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let input = "\u{FEFF}Key=A\r\nÄB=\rc";
+        let pipeline = Pipeline::new().strip_bom().normalize_newlines().lowercase();
+        let processed = pipeline.apply(input);
+        let text = processed.as_str();
+
+        assert_eq!(text, "key=a\näb=\nc");
+        assert_eq!(processed.original(), input);
+        assert_eq!(processed.original_offset(0), 3);
+        assert_eq!(processed.original_offset(5), 8);
+        assert_eq!(processed.original_offset(6), 10);
+        assert_eq!(processed.original_offset(10), 14);
+        assert_eq!(processed.original_offset(11), 15);
+        assert_eq!(processed.original_offset(12), 16);
+        assert_eq!(processed.original_offset(20), 16);
+        assert_eq!(
+            processed.position(&text[6..]),
+            Some(Position::new(input, 10))
+        );
+        assert_eq!(
+            processed.span(&text[6..], &text[9..]).unwrap().slice(input),
+            "ÄB"
+        );
+    }
+
+    #[test]
+    fn test_pipeline_expanding() {
+        let input = "a\u{130}b";
+        let processed = Pipeline::new().lowercase().apply(input);
+        let text = processed.as_str();
+
+        assert_eq!(text, "ai\u{307}b");
+        assert_eq!(processed.original_offset(1), 1);
+        assert_eq!(processed.original_offset(4), 3);
+        assert_eq!(processed.offset(&text[4..]), Some(3));
+        assert_eq!(processed.original_offset(5), 4);
+        assert_eq!(Pipeline::new().apply("a\r\nb").as_str(), "a\r\nb");
+    }
+
+    #[test]
+    fn test_pipeline_error() {
+        let input = "A\r\nB";
+        let processed = Pipeline::new()
+            .normalize_newlines()
+            .lowercase()
+            .apply(input);
+        let text = processed.as_str();
+        let err = crate::combinator::spanned(text, "a\nc")
+            .parse(text)
+            .unwrap_err();
+
+        assert_eq!(
+            processed.map_error(err),
+            Error::expect('c')
+                .but_found('b')
+                .with_span(Span::new(input, 0, 0))
+        );
+    }
+}
@@ -0,0 +1,398 @@
+use crate::combinator::branch::{branch, either, optional};
+use crate::combinator::series::{delimited, leading, list, pair, trailing};
+use crate::combinator::{consume, context, fail, map};
+use crate::error::Error;
+use crate::limit::Limit;
+use crate::number::float;
+use crate::parser::{take, take_while, Output, Parser};
+use crate::sequence::{decimal, Sequence};
+
+const MAX_NESTING: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+pub fn value(input: &str) -> Output<JsonValue> {
+    nested_value(input, 0)
+}
+
+fn nested_value<'a>(input: &'a str, depth: usize) -> Output<'a, JsonValue> {
+    delimited(
+        whitespace,
+        context(
+            "value",
+            branch((
+                map(null, |_| JsonValue::Null),
+                map(boolean, JsonValue::Bool),
+                map(number, JsonValue::Number),
+                map(string, JsonValue::String),
+                map(move |input| nested_array(input, depth), JsonValue::Array),
+                map(move |input| nested_object(input, depth), JsonValue::Object),
+            )),
+        ),
+        whitespace,
+    )
+    .parse(input)
+}
+
+pub fn null(input: &str) -> Output<()> {
+    map("null", |_| ()).parse(input)
+}
+
+pub fn boolean(input: &str) -> Output<bool> {
+    either(map("true", |_| true), map("false", |_| false)).parse(input)
+}
+
+pub fn number(input: &str) -> Output<f64> {
+    consume((
+        optional('-'),
+        either(
+            consume('0'),
+            consume(pair(
+                take(|ch: char| ('1'..='9').contains(&ch)),
+                optional(decimal),
+            )),
+        ),
+        optional(pair('.', fail(decimal))),
+        optional((either('e', 'E'), optional(either('+', '-')), fail(decimal))),
+    ))
+    .parse(input)
+    .and_then(|(out, rem)| match float(out) {
+        Ok((num, "")) => Ok((num, rem)),
+        _ => Err(Error::invalid()),
+    })
+}
+
+pub fn string(input: &str) -> Output<String> {
+    let mut iter = '"'.parse(input)?.1.char_indices();
+    let mut out = String::new();
+
+    while let Some((idx, ch)) = iter.next() {
+        match ch {
+            '"' => return Ok((out, &input[idx + 2..])),
+            '\\' => match iter.next() {
+                Some((_, '"')) => out.push('"'),
+                Some((_, '\\')) => out.push('\\'),
+                Some((_, '/')) => out.push('/'),
+                Some((_, 'b')) => out.push('\u{0008}'),
+                Some((_, 'f')) => out.push('\u{000C}'),
+                Some((_, 'n')) => out.push('\n'),
+                Some((_, 'r')) => out.push('\r'),
+                Some((_, 't')) => out.push('\t'),
+                Some((pos, 'u')) => {
+                    let (ch, len) = unicode(&input[pos + 2..])?;
+
+                    for _ in 0..len {
+                        iter.next();
+                    }
+
+                    out.push(ch);
+                }
+                Some((_, ch)) => return Err(Error::found(ch).into_fail()),
+                None => return Err(Error::found_end().into_fail()),
+            },
+            '\u{0000}'..='\u{001F}' => return Err(Error::expect('"').but_found(ch).into_fail()),
+            _ => out.push(ch),
+        }
+    }
+
+    Err(Error::expect('"').but_found_end().into_fail())
+}
+
+pub fn array(input: &str) -> Output<Vec<JsonValue>> {
+    nested_array(input, 0)
+}
+
+fn nested_array<'a>(input: &'a str, depth: usize) -> Output<'a, Vec<JsonValue>> {
+    leading(
+        pair('[', whitespace),
+        context("array", move |input: &'a str| {
+            let depth = deeper(depth)?;
+
+            trailing(
+                map(
+                    optional(list(move |input| nested_value(input, depth), ',')),
+                    Option::unwrap_or_default,
+                ),
+                fail(']'),
+            )
+            .parse(input)
+        }),
+    )
+    .parse(input)
+}
+
+pub fn object(input: &str) -> Output<Vec<(String, JsonValue)>> {
+    nested_object(input, 0)
+}
+
+fn nested_object<'a>(input: &'a str, depth: usize) -> Output<'a, Vec<(String, JsonValue)>> {
+    leading(
+        pair('{', whitespace),
+        context("object", move |input: &'a str| {
+            let depth = deeper(depth)?;
+
+            trailing(
+                map(
+                    optional(list(move |input| member(input, depth), ',')),
+                    Option::unwrap_or_default,
+                ),
+                fail('}'),
+            )
+            .parse(input)
+        }),
+    )
+    .parse(input)
+}
+
+fn member(input: &str, depth: usize) -> Output<(String, JsonValue)> {
+    pair(
+        delimited(whitespace, string, whitespace),
+        leading(fail(':'), fail(move |input| nested_value(input, depth))),
+    )
+    .parse(input)
+}
+
+fn deeper(depth: usize) -> Result<usize, Error> {
+    match depth < MAX_NESTING {
+        true => Ok(depth + 1),
+        false => Err(Error::exceeded(Limit::Depth(MAX_NESTING))),
+    }
+}
+
+fn whitespace(input: &str) -> Output<()> {
+    map(
+        optional(take_while(|ch: char| {
+            matches!(ch, ' ' | '\t' | '\n' | '\r')
+        })),
+        |_| (),
+    )
+    .parse(input)
+}
+
+fn unicode(input: &str) -> Result<(char, usize), Error> {
+    let high = hex4(input)?;
+
+    if let 0xD800..=0xDBFF = high {
+        let low = Parser::parse(&"\\u", &input[4..])
+            .map_err(|err| err.into_fail())
+            .and_then(|(_, rem)| hex4(rem))?;
+
+        match low {
+            0xDC00..=0xDFFF => {
+                let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+
+                std::char::from_u32(code)
+                    .map(|ch| (ch, 10))
+                    .ok_or_else(|| Error::invalid().into_fail())
+            }
+            _ => Err(Error::invalid().into_fail()),
+        }
+    } else {
+        std::char::from_u32(high)
+            .map(|ch| (ch, 4))
+            .ok_or_else(|| Error::invalid().into_fail())
+    }
+}
+
+fn hex4(input: &str) -> Result<u32, Error> {
+    match input.get(..4) {
+        Some(digits) if digits.chars().all(|ch| ch.is_ascii_hexdigit()) => {
+            u32::from_str_radix(digits, 16).map_err(|_| Error::invalid())
+        }
+        _ => Err(Error::expect(Sequence::Hexadecimal).into_fail()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_value() {
+        assert_eq!(parse("null", value), Ok((JsonValue::Null, "")));
+        assert_eq!(parse(" true ", value), Ok((JsonValue::Bool(true), "")));
+        assert_eq!(parse("false,", value), Ok((JsonValue::Bool(false), ",")));
+        assert_eq!(parse("-1.5e3", value), Ok((JsonValue::Number(-1500.0), "")));
+        assert_eq!(
+            parse(r#""hello""#, value),
+            Ok((JsonValue::String("hello".to_owned()), ""))
+        );
+        assert_eq!(
+            parse(r#"{"a": [1, {"b": null}], "c": "d"}"#, value),
+            Ok((
+                JsonValue::Object(vec![
+                    (
+                        "a".to_owned(),
+                        JsonValue::Array(vec![
+                            JsonValue::Number(1.0),
+                            JsonValue::Object(vec![("b".to_owned(), JsonValue::Null)]),
+                        ])
+                    ),
+                    ("c".to_owned(), JsonValue::String("d".to_owned())),
+                ]),
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("", value),
+            Err(Error::expect('{').but_found_end().with_context("value"))
+        );
+        assert_eq!(
+            parse("nul", value),
+            Err(Error::expect('{').but_found('n').with_context("value"))
+        );
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(parse("0", number), Ok((0.0, "")));
+        assert_eq!(parse("-0", number), Ok((-0.0, "")));
+        assert_eq!(parse("123", number), Ok((123.0, "")));
+        assert_eq!(parse("0123", number), Ok((0.0, "123")));
+        assert_eq!(parse("1.25", number), Ok((1.25, "")));
+        assert_eq!(parse("1E+2", number), Ok((100.0, "")));
+        assert_eq!(parse("1e-2", number), Ok((0.01, "")));
+        assert_eq!(
+            parse("1.", number),
+            Err(Error::expect(Sequence::Decimal).but_found_end().into_fail())
+        );
+        assert_eq!(
+            parse("1e", number),
+            Err(Error::expect(Sequence::Decimal).but_found_end().into_fail())
+        );
+        assert!(parse("-", number).is_err());
+        assert!(parse(".5", number).is_err());
+    }
+
+    #[test]
+    fn test_string() {
+        assert_eq!(parse(r#""""#, string), Ok((String::new(), "")));
+        assert_eq!(
+            parse(r#""a\"b\\c\/d\n" rest"#, string),
+            Ok(("a\"b\\c/d\n".to_owned(), " rest"))
+        );
+        assert_eq!(parse(r#""é€""#, string), Ok(("é€".to_owned(), "")));
+        assert_eq!(parse(r#""😀""#, string), Ok(("😀".to_owned(), "")));
+        assert_eq!(parse(r#""ß💣""#, string), Ok(("ß💣".to_owned(), "")));
+        assert!(parse(r#""\ud83d""#, string).is_err());
+        assert_eq!(
+            parse(r#""\ud83d\u0041""#, string),
+            Err(Error::invalid().into_fail())
+        );
+        assert_eq!(
+            parse(r#""\udc00""#, string),
+            Err(Error::invalid().into_fail())
+        );
+        assert_eq!(
+            parse(r#""\udc00""#, optional(string)),
+            Err(Error::invalid().into_fail())
+        );
+        assert_eq!(
+            parse(r#""\u12""#, string),
+            Err(Error::expect(Sequence::Hexadecimal).into_fail())
+        );
+        assert_eq!(parse(r#""\x""#, string), Err(Error::found('x').into_fail()));
+        assert_eq!(
+            parse("\"a\nb\"", string),
+            Err(Error::expect('"').but_found('\n').into_fail())
+        );
+        assert_eq!(
+            parse("\"abc", string),
+            Err(Error::expect('"').but_found_end().into_fail())
+        );
+        assert_eq!(parse("abc", string), Err(Error::expect('"').but_found('a')));
+    }
+
+    #[test]
+    fn test_array() {
+        assert_eq!(parse("[]", array), Ok((vec![], "")));
+        assert_eq!(parse("[ ]", array), Ok((vec![], "")));
+        assert_eq!(
+            parse("[1, true ,null]", array),
+            Ok((
+                vec![
+                    JsonValue::Number(1.0),
+                    JsonValue::Bool(true),
+                    JsonValue::Null
+                ],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("[1,]", array),
+            Err(Error::expect(']')
+                .but_found(',')
+                .into_fail()
+                .with_context("array"))
+        );
+        assert_eq!(
+            parse("[1", array),
+            Err(Error::expect(']')
+                .but_found_end()
+                .into_fail()
+                .with_context("array"))
+        );
+    }
+
+    #[test]
+    fn test_nesting() {
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        assert!(parse(&nested(MAX_NESTING), value).is_ok());
+        assert_eq!(
+            parse(&nested(MAX_NESTING + 1), value)
+                .unwrap_err()
+                .get_limit(),
+            Some(Limit::Depth(MAX_NESTING))
+        );
+
+        let err = parse(&"[".repeat(200_000), value).unwrap_err();
+
+        assert!(err.is_fail());
+        assert_eq!(err.get_limit(), Some(Limit::Depth(MAX_NESTING)));
+        assert_eq!(
+            parse(&r#"{"a":"#.repeat(200_000), object)
+                .unwrap_err()
+                .get_limit(),
+            Some(Limit::Depth(MAX_NESTING))
+        );
+    }
+
+    #[test]
+    fn test_object() {
+        assert_eq!(parse("{}", object), Ok((vec![], "")));
+        assert_eq!(
+            parse(r#"{ "a" : 1 , "b":"c" }"#, object),
+            Ok((
+                vec![
+                    ("a".to_owned(), JsonValue::Number(1.0)),
+                    ("b".to_owned(), JsonValue::String("c".to_owned())),
+                ],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse(r#"{"a" 1}"#, object),
+            Err(Error::expect(':')
+                .but_found('1')
+                .into_fail()
+                .with_context("object"))
+        );
+        assert_eq!(
+            parse(r#"{"a":}"#, object),
+            Err(Error::expect('{')
+                .but_found('}')
+                .into_fail()
+                .with_context("value"))
+        );
+    }
+}
@@ -1,19 +1,81 @@
 pub mod character;
+pub mod cli;
 pub mod combinator;
+pub mod cst;
+pub mod cursor;
+pub mod datetime;
+#[cfg(feature = "encoding")]
+pub mod encoding;
 pub mod error;
+pub mod formats;
+pub mod grammar;
+pub mod http;
+pub mod ini;
+pub mod input;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod limit;
+pub mod logs;
+pub mod markdown;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod node;
+#[cfg(feature = "unicode")]
+pub mod normalize;
+pub mod number;
+pub mod options;
 pub mod parser;
+#[cfg(feature = "pest")]
+pub mod pest;
+pub mod position;
+pub mod rule;
 pub mod sequence;
+pub mod shell;
+mod state;
+pub mod template;
+pub mod testing;
+pub mod text;
+pub mod xml;
 
 pub mod prelude {
-    pub use crate::combinator::branch::{branch, either, optional};
+    pub use crate::character::CharPredicate;
+    pub use crate::combinator::branch::{
+        branch, branch_indexed, branch_iter, branch_tagged, either, either2, either3, either4,
+        either5, either6, keywords, longest, opt_or, opt_or_else, optional, optional_if,
+        parse_all_alternatives, parse_ambiguous, parse_backtracking, Ambiguity, ArmStats,
+        BranchStats, Either2, Either3, Either4, Either5, Either6,
+    };
+    pub use crate::combinator::join::join;
+    pub use crate::combinator::nesting::{
+        balanced, balanced_quoted, block, braces, brackets, parens, until_balanced,
+        until_balanced_quoted,
+    };
+    #[cfg(feature = "unicode")]
+    pub use crate::combinator::normalized;
+    pub use crate::combinator::recovery::{
+        parse_partial, partial, partial_or, partial_until, Partial,
+    };
     pub use crate::combinator::series::{
-        delimited, leading, list, pair, repeat, series, trailing, trio,
+        consume_many, count_matches, delimited, delimited_keep, delimited_list, delimited_outer,
+        fold_list, leading, list, pair, punctuated, punctuated_trailing, quoted, repeat,
+        repeat_consume, series, skip_many, skip_many1, surrounded_by, trailing, trio,
     };
+    pub use crate::combinator::sugar::{sugar, Sugar};
     pub use crate::combinator::{
-        complete, consume, context, escaped, fail, fold, map, map_err, not, pass, peek, unescape,
+        at_line_start, boxed, complete, consume, consumed, context, escape, escaped, fail, fold,
+        fold_case, lexeme, map, map_err, not, not_preceded_by, owned, pass, peek, preceded_by,
+        rule, spanned, unescape, with_position, EscapeRules, IntoOwned,
+    };
+    pub use crate::error::{Error, Expect, Renderer};
+    pub use crate::input::{parse_chunks, parse_with_progress, Pipeline};
+    pub use crate::limit::{with_budget, with_deadline, Limit, ParseBudget};
+    pub use crate::node::{children, node, Node};
+    pub use crate::options::{NewlinePolicy, ParseOptions};
+    pub use crate::parser::{
+        offset, parse, parse_at, parse_prefix, parse_with, peek_char, peek_slice, take, take_bytes,
+        take_till, take_while, take_while0, BoxedParser, Output, Parser, Prefix,
     };
-    pub use crate::error::{Error, Expect};
-    pub use crate::parser::{parse, take, take_while, Output, Parser};
-    pub use crate::sequence::end;
+    pub use crate::position::{Position, Span, Spanned};
+    pub use crate::sequence::{at_end, end, not_at_end, ws};
     pub use crate::{character, sequence};
 }
@@ -0,0 +1,173 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::parser::Parser;
+use crate::state;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Limit {
+    Budget(usize),
+    Deadline(Duration),
+    Depth(usize),
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Budget(steps) => write!(f, "budget of {} steps", steps),
+            Self::Deadline(duration) => write!(f, "deadline of {:?}", duration),
+            Self::Depth(depth) => write!(f, "depth of {} levels", depth),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParseBudget {
+    steps: usize,
+}
+
+impl ParseBudget {
+    pub fn new(steps: usize) -> Self {
+        Self { steps }
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+pub fn with_budget<'a, O>(budget: ParseBudget, parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    move |input| state::with_budget(budget.steps, || parser.parse(input))
+}
+
+pub fn with_deadline<'a, O>(duration: Duration, parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    move |input| state::with_deadline(duration, || parser.parse(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::Character;
+    use crate::combinator::branch::branch;
+    use crate::combinator::series::{list, pair, repeat};
+    use crate::error::Error;
+    use crate::parser::{parse, Output};
+    use crate::sequence::{alphabetic, at_least};
+
+    fn limit<O>(output: Output<O>) -> Option<Limit> {
+        output.err().as_ref().and_then(Error::get_limit)
+    }
+
+    #[test]
+    fn test_with_budget() {
+        let words = || list(alphabetic, ' ');
+
+        assert_eq!(
+            parse("a b c", with_budget(ParseBudget::new(100), words())),
+            Ok((vec!["a", "b", "c"], ""))
+        );
+        assert_eq!(
+            limit(parse("a b c", with_budget(ParseBudget::new(4), words()))),
+            Some(Limit::Budget(4))
+        );
+        assert_eq!(
+            limit(parse("x", with_budget(ParseBudget::new(0), 'x'))),
+            Some(Limit::Budget(0))
+        );
+        assert!(parse("a b c", with_budget(ParseBudget::new(4), words()))
+            .unwrap_err()
+            .is_fail());
+    }
+
+    #[test]
+    fn test_with_budget_repetition() {
+        let digits = || at_least(1, Character::Decimal);
+
+        assert_eq!(
+            limit(parse("123456", with_budget(ParseBudget::new(3), digits()))),
+            Some(Limit::Budget(3))
+        );
+        assert_eq!(
+            parse("123456", with_budget(ParseBudget::new(100), digits())),
+            Ok(("123456", ""))
+        );
+    }
+
+    #[test]
+    fn test_with_budget_backtracking() {
+        let ab = || branch((pair('a', 'b'), pair('a', 'c'), pair('a', 'd')));
+
+        assert_eq!(
+            limit(parse("ad", with_budget(ParseBudget::new(4), ab()))),
+            Some(Limit::Budget(4))
+        );
+        assert_eq!(
+            parse("ad", with_budget(ParseBudget::new(100), ab())),
+            Ok((('a', 'd'), ""))
+        );
+    }
+
+    #[test]
+    fn test_with_budget_nested() {
+        let inner = with_budget(ParseBudget::new(100), list(alphabetic, ' '));
+
+        assert_eq!(
+            limit(parse("a b c", with_budget(ParseBudget::new(4), inner))),
+            Some(Limit::Budget(4))
+        );
+
+        let inner = with_budget(ParseBudget::new(2), list(alphabetic, ' '));
+
+        assert_eq!(
+            limit(parse("a b c", with_budget(ParseBudget::new(100), inner))),
+            Some(Limit::Budget(2))
+        );
+        assert_eq!(
+            parse("a b", pair(with_budget(ParseBudget::new(100), 'a'), " b")),
+            Ok((('a', " b"), ""))
+        );
+    }
+
+    #[test]
+    fn test_with_deadline() {
+        let words = || list(alphabetic, ' ');
+
+        assert_eq!(
+            parse("a b c", with_deadline(Duration::from_secs(60), words())),
+            Ok((vec!["a", "b", "c"], ""))
+        );
+
+        let input = "a".repeat(10_000);
+
+        assert_eq!(
+            limit(parse(&input, with_deadline(Duration::ZERO, repeat('a')))),
+            Some(Limit::Deadline(Duration::ZERO))
+        );
+        assert_eq!(
+            limit(parse(
+                &input,
+                with_deadline(
+                    Duration::from_secs(60),
+                    with_deadline(Duration::ZERO, repeat('a'))
+                )
+            )),
+            Some(Limit::Deadline(Duration::ZERO))
+        );
+        assert_eq!(
+            parse(&input, with_deadline(Duration::MAX, repeat('a'))).map(|(out, _)| out.len()),
+            Ok(10_000)
+        );
+    }
+
+    #[test]
+    fn test_limit_display() {
+        assert_eq!(
+            Error::exceeded(Limit::Budget(10)).to_string(),
+            "Error:\nFound limit: budget of 10 steps"
+        );
+        assert_eq!(
+            Error::exceeded(Limit::Deadline(Duration::from_millis(5))).to_string(),
+            "Error:\nFound limit: deadline of 5ms"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use crate::character::is_linebreak;
+use crate::combinator::branch::{either, optional};
+use crate::combinator::series::{delimited, leading, pair, repeat, trailing};
+use crate::combinator::{context, fail, map};
+use crate::error::Error;
+use crate::parser::{take_while, Output, Parser};
+use crate::sequence::decimal;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyslogRecord<'a> {
+    pub facility: u8,
+    pub severity: u8,
+    pub version: u8,
+    pub timestamp: Option<&'a str>,
+    pub hostname: Option<&'a str>,
+    pub app_name: Option<&'a str>,
+    pub proc_id: Option<&'a str>,
+    pub msg_id: Option<&'a str>,
+    pub structured_data: Vec<StructuredData<'a>>,
+    pub message: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructuredData<'a> {
+    pub id: &'a str,
+    pub params: Vec<(&'a str, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommonLogRecord<'a> {
+    pub host: &'a str,
+    pub ident: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub timestamp: &'a str,
+    pub request: &'a str,
+    pub method: Option<&'a str>,
+    pub path: Option<&'a str>,
+    pub protocol: Option<&'a str>,
+    pub status: u16,
+    pub size: Option<u64>,
+}
+
+pub fn syslog_rfc5424(input: &str) -> Output<SyslogRecord> {
+    let (priority, rem) = context("priority", delimited('<', decimal, fail('>'))).parse(input)?;
+    let priority = match priority.parse::<u8>() {
+        Ok(priority) if priority <= 191 => priority,
+        _ => return Err(Error::invalid().with_context("priority")),
+    };
+    let (version, rem) = context("version", decimal).parse(rem)?;
+    let version = version
+        .parse()
+        .map_err(|_| Error::invalid().with_context("version"))?;
+    let (timestamp, rem) = context("timestamp", leading(fail(' '), nil_or(field))).parse(rem)?;
+    let (hostname, rem) = context("hostname", leading(fail(' '), nil_or(field))).parse(rem)?;
+    let (app_name, rem) = context("app name", leading(fail(' '), nil_or(field))).parse(rem)?;
+    let (proc_id, rem) = context("proc id", leading(fail(' '), nil_or(field))).parse(rem)?;
+    let (msg_id, rem) = context("msg id", leading(fail(' '), nil_or(field))).parse(rem)?;
+    let (structured_data, rem) = context(
+        "structured data",
+        leading(
+            fail(' '),
+            either(map('-', |_| Vec::new()), repeat(structured_element)),
+        ),
+    )
+    .parse(rem)?;
+    let (message, rem) = optional(leading(' ', rest_of_line)).parse(rem)?;
+
+    Ok((
+        SyslogRecord {
+            facility: priority / 8,
+            severity: priority % 8,
+            version,
+            timestamp,
+            hostname,
+            app_name,
+            proc_id,
+            msg_id,
+            structured_data,
+            message: message.filter(|message| !message.is_empty()),
+        },
+        rem,
+    ))
+}
+
+pub fn apache_common(input: &str) -> Output<CommonLogRecord> {
+    let (host, rem) = context("host", field).parse(input)?;
+    let (ident, rem) = context("ident", leading(fail(' '), nil_or(field))).parse(rem)?;
+    let (user, rem) = context("user", leading(fail(' '), nil_or(field))).parse(rem)?;
+    let (timestamp, rem) = context(
+        "timestamp",
+        leading(
+            fail(' '),
+            delimited(fail('['), fail(take_while(|ch: char| ch != ']')), fail(']')),
+        ),
+    )
+    .parse(rem)?;
+    let (request, rem) = context(
+        "request",
+        leading(
+            fail(' '),
+            delimited(
+                fail('"'),
+                map(
+                    optional(take_while(|ch: char| ch != '"')),
+                    Option::unwrap_or_default,
+                ),
+                fail('"'),
+            ),
+        ),
+    )
+    .parse(rem)?;
+    let (status, rem) = context("status", leading(fail(' '), fail(decimal))).parse(rem)?;
+    let status = status
+        .parse()
+        .map_err(|_| Error::invalid().with_context("status"))?;
+    let (size, rem) = context("size", leading(fail(' '), nil_or(fail(decimal)))).parse(rem)?;
+    let size = match size.map(str::parse) {
+        Some(Ok(size)) => Some(size),
+        Some(Err(_)) => return Err(Error::invalid().with_context("size")),
+        None => None,
+    };
+    let mut parts = request.split(' ');
+    let (method, path, protocol) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(path), protocol, None) if !method.is_empty() && !path.is_empty() => {
+            (Some(method), Some(path), protocol)
+        }
+        _ => (None, None, None),
+    };
+
+    Ok((
+        CommonLogRecord {
+            host,
+            ident,
+            user,
+            timestamp,
+            request,
+            method,
+            path,
+            protocol,
+            status,
+            size,
+        },
+        rem,
+    ))
+}
+
+fn nil_or<'a>(parser: impl Parser<'a, &'a str>) -> impl Parser<'a, Option<&'a str>> {
+    move |input: &'a str| match input.chars().next() {
+        Some('-') if field(input).map(|(out, _)| out == "-").unwrap_or(false) => {
+            Ok((None, &input[1..]))
+        }
+        _ => parser.parse(input).map(|(out, rem)| (Some(out), rem)),
+    }
+}
+
+fn field(input: &str) -> Output<&str> {
+    take_while(|ch: char| ch.is_ascii_graphic()).parse(input)
+}
+
+fn rest_of_line(input: &str) -> Output<&str> {
+    map(
+        optional(take_while(|ch: char| !is_linebreak(ch))),
+        Option::unwrap_or_default,
+    )
+    .parse(input)
+}
+
+fn structured_element(input: &str) -> Output<StructuredData> {
+    delimited(
+        '[',
+        pair(
+            fail(sd_name),
+            map(
+                optional(repeat(leading(' ', fail(sd_param)))),
+                Option::unwrap_or_default,
+            ),
+        ),
+        fail(']'),
+    )
+    .parse(input)
+    .map(|((id, params), rem)| (StructuredData { id, params }, rem))
+}
+
+fn sd_param(input: &str) -> Output<(&str, String)> {
+    pair(
+        trailing(sd_name, fail('=')),
+        delimited(fail('"'), sd_value, fail('"')),
+    )
+    .parse(input)
+}
+
+fn sd_name(input: &str) -> Output<&str> {
+    take_while(|ch: char| ch.is_ascii_graphic() && !matches!(ch, '=' | ']' | '"')).parse(input)
+}
+
+fn sd_value(input: &str) -> Output<String> {
+    let mut iter = input.char_indices();
+    let mut out = String::new();
+
+    while let Some((idx, ch)) = iter.next() {
+        match ch {
+            '"' => return Ok((out, &input[idx..])),
+            '\\' => match iter.next() {
+                Some((_, ch)) if matches!(ch, '"' | '\\' | ']') => out.push(ch),
+                Some((_, ch)) => {
+                    out.push('\\');
+                    out.push(ch);
+                }
+                None => return Err(Error::found_end().into_fail()),
+            },
+            _ => out.push(ch),
+        }
+    }
+
+    Err(Error::expect('"').but_found_end().into_fail())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use crate::sequence::Sequence;
+
+    #[test]
+    fn test_syslog_rfc5424() {
+        assert_eq!(
+            parse(
+                "<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - 'su root' failed for lonvick on /dev/pts/8\nnext",
+                syslog_rfc5424
+            ),
+            Ok((
+                SyslogRecord {
+                    facility: 4,
+                    severity: 2,
+                    version: 1,
+                    timestamp: Some("2003-10-11T22:14:15.003Z"),
+                    hostname: Some("mymachine.example.com"),
+                    app_name: Some("su"),
+                    proc_id: None,
+                    msg_id: Some("ID47"),
+                    structured_data: vec![],
+                    message: Some("'su root' failed for lonvick on /dev/pts/8"),
+                },
+                "\nnext"
+            ))
+        );
+        assert_eq!(
+            parse(
+                r#"<165>1 - host app 1234 - [exampleSDID@32473 iut="3" eventSource="App\"li\]cation"][other@1]"#,
+                syslog_rfc5424
+            ),
+            Ok((
+                SyslogRecord {
+                    facility: 20,
+                    severity: 5,
+                    version: 1,
+                    timestamp: None,
+                    hostname: Some("host"),
+                    app_name: Some("app"),
+                    proc_id: Some("1234"),
+                    msg_id: None,
+                    structured_data: vec![
+                        StructuredData {
+                            id: "exampleSDID@32473",
+                            params: vec![
+                                ("iut", "3".to_owned()),
+                                ("eventSource", "App\"li]cation".to_owned()),
+                            ],
+                        },
+                        StructuredData {
+                            id: "other@1",
+                            params: vec![],
+                        },
+                    ],
+                    message: None,
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("34>1", syslog_rfc5424),
+            Err(Error::expect('<').but_found('3').with_context("priority"))
+        );
+        assert_eq!(
+            parse("<999>1", syslog_rfc5424),
+            Err(Error::invalid().with_context("priority"))
+        );
+        assert_eq!(
+            parse("<34>1 - - - - -", syslog_rfc5424),
+            Err(Error::expect(' ')
+                .but_found_end()
+                .into_fail()
+                .with_context("structured data"))
+        );
+    }
+
+    #[test]
+    fn test_apache_common() {
+        assert_eq!(
+            parse(
+                r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326"#,
+                apache_common
+            ),
+            Ok((
+                CommonLogRecord {
+                    host: "127.0.0.1",
+                    ident: None,
+                    user: Some("frank"),
+                    timestamp: "10/Oct/2000:13:55:36 -0700",
+                    request: "GET /apache_pb.gif HTTP/1.0",
+                    method: Some("GET"),
+                    path: Some("/apache_pb.gif"),
+                    protocol: Some("HTTP/1.0"),
+                    status: 200,
+                    size: Some(2326),
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            parse(
+                r#"::1 - - [01/Jan/2020:00:00:00 +0000] "-" 408 -"#,
+                apache_common
+            ),
+            Ok((
+                CommonLogRecord {
+                    host: "::1",
+                    ident: None,
+                    user: None,
+                    timestamp: "01/Jan/2020:00:00:00 +0000",
+                    request: "-",
+                    method: None,
+                    path: None,
+                    protocol: None,
+                    status: 408,
+                    size: None,
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            parse(r#"host - - [now] "GET /" abc 0"#, apache_common),
+            Err(Error::expect(Sequence::Decimal)
+                .but_found('a')
+                .into_fail()
+                .with_context("status"))
+        );
+    }
+}
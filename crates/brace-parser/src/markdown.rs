@@ -0,0 +1,709 @@
+use crate::error::{Error, Expect};
+use crate::parser::{offset, source_offset, Output, Parser};
+use crate::position::{Columns, Position, Span, Spanned};
+use crate::sequence::Sequence;
+
+const MAX_NESTING: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Inline<'a> {
+    Text(&'a str),
+    Code(&'a str),
+    Emphasis(Vec<Spanned<Inline<'a>>>),
+    Strong(Vec<Spanned<Inline<'a>>>),
+    Link(Link<'a>),
+    Image(Link<'a>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Link<'a> {
+    pub content: Vec<Spanned<Inline<'a>>>,
+    pub destination: &'a str,
+    pub title: Option<&'a str>,
+}
+
+pub fn inlines<'a>(source: &'a str) -> impl Parser<'a, Vec<Spanned<Inline<'a>>>> {
+    move |input: &'a str| {
+        let mut scanner = Scanner::new(source, input)?;
+
+        scanner.run(false);
+        Ok((scanner.finish(), &input[input.len()..]))
+    }
+}
+
+pub fn link<'a>(source: &'a str) -> impl Parser<'a, Spanned<Inline<'a>>> {
+    move |input: &'a str| link_at(source, input, false)
+}
+
+pub fn image<'a>(source: &'a str) -> impl Parser<'a, Spanned<Inline<'a>>> {
+    move |input: &'a str| link_at(source, input, true)
+}
+
+pub fn code_span(input: &str) -> Output<&str> {
+    let run = backticks(input);
+
+    if run == 0 {
+        return Err(missing(input, '`'));
+    }
+
+    let rest = &input[run..];
+    let mut idx = 0;
+
+    while let Some(pos) = rest[idx..].find('`') {
+        let start = idx + pos;
+        let len = backticks(&rest[start..]);
+
+        if len == run {
+            let code = &rest[..start];
+            let code = match code.starts_with(' ') && code.ends_with(' ') {
+                true if !code.trim_matches(' ').is_empty() => &code[1..code.len() - 1],
+                _ => code,
+            };
+
+            return Ok((code, &rest[start + len..]));
+        }
+
+        idx = start + len;
+    }
+
+    Err(Error::expect(Sequence::custom(&input[..run])).but_found_end())
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Delim {
+    ch: char,
+    start: Position,
+    count: usize,
+    original: usize,
+    can_open: bool,
+    can_close: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bracket {
+    image: bool,
+    start: Position,
+}
+
+enum Item<'a> {
+    Node(Spanned<Inline<'a>>),
+    Delim(Delim),
+    Bracket(Bracket),
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    text: &'a str,
+    base: usize,
+    columns: Columns,
+    pos: Position,
+    plain: (usize, Position),
+    items: Vec<Item<'a>>,
+    brackets: Vec<usize>,
+    inactive: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(source: &'a str, text: &'a str) -> Result<Self, Error> {
+        let columns = Columns::default();
+        let base = source_offset(source, text)?;
+        let pos = Position::new_with(source, base, columns);
+
+        Ok(Self {
+            source,
+            text,
+            base,
+            columns,
+            pos,
+            plain: (0, pos),
+            items: Vec::new(),
+            brackets: Vec::new(),
+            inactive: 0,
+        })
+    }
+
+    fn run(&mut self, first: bool) -> Option<Output<'a, Spanned<Inline<'a>>>> {
+        let text = self.text;
+        let mut idx = 0;
+
+        while let Some(ch) = text[idx..].chars().next() {
+            let rest = &text[idx..];
+            let len = match ch {
+                '\\' => match rest[1..].chars().next() {
+                    Some(next) if next.is_ascii_punctuation() => {
+                        let node = self.node(idx, idx + 2, Inline::Text(&rest[1..2]));
+
+                        self.push(idx, Item::Node(node), 2)
+                    }
+                    _ => 1,
+                },
+                '`' => match code_span(rest) {
+                    Ok((code, after)) => {
+                        let len = rest.len() - after.len();
+                        let node = self.node(idx, idx + len, Inline::Code(code));
+
+                        self.push(idx, Item::Node(node), len)
+                    }
+                    Err(_) => backticks(rest),
+                },
+                '!' | '[' if rest.starts_with('[') || rest.starts_with("![") => {
+                    let bracket = Bracket {
+                        image: ch == '!',
+                        start: self.at(idx),
+                    };
+                    let len = self.push(idx, Item::Bracket(bracket), 1 + usize::from(ch == '!'));
+
+                    self.brackets.push(self.items.len() - 1);
+                    len
+                }
+                ']' => match self.close(idx) {
+                    Some(Ok(after)) => {
+                        let len = rest.len() - after.len();
+
+                        if first && self.brackets.is_empty() {
+                            return self.items.pop().map(|item| match item {
+                                Item::Node(link) => Ok((link, after)),
+                                _ => Err(Error::invalid()),
+                            });
+                        }
+
+                        len
+                    }
+                    Some(Err(err)) if first && self.brackets.is_empty() => return Some(Err(err)),
+                    _ => 1,
+                },
+                '*' | '_' => {
+                    let delim = self.delim(idx, ch);
+
+                    self.push(idx, Item::Delim(delim), delim.count)
+                }
+                _ => ch.len_utf8(),
+            };
+
+            idx += len;
+        }
+
+        None
+    }
+
+    fn close(&mut self, idx: usize) -> Option<Result<&'a str, Error>> {
+        let opener = self.brackets.pop()?;
+        let bracket = match &self.items[opener] {
+            Item::Bracket(bracket) => *bracket,
+            _ => return None,
+        };
+        let active = bracket.image || self.brackets.len() >= self.inactive;
+
+        self.inactive = self.inactive.min(self.brackets.len());
+
+        if !active {
+            return Some(Err(Error::invalid()
+                .into_pass()
+                .with_context("links may not contain other links")));
+        }
+
+        let ((destination, title), after) = match tail(&self.text[idx + 1..]) {
+            Ok(tail) => tail,
+            Err(err) => return Some(Err(err)),
+        };
+        let end = self.text.len() - after.len();
+
+        self.flush(idx);
+
+        let link = Link {
+            content: emphasis(self.source, self.items.drain(opener + 1..).collect()),
+            destination,
+            title,
+        };
+        let span = Span {
+            start: bracket.start,
+            end: self.at(end),
+        };
+
+        self.items[opener] = Item::Node(Spanned::new(
+            match bracket.image {
+                true => Inline::Image(link),
+                false => Inline::Link(link),
+            },
+            span,
+        ));
+        self.plain = (end, span.end);
+
+        if !bracket.image {
+            self.inactive = self.brackets.len();
+        }
+
+        Some(Ok(after))
+    }
+
+    fn delim(&mut self, idx: usize, ch: char) -> Delim {
+        let text = self.text;
+        let count = text[idx..]
+            .bytes()
+            .take_while(|byte| *byte == ch as u8)
+            .count();
+        let prev = text[..idx].chars().next_back();
+        let next = text[idx + count..].chars().next();
+        let left = !is_space(next) && (!is_punct(next) || is_space(prev) || is_punct(prev));
+        let right = !is_space(prev) && (!is_punct(prev) || is_space(next) || is_punct(next));
+        let (can_open, can_close) = match ch {
+            '*' => (left, right),
+            _ => (
+                left && (!right || is_punct(prev)),
+                right && (!left || is_punct(next)),
+            ),
+        };
+
+        Delim {
+            ch,
+            start: self.at(idx),
+            count,
+            original: count,
+            can_open,
+            can_close,
+        }
+    }
+
+    fn push(&mut self, idx: usize, item: Item<'a>, len: usize) -> usize {
+        self.flush(idx);
+        self.items.push(item);
+        self.plain = (idx + len, self.at(idx + len));
+
+        len
+    }
+
+    fn flush(&mut self, idx: usize) {
+        let (plain, start) = self.plain;
+
+        if plain < idx {
+            let end = self.at(idx);
+
+            self.items.push(Item::Node(Spanned::new(
+                Inline::Text(&self.text[plain..idx]),
+                Span { start, end },
+            )));
+            self.plain = (idx, end);
+        }
+    }
+
+    fn finish(mut self) -> Vec<Spanned<Inline<'a>>> {
+        self.flush(self.text.len());
+        emphasis(self.source, self.items)
+    }
+
+    fn node(&mut self, start: usize, end: usize, value: Inline<'a>) -> Spanned<Inline<'a>> {
+        let start = self.at(start);
+
+        Spanned::new(
+            value,
+            Span {
+                start,
+                end: self.at(end),
+            },
+        )
+    }
+
+    fn at(&mut self, idx: usize) -> Position {
+        let offset = self.base + idx;
+
+        if offset < self.pos.offset {
+            return Position::new_with(self.source, offset, self.columns);
+        }
+
+        self.pos = self.pos.advance(self.source, offset, self.columns);
+        self.pos
+    }
+}
+
+fn emphasis<'a>(source: &'a str, items: Vec<Item<'a>>) -> Vec<Spanned<Inline<'a>>> {
+    let mut out: Vec<Item<'a>> = Vec::with_capacity(items.len());
+    let mut openers: Vec<usize> = Vec::new();
+    let mut bottom = [0; 12];
+
+    for item in items {
+        let mut close = match item {
+            Item::Delim(delim) if delim.can_close => delim,
+            item => {
+                if let Item::Delim(delim) = &item {
+                    if delim.can_open {
+                        openers.push(out.len());
+                    }
+                }
+
+                out.push(item);
+                continue;
+            }
+        };
+        let key =
+            usize::from(close.ch == '_') * 6 + close.original % 3 * 2 + usize::from(close.can_open);
+
+        while close.count > 0 {
+            let floor = bottom[key].min(openers.len());
+            let found = (floor..openers.len()).rev().find(
+                |idx| matches!(&out[openers[*idx]], Item::Delim(open) if pairs(open, &close)),
+            );
+            let idx = match found {
+                Some(idx) => idx,
+                None => {
+                    bottom[key] = openers.len();
+                    break;
+                }
+            };
+            let opener = openers[idx];
+            let children = finish(source, out.drain(opener + 1..).collect());
+            let (used, start) = match &mut out[opener] {
+                Item::Delim(open) => {
+                    let used = match open.count >= 2 && close.count >= 2 {
+                        true => 2,
+                        false => 1,
+                    };
+
+                    open.count -= used;
+                    (used, shift(open.start, open.count))
+                }
+                _ => break,
+            };
+            let span = Span {
+                start,
+                end: shift(close.start, used),
+            };
+
+            close.count -= used;
+            close.start = span.end;
+            out.push(Item::Node(Spanned::new(
+                match used {
+                    2 => Inline::Strong(children),
+                    _ => Inline::Emphasis(children),
+                },
+                span,
+            )));
+            openers.truncate(idx + 1);
+
+            if matches!(&out[opener], Item::Delim(open) if open.count == 0) {
+                openers.pop();
+            }
+
+            for bottom in bottom.iter_mut() {
+                *bottom = (*bottom).min(openers.len());
+            }
+        }
+
+        if close.count > 0 {
+            if close.can_open {
+                openers.push(out.len());
+            }
+
+            out.push(Item::Delim(close));
+        }
+    }
+
+    finish(source, out)
+}
+
+fn pairs(open: &Delim, close: &Delim) -> bool {
+    open.ch == close.ch
+        && open.can_open
+        && open.count > 0
+        && !((open.can_close || close.can_open)
+            && (open.original + close.original).is_multiple_of(3)
+            && !(open.original.is_multiple_of(3) && close.original.is_multiple_of(3)))
+}
+
+fn finish<'a>(source: &'a str, items: Vec<Item<'a>>) -> Vec<Spanned<Inline<'a>>> {
+    let mut out: Vec<Spanned<Inline<'a>>> = Vec::new();
+
+    for item in items {
+        let (start, len) = match item {
+            Item::Node(node) => {
+                merge(source, &mut out, node);
+                continue;
+            }
+            Item::Delim(delim) => (delim.start, delim.count),
+            Item::Bracket(bracket) => (bracket.start, 1 + usize::from(bracket.image)),
+        };
+
+        if len > 0 {
+            let text = &source[start.offset..start.offset + len];
+            let span = Span {
+                start,
+                end: shift(start, len),
+            };
+
+            merge(source, &mut out, Spanned::new(Inline::Text(text), span));
+        }
+    }
+
+    out
+}
+
+fn merge<'a>(source: &'a str, out: &mut Vec<Spanned<Inline<'a>>>, next: Spanned<Inline<'a>>) {
+    if let (Some(prev), Inline::Text(text)) = (out.last_mut(), &next.value) {
+        if let Inline::Text(prev_text) = prev.value {
+            let (start, end) = match (offset(source, prev_text), offset(source, text)) {
+                (Some(start), Some(end)) => (start, end),
+                _ => return out.push(next),
+            };
+
+            if start + prev_text.len() == end {
+                prev.value = Inline::Text(&source[start..end + text.len()]);
+                prev.span.end = next.span.end;
+                return;
+            }
+        }
+    }
+
+    out.push(next);
+}
+
+fn shift(pos: Position, len: usize) -> Position {
+    Position {
+        offset: pos.offset + len,
+        column: pos.column + len,
+        ..pos
+    }
+}
+
+fn link_at<'a>(source: &'a str, input: &'a str, image: bool) -> Output<'a, Spanned<Inline<'a>>> {
+    let open = match image {
+        true => "![",
+        false => "[",
+    };
+
+    if !input.starts_with(open) {
+        return Err(missing(input, Sequence::custom(open)));
+    }
+
+    match Scanner::new(source, input)?.run(true) {
+        Some(result) => result,
+        None => Err(Error::expect(']').but_found_end()),
+    }
+}
+
+fn tail(input: &str) -> Output<(&str, Option<&str>)> {
+    let rem = match input.strip_prefix('(') {
+        Some(rem) => rem.trim_start_matches(is_blank),
+        None => return Err(missing(input, '(')),
+    };
+    let (destination, rem) = destination(rem)?;
+    let after = rem.trim_start_matches(is_blank);
+    let (title, rem) = match after.chars().next() {
+        Some('"') | Some('\'') | Some('(') if after.len() < rem.len() => {
+            let (title, rem) = title(after)?;
+
+            (Some(title), rem.trim_start_matches(is_blank))
+        }
+        _ => (None, after),
+    };
+
+    match rem.strip_prefix(')') {
+        Some(rem) => Ok(((destination, title), rem)),
+        None => Err(missing(rem, ')')),
+    }
+}
+
+fn destination(input: &str) -> Output<&str> {
+    if let Some(rest) = input.strip_prefix('<') {
+        return match rest.find(&['>', '<', '\n'][..]) {
+            Some(end) if rest[end..].starts_with('>') => Ok((&rest[..end], &rest[end + 1..])),
+            Some(end) => Err(missing(&rest[end..], '>')),
+            None => Err(Error::expect('>').but_found_end()),
+        };
+    }
+
+    let mut depth = 0;
+    let mut chars = input.char_indices();
+
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            '(' if depth == MAX_NESTING => return Err(missing(&input[idx..], ')')),
+            '(' => depth += 1,
+            ')' if depth == 0 => return Ok((&input[..idx], &input[idx..])),
+            ')' => depth -= 1,
+            ch if ch.is_whitespace() || ch.is_control() => {
+                return Ok((&input[..idx], &input[idx..]))
+            }
+            _ => {}
+        }
+    }
+
+    Ok((input, &input[input.len()..]))
+}
+
+fn title(input: &str) -> Output<&str> {
+    let mut chars = input.char_indices();
+    let close = match chars.next() {
+        Some((_, '(')) => ')',
+        Some((_, quote)) => quote,
+        None => return Err(Error::expect(Sequence::custom("link title")).but_found_end()),
+    };
+
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            ch if ch == close => return Ok((&input[1..idx], &input[idx + 1..])),
+            _ => {}
+        }
+    }
+
+    Err(Error::expect(close).but_found_end())
+}
+
+fn backticks(input: &str) -> usize {
+    input.bytes().take_while(|byte| *byte == b'`').count()
+}
+
+fn is_blank(ch: char) -> bool {
+    matches!(ch, ' ' | '\t' | '\n')
+}
+
+fn is_space(ch: Option<char>) -> bool {
+    match ch {
+        Some(ch) => ch.is_whitespace(),
+        None => true,
+    }
+}
+
+fn is_punct(ch: Option<char>) -> bool {
+    matches!(ch, Some(ch) if ch.is_ascii_punctuation())
+}
+
+fn missing<T>(input: &str, expect: T) -> Error
+where
+    T: Into<Expect>,
+{
+    match input.chars().next() {
+        Some(ch) => Error::expect(expect).but_found(ch),
+        None => Error::expect(expect).but_found_end(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn shape(nodes: &[Spanned<Inline>]) -> String {
+        nodes
+            .iter()
+            .map(|node| match &node.value {
+                Inline::Text(text) => format!("text({})", text),
+                Inline::Code(code) => format!("code({})", code),
+                Inline::Emphasis(children) => format!("em({})", shape(children)),
+                Inline::Strong(children) => format!("strong({})", shape(children)),
+                Inline::Link(link) | Inline::Image(link) => format!(
+                    "{}({} -> {}{})",
+                    match node.value {
+                        Inline::Image(_) => "image",
+                        _ => "link",
+                    },
+                    shape(&link.content),
+                    link.destination,
+                    link.title
+                        .map(|title| format!(" {:?}", title))
+                        .unwrap_or_default()
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn render(source: &str) -> String {
+        shape(&parse(source, inlines(source)).unwrap().0)
+    }
+
+    #[test]
+    fn test_code_span() {
+        assert_eq!(parse("`` a`b ``rest", code_span), Ok(("a`b", "rest")));
+        assert_eq!(parse("` `", code_span), Ok((" ", "")));
+        assert_eq!(parse("`a``b`", code_span), Ok(("a``b", "")));
+        assert_eq!(
+            parse("``a`", code_span),
+            Err(Error::expect(Sequence::custom("``")).but_found_end())
+        );
+        assert_eq!(
+            parse("a", code_span),
+            Err(Error::expect('`').but_found('a'))
+        );
+    }
+
+    #[test]
+    fn test_emphasis() {
+        let source = "*a **b** c*";
+        let (nodes, _) = parse(source, inlines(source)).unwrap();
+
+        assert_eq!(shape(&nodes), "em(text(a ) strong(text(b)) text( c))");
+        assert_eq!(nodes[0].span, Span::new(source, 0, 11));
+
+        match &nodes[0].value {
+            Inline::Emphasis(children) => assert_eq!(children[1].span, Span::new(source, 3, 8)),
+            _ => unreachable!(),
+        }
+
+        assert_eq!(
+            render("*foo**bar**baz*"),
+            "em(text(foo) strong(text(bar)) text(baz))"
+        );
+        assert_eq!(render("**a*"), "text(*) em(text(a))");
+        assert_eq!(render("foo*bar*"), "text(foo) em(text(bar))");
+        assert_eq!(render("snake_case_name"), "text(snake_case_name)");
+        assert_eq!(render("_a_ * b *"), "em(text(a)) text( * b *)");
+        assert_eq!(render("\\*not\\*"), "text(*not) text(*)");
+        assert_eq!(render("*`*`*"), "em(code(*))");
+    }
+
+    #[test]
+    fn test_links() {
+        assert_eq!(
+            render("see [the `]` docs](http://x.com/a_(b) \"T\") now"),
+            "text(see ) link(text(the ) code(]) text( docs) -> http://x.com/a_(b) \"T\") text( now)"
+        );
+        assert_eq!(render("[a [b] c](u)"), "link(text(a [b] c) -> u)");
+        assert_eq!(
+            render("[a [b](c) d](e)"),
+            "text([a ) link(text(b) -> c) text( d](e))"
+        );
+        assert_eq!(
+            render("![alt *x*](<i m.png> 'pic')"),
+            "image(text(alt ) em(text(x)) -> i m.png \"pic\")"
+        );
+        assert_eq!(render("[a](b"), "text([a](b)");
+
+        let source = "[a](b";
+
+        assert_eq!(
+            parse(source, link(source)),
+            Err(Error::expect(')').but_found_end())
+        );
+        assert_eq!(
+            parse(source, image(source)),
+            Err(Error::expect(Sequence::custom("![")).but_found('['))
+        );
+    }
+
+    #[test]
+    fn test_pathological() {
+        let source = format!("{}x{}", "[".repeat(2000), "](u)".repeat(2000));
+        let (nodes, _) = parse(&source, inlines(&source)).unwrap();
+
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[1].span, Span::new(&source, 1999, 2005));
+        assert!(parse(&source, link(&source)).is_err());
+
+        let source = "*a_ ".repeat(20000);
+        let (nodes, _) = parse(&source, inlines(&source)).unwrap();
+
+        assert_eq!(shape(&nodes), format!("text({})", source));
+
+        let source = "*a* _b_\n".repeat(10000);
+        let (nodes, _) = parse(&source, inlines(&source)).unwrap();
+
+        assert_eq!(nodes.len(), 40000);
+        assert_eq!(nodes[39996].span, Span::new(&source, 79992, 79995));
+        assert_eq!(nodes[39996].span.start.line, 10000);
+    }
+}
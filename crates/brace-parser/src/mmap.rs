@@ -0,0 +1,410 @@
+use std::cell::Cell;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::{slice, str};
+
+use crate::error::{Error, Expect};
+use crate::parser::{Output, Parser};
+use crate::position::Span;
+use crate::state;
+
+const WINDOW: usize = 64 * 1024;
+
+#[derive(Debug)]
+enum Data {
+    #[cfg(all(unix, target_pointer_width = "64"))]
+    Mapped(*const u8, usize),
+    Owned(Vec<u8>),
+}
+
+#[derive(Debug)]
+pub struct Mapped {
+    path: PathBuf,
+    data: Data,
+    valid: Cell<usize>,
+}
+
+impl Mapped {
+    /// # Safety
+    ///
+    /// The file is mapped into memory rather than copied. The caller must
+    /// ensure that no process truncates or writes to the file while the
+    /// returned `Mapped`, or any slice borrowed from it, is alive. Otherwise
+    /// reads may observe changing data or fault with `SIGBUS`.
+    pub unsafe fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let data = map(path).map_err(|err| io_error(path, err))?;
+
+        Ok(Self {
+            path: path.to_owned(),
+            data,
+            valid: Cell::new(0),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.data {
+            #[cfg(all(unix, target_pointer_width = "64"))]
+            Data::Mapped(ptr, len) => unsafe { slice::from_raw_parts(*ptr, *len) },
+            Data::Owned(bytes) => bytes,
+        }
+    }
+
+    pub fn as_str(&self) -> Result<&str, Error> {
+        self.window(usize::MAX)
+    }
+
+    fn start(&self) -> Result<usize, Error> {
+        match self.as_bytes() {
+            [0xEF, 0xBB, 0xBF, ..] => Ok(3),
+            [0xFF, 0xFE, ..] => Err(self.error(Error::invalid().with_context("UTF-16LE input"))),
+            [0xFE, 0xFF, ..] => Err(self.error(Error::invalid().with_context("UTF-16BE input"))),
+            _ => Ok(0),
+        }
+    }
+
+    fn window(&self, len: usize) -> Result<&str, Error> {
+        let bytes = self.as_bytes();
+        let start = self.start()?;
+        let valid = self.valid.get().max(start);
+        let end = start.saturating_add(len).min(bytes.len());
+
+        if end > valid {
+            match str::from_utf8(&bytes[valid..end]) {
+                Ok(_) => self.valid.set(end),
+                Err(err) if err.error_len().is_none() && end < bytes.len() => {
+                    self.valid.set(valid + err.valid_up_to())
+                }
+                Err(err) => {
+                    let text = self.text(start, valid + err.valid_up_to());
+                    let span = Span::new(text, text.len(), text.len());
+
+                    return Err(self.locate(
+                        Error::invalid().with_context("UTF-8 input").with_span(span),
+                        start,
+                    ));
+                }
+            }
+        }
+
+        Ok(self.text(start, self.valid.get().max(start)))
+    }
+
+    fn text(&self, start: usize, end: usize) -> &str {
+        unsafe { str::from_utf8_unchecked(&self.as_bytes()[start..end]) }
+    }
+
+    pub fn parse<'a, P, O>(&'a self, parser: P) -> Output<'a, O>
+    where
+        P: Parser<'a, O>,
+    {
+        let start = self.start()?;
+        let mut len = WINDOW;
+
+        loop {
+            let input = self.window(len)?;
+            let complete = start + input.len() == self.as_bytes().len();
+            let (result, reach) = state::with_reach(input, || parser.parse(input));
+
+            match result {
+                Ok((out, rem)) if complete || !rem.is_empty() => return Ok((out, rem)),
+                Err(err) if complete || err.get_limit().is_some() => {
+                    return Err(match err.get_span() {
+                        Some(_) => self.locate(err, start),
+                        None => self.locate(err.with_span(Span::new(input, reach, reach)), start),
+                    })
+                }
+                _ => len = len.saturating_mul(2),
+            }
+        }
+    }
+
+    fn locate(&self, err: Error, start: usize) -> Error {
+        match err.get_span().copied() {
+            Some(mut span) => {
+                span.start.offset += start;
+                span.end.offset += start;
+                self.error(err.with_span(span))
+            }
+            None => self.error(err),
+        }
+    }
+
+    fn error(&self, err: Error) -> Error {
+        let ctx = match err.get_context() {
+            Some(ctx) => format!("{}: {}", self.path.display(), ctx),
+            None => self.path.display().to_string(),
+        };
+
+        err.with_context(ctx)
+    }
+}
+
+impl Drop for Mapped {
+    fn drop(&mut self) {
+        #[cfg(all(unix, target_pointer_width = "64"))]
+        if let Data::Mapped(ptr, len) = self.data {
+            unsafe {
+                sys::munmap(ptr as *mut _, len);
+            }
+        }
+    }
+}
+
+/// # Safety
+///
+/// The file must not be truncated or written to while it is being parsed.
+/// See [`Mapped::open`].
+pub unsafe fn parse_file<F, P, O>(path: F, parser: P) -> Result<O, Error>
+where
+    F: AsRef<Path>,
+    P: for<'a> Parser<'a, O>,
+{
+    let mapped = Mapped::open(path)?;
+    let (out, rem) = mapped.parse(parser)?;
+
+    match rem.chars().next() {
+        Some(ch) => {
+            let start = mapped.start()?;
+            let input = mapped.text(
+                start,
+                rem.as_ptr() as usize - mapped.as_bytes().as_ptr() as usize,
+            );
+            let err = Error::expect(Expect::End)
+                .but_found(ch)
+                .with_span(Span::new(input, input.len(), input.len()));
+
+            Err(mapped.locate(err, start))
+        }
+        None => Ok(out),
+    }
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> Error {
+    Error::invalid().with_context(format!("{}: {}", path.display(), err))
+}
+
+#[cfg(all(unix, target_pointer_width = "64"))]
+fn map(path: &Path) -> std::io::Result<Data> {
+    use std::os::unix::io::AsRawFd;
+
+    let file = File::open(path)?;
+    let len = file.metadata()?.len() as usize;
+
+    if len == 0 {
+        return Ok(Data::Owned(Vec::new()));
+    }
+
+    let ptr = unsafe {
+        sys::mmap(
+            std::ptr::null_mut(),
+            len,
+            sys::PROT_READ,
+            sys::MAP_PRIVATE,
+            file.as_raw_fd(),
+            0,
+        )
+    };
+
+    match ptr as isize {
+        -1 => fs::read(path).map(Data::Owned),
+        _ => Ok(Data::Mapped(ptr as *const u8, len)),
+    }
+}
+
+#[cfg(not(all(unix, target_pointer_width = "64")))]
+fn map(path: &Path) -> std::io::Result<Data> {
+    File::open(path)?;
+    fs::read(path).map(Data::Owned)
+}
+
+#[cfg(all(unix, target_pointer_width = "64"))]
+mod sys {
+    use std::os::raw::{c_int, c_void};
+
+    pub const PROT_READ: c_int = 1;
+    pub const MAP_PRIVATE: c_int = 2;
+
+    extern "C" {
+        pub fn mmap(
+            addr: *mut c_void,
+            len: usize,
+            prot: c_int,
+            flags: c_int,
+            fd: c_int,
+            offset: i64,
+        ) -> *mut c_void;
+        pub fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::owned;
+    use crate::combinator::series::{list, trailing};
+    use crate::sequence::{alphabetic, decimal, Sequence};
+
+    fn numbers(input: &str) -> Output<Vec<String>> {
+        owned(trailing(list(decimal, ','), '\n')).parse(input)
+    }
+
+    fn fixture(name: &str, contents: &[u8]) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("brace-parser-{}-{}", std::process::id(), name));
+
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let path = fixture("list.txt", b"\xEF\xBB\xBF1,22,333\n");
+
+        assert_eq!(
+            unsafe { parse_file(&path, numbers) },
+            Ok(vec!["1".to_owned(), "22".to_owned(), "333".to_owned()])
+        );
+
+        fs::write(&path, "1,2\n3\n").unwrap();
+
+        let input = "1,2\n3\n";
+        let err = unsafe { parse_file(&path, numbers) }.unwrap_err();
+
+        assert_eq!(
+            err,
+            Error::expect(Expect::End)
+                .but_found('3')
+                .with_span(Span::new(input, 4, 4))
+                .with_context(path.display().to_string())
+        );
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: in {} at 2:1\nExpected end of input\nFound character: '3'",
+                path.display()
+            )
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped() {
+        let path = fixture("words.txt", b"abc\nde1");
+        let mapped = unsafe { Mapped::open(&path) }.unwrap();
+        let input = mapped.as_str().unwrap();
+
+        assert_eq!(mapped.path(), path.as_path());
+        assert_eq!(mapped.as_bytes(), b"abc\nde1");
+        assert_eq!(mapped.as_str().map(str::as_ptr), Ok(input.as_ptr()));
+        assert_eq!(
+            mapped.parse(list(alphabetic, '\n')),
+            Ok((vec!["abc", "de"], "1"))
+        );
+        assert_eq!(
+            mapped.parse(list(decimal, '\n')),
+            Err(Error::expect(Sequence::Decimal)
+                .but_found('a')
+                .with_span(Span::new(input, 0, 0))
+                .with_context(path.display().to_string()))
+        );
+
+        drop(mapped);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped_invalid() {
+        let path = fixture("invalid.txt", b"ok\nbad \xFF here");
+        let err = unsafe { Mapped::open(&path) }
+            .unwrap()
+            .as_str()
+            .unwrap_err();
+
+        assert_eq!(
+            err.get_context(),
+            Some(format!("{}: UTF-8 input", path.display()).as_str())
+        );
+        assert_eq!(err.get_span().map(|span| span.start.offset), Some(7));
+        assert_eq!(err.get_span().map(|span| span.start.line), Some(2));
+
+        let mapped = unsafe { Mapped::open(&path) }.unwrap();
+
+        assert_eq!(mapped.as_str(), mapped.as_str());
+        assert!(mapped.as_str().is_err());
+
+        fs::write(&path, b"").unwrap();
+
+        assert_eq!(unsafe { Mapped::open(&path) }.unwrap().as_str(), Ok(""));
+
+        fs::remove_file(&path).unwrap();
+
+        let err = unsafe { parse_file(&path, numbers) }.unwrap_err();
+
+        assert!(err
+            .get_context()
+            .unwrap()
+            .starts_with(&path.display().to_string()));
+    }
+
+    #[test]
+    fn test_mapped_lazy() {
+        let mut contents = b"abc ".to_vec();
+
+        contents.extend("1,".repeat(WINDOW).as_bytes());
+        contents.extend(b"\xFF");
+
+        let path = fixture("lazy.txt", &contents);
+        let mapped = unsafe { Mapped::open(&path) }.unwrap();
+        let (out, rem) = mapped.parse(alphabetic).unwrap();
+
+        assert_eq!(out, "abc");
+        assert!(rem.starts_with(" 1,1,"));
+        assert_eq!(
+            mapped
+                .as_str()
+                .unwrap_err()
+                .get_span()
+                .map(|span| span.start.offset),
+            Some(contents.len() - 1)
+        );
+
+        fs::write(&path, "1,".repeat(WINDOW) + "1\n").unwrap();
+
+        assert_eq!(
+            unsafe { parse_file(&path, numbers) }.map(|out| out.len()),
+            Ok(WINDOW + 1)
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped_bom_offsets() {
+        let path = fixture("bom.txt", b"\xEF\xBB\xBFok\n\xFF");
+        let err = unsafe { Mapped::open(&path) }
+            .unwrap()
+            .as_str()
+            .unwrap_err();
+
+        assert_eq!(err.get_span().map(|span| span.start.offset), Some(6));
+        assert_eq!(err.get_span().map(|span| span.start.line), Some(2));
+
+        fs::write(&path, b"\xEF\xBB\xBF1,2\n3\n").unwrap();
+
+        let err = unsafe { parse_file(&path, numbers) }.unwrap_err();
+
+        assert_eq!(err.get_span().map(|span| span.start.offset), Some(7));
+        assert_eq!(err.get_span().map(|span| span.start.line), Some(2));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
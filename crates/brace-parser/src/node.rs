@@ -0,0 +1,205 @@
+use crate::combinator::{map, spanned};
+use crate::parser::Parser;
+use crate::position::Span;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node<K, O> {
+    pub kind: K,
+    pub span: Span,
+    pub value: O,
+}
+
+impl<K, O> Node<K, O> {
+    pub fn new(kind: K, span: Span, value: O) -> Self {
+        Self { kind, span, value }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(O) -> U) -> Node<K, U> {
+        Node {
+            kind: self.kind,
+            span: self.span,
+            value: f(self.value),
+        }
+    }
+
+    pub fn slice<'a>(&self, source: &'a str) -> &'a str {
+        self.span.slice(source)
+    }
+}
+
+pub fn node<'a, K, O>(
+    source: &'a str,
+    kind: K,
+    parser: impl Parser<'a, O>,
+) -> impl Parser<'a, Node<K, O>>
+where
+    K: Clone,
+{
+    map(spanned(source, parser), move |spanned| {
+        Node::new(kind.clone(), spanned.span, spanned.value)
+    })
+}
+
+pub fn children<'a, K, O, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<Node<K, O>>>
+where
+    T: Children<K, O>,
+{
+    map(parser, |out: T| {
+        let mut nodes = Vec::new();
+
+        out.collect_into(&mut nodes);
+        nodes
+    })
+}
+
+pub trait Children<K, O> {
+    fn collect_into(self, nodes: &mut Vec<Node<K, O>>);
+}
+
+impl<K, O> Children<K, O> for () {
+    fn collect_into(self, _: &mut Vec<Node<K, O>>) {}
+}
+
+impl<K, O> Children<K, O> for Node<K, O> {
+    fn collect_into(self, nodes: &mut Vec<Node<K, O>>) {
+        nodes.push(self);
+    }
+}
+
+impl<K, O, T> Children<K, O> for Option<T>
+where
+    T: Children<K, O>,
+{
+    fn collect_into(self, nodes: &mut Vec<Node<K, O>>) {
+        if let Some(inner) = self {
+            inner.collect_into(nodes);
+        }
+    }
+}
+
+impl<K, O, T> Children<K, O> for Vec<T>
+where
+    T: Children<K, O>,
+{
+    fn collect_into(self, nodes: &mut Vec<Node<K, O>>) {
+        for item in self {
+            item.collect_into(nodes);
+        }
+    }
+}
+
+macro_rules! impl_children {
+    ($(($idx:tt, $T:ident),)+) => {
+        impl_children!(@iter $(($idx, $T),)+;);
+    };
+
+    (@iter ($idx:tt, $T:ident),; $(($i:tt, $U:ident),)*) => {
+        impl_children!(@impl $(($i, $U),)* ($idx, $T),);
+    };
+
+    (@iter ($idx:tt, $T:ident), $(($a:tt, $b:ident),)+; $(($i:tt, $U:ident),)*) => {
+        impl_children!(@impl $(($i, $U),)* ($idx, $T),);
+        impl_children!(@iter $(($a, $b),)*; $(($i, $U),)* ($idx, $T),);
+    };
+
+    (@impl $(($idx:tt, $T:ident),)+) => {
+        impl<Kind, Value, $($T,)+> Children<Kind, Value> for ($($T,)+)
+        where
+            $($T: Children<Kind, Value>,)+
+        {
+            fn collect_into(self, nodes: &mut Vec<Node<Kind, Value>>) {
+                $(self.$idx.collect_into(nodes);)+
+            }
+        }
+    };
+}
+
+impl_children! {
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F),
+    (6, G),
+    (7, H),
+    (8, I),
+    (9, J),
+    (10, K),
+    (11, L),
+    (12, M),
+    (13, N),
+    (14, P),
+    (15, Q),
+    (16, R),
+    (17, S),
+    (18, T),
+    (19, U),
+    (20, V),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::branch::optional;
+    use crate::combinator::lexeme;
+    use crate::combinator::series::{leading, list, pair};
+    use crate::parser::parse;
+    use crate::sequence::{alphabetic, decimal};
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Kind {
+        Ident,
+        Number,
+    }
+
+    #[test]
+    fn test_node() {
+        let source = "abc 123";
+
+        assert_eq!(
+            parse(source, node(source, Kind::Ident, alphabetic)),
+            Ok((
+                Node::new(Kind::Ident, Span::new(source, 0, 3), "abc"),
+                " 123"
+            ))
+        );
+
+        let (out, _) = parse(&source[4..], node(source, Kind::Number, decimal)).unwrap();
+
+        assert_eq!(out.span, Span::new(source, 4, 7));
+        assert_eq!(out.slice(source), "123");
+        assert_eq!(out.map(|value| value.parse::<u32>().unwrap()).value, 123);
+    }
+
+    #[test]
+    fn test_children() {
+        let source = "f 1 2 3";
+        let call = children((
+            node(source, Kind::Ident, alphabetic),
+            list(leading(' ', node(source, Kind::Number, decimal)), ()),
+        ));
+        let (nodes, rem) = parse(source, call).unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(
+            nodes.iter().map(|node| &node.kind).collect::<Vec<_>>(),
+            vec![&Kind::Ident, &Kind::Number, &Kind::Number, &Kind::Number]
+        );
+        assert_eq!(nodes[3].span, Span::new(source, 6, 7));
+
+        let source = "x";
+        let maybe = children(pair(
+            node(source, Kind::Ident, lexeme(alphabetic)),
+            optional(node(source, Kind::Number, decimal)),
+        ));
+
+        assert_eq!(
+            parse(source, maybe),
+            Ok((
+                vec![Node::new(Kind::Ident, Span::new(source, 0, 1), "x")],
+                ""
+            ))
+        );
+    }
+}
@@ -0,0 +1,270 @@
+mod tables;
+
+use std::cmp::Ordering;
+
+use crate::error::Error;
+
+use self::tables::{COMBINING_CLASS, COMPOSING_STARTERS, COMPOSITION, DECOMPOSITION};
+
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const N_COUNT: u32 = V_COUNT * T_COUNT;
+const S_COUNT: u32 = L_COUNT * N_COUNT;
+
+pub fn nfd(input: &str) -> String {
+    let mut out = Vec::new();
+
+    for ch in input.chars() {
+        decompose(ch, |decomposed| out.push((ch, decomposed)));
+    }
+
+    reorder(&mut out);
+    out.into_iter().map(|(_, ch)| ch).collect()
+}
+
+pub fn nfc(input: &str) -> String {
+    let decomposed = nfd(input);
+    let mut out: Vec<char> = Vec::with_capacity(decomposed.len());
+    let mut starter = None;
+    let mut last = None;
+
+    for ch in decomposed.chars() {
+        let class = combining_class(ch);
+
+        if let Some(idx) = starter {
+            let blocked = matches!(last, Some(last) if last >= class);
+
+            if let Some(composed) = compose(out[idx], ch).filter(|_| !blocked) {
+                out[idx] = composed;
+                continue;
+            }
+        }
+
+        match class {
+            0 => {
+                starter = Some(out.len());
+                last = None;
+            }
+            _ => last = Some(class),
+        }
+
+        out.push(ch);
+    }
+
+    out.into_iter().collect()
+}
+
+pub fn is_nfc(input: &str) -> bool {
+    nfc(input) == input
+}
+
+pub(crate) fn match_prefix(
+    literal: &str,
+    input: &str,
+    eq: impl Fn(char, char) -> bool,
+) -> Result<usize, Error> {
+    let mut expected = Vec::new();
+
+    for ch in literal.chars() {
+        decompose(ch, |decomposed| expected.push((ch, decomposed)));
+    }
+
+    reorder(&mut expected);
+
+    let mut pos = 0;
+    let mut idx = 0;
+    let mut segment = Vec::new();
+
+    while pos < expected.len() {
+        let rest = &input[idx..];
+        let len = segment_len(rest);
+
+        if len == 0 {
+            return Err(Error::expect(expected[pos].0).but_found_end());
+        }
+
+        segment.clear();
+
+        for ch in rest[..len].chars() {
+            decompose(ch, |decomposed| segment.push((ch, decomposed)));
+        }
+
+        reorder(&mut segment);
+
+        for (character, decomposed) in segment.iter().copied() {
+            match expected.get(pos) {
+                Some((_, ch)) if eq(*ch, decomposed) => pos += 1,
+                Some((ch, _)) => return Err(Error::expect(*ch).but_found(character)),
+                None => return Err(Error::expect(literal).but_found(character)),
+            }
+        }
+
+        idx += len;
+    }
+
+    Ok(idx)
+}
+
+fn segment_len(input: &str) -> usize {
+    let mut iter = input.char_indices();
+
+    match iter.next() {
+        Some(_) => iter
+            .find(|(_, ch)| is_boundary(*ch))
+            .map_or(input.len(), |(idx, _)| idx),
+        None => 0,
+    }
+}
+
+fn is_boundary(ch: char) -> bool {
+    let code = ch as u32;
+    let mut first = None;
+
+    decompose(ch, |decomposed| {
+        first.get_or_insert(decomposed);
+    });
+
+    first.map_or(0, combining_class) == 0
+        && !(V_BASE..V_BASE + V_COUNT).contains(&code)
+        && !(T_BASE + 1..T_BASE + T_COUNT).contains(&code)
+        && COMPOSING_STARTERS.binary_search(&ch).is_err()
+}
+
+fn combining_class(ch: char) -> u8 {
+    match COMBINING_CLASS.binary_search_by(|(start, end, _)| {
+        if *end < ch {
+            Ordering::Less
+        } else if *start > ch {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }) {
+        Ok(idx) => COMBINING_CLASS[idx].2,
+        Err(_) => 0,
+    }
+}
+
+fn decompose(ch: char, mut push: impl FnMut(char)) {
+    let code = ch as u32;
+
+    if (S_BASE..S_BASE + S_COUNT).contains(&code) {
+        let index = code - S_BASE;
+        let jamo = [
+            L_BASE + index / N_COUNT,
+            V_BASE + (index % N_COUNT) / T_COUNT,
+            T_BASE + index % T_COUNT,
+        ];
+
+        for code in jamo.iter().copied().filter(|code| *code != T_BASE) {
+            push(char::from_u32(code).unwrap_or(ch));
+        }
+
+        return;
+    }
+
+    match DECOMPOSITION.binary_search_by_key(&ch, |(from, _)| *from) {
+        Ok(idx) => DECOMPOSITION[idx].1.chars().for_each(push),
+        Err(_) => push(ch),
+    }
+}
+
+fn compose(a: char, b: char) -> Option<char> {
+    let (a, b) = (a as u32, b as u32);
+
+    if (L_BASE..L_BASE + L_COUNT).contains(&a) && (V_BASE..V_BASE + V_COUNT).contains(&b) {
+        let index = (a - L_BASE) * N_COUNT + (b - V_BASE) * T_COUNT;
+
+        return char::from_u32(S_BASE + index);
+    }
+
+    if (S_BASE..S_BASE + S_COUNT).contains(&a)
+        && (a - S_BASE).is_multiple_of(T_COUNT)
+        && (T_BASE + 1..T_BASE + T_COUNT).contains(&b)
+    {
+        return char::from_u32(a + b - T_BASE);
+    }
+
+    let key = (char::from_u32(a)?, char::from_u32(b)?);
+
+    COMPOSITION
+        .binary_search_by_key(&key, |(pair, _)| *pair)
+        .ok()
+        .map(|idx| COMPOSITION[idx].1)
+}
+
+fn reorder(chars: &mut [(char, char)]) {
+    let mut start = 0;
+
+    while start < chars.len() {
+        let len = chars[start..]
+            .iter()
+            .take_while(|(_, ch)| combining_class(*ch) != 0)
+            .count();
+
+        if len > 1 {
+            chars[start..start + len].sort_by_key(|(_, ch)| combining_class(*ch));
+        }
+
+        start += len.max(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nfd() {
+        assert_eq!(nfd("\u{e9}"), "e\u{301}");
+        assert_eq!(nfd("\u{1e69}"), "s\u{323}\u{307}");
+        assert_eq!(nfd("s\u{307}\u{323}"), "s\u{323}\u{307}");
+        assert_eq!(nfd("\u{ac01}"), "\u{1100}\u{1161}\u{11a8}");
+        assert_eq!(nfd("\u{212b}"), "A\u{30a}");
+        assert_eq!(nfd("abc"), "abc");
+    }
+
+    #[test]
+    fn test_nfc() {
+        assert_eq!(nfc("e\u{301}"), "\u{e9}");
+        assert_eq!(nfc("s\u{307}\u{323}"), "\u{1e69}");
+        assert_eq!(nfc("\u{1100}\u{1161}\u{11a8}"), "\u{ac01}");
+        assert_eq!(nfc("\u{212b}"), "\u{c5}");
+        assert_eq!(nfc("\u{344}"), "\u{308}\u{301}");
+        assert_eq!(nfc("e\u{334}\u{301}"), "\u{e9}\u{334}");
+        assert_eq!(nfc("a\u{301}\u{301}"), "\u{e1}\u{301}");
+        assert!(is_nfc("caf\u{e9}"));
+        assert!(!is_nfc("cafe\u{301}"));
+    }
+
+    #[test]
+    fn test_match_prefix() {
+        let eq = |a: char, b: char| a == b;
+
+        assert_eq!(match_prefix("caf\u{e9}", "cafe\u{301}!", eq), Ok(6));
+        assert_eq!(match_prefix("cafe\u{301}", "caf\u{e9}!", eq), Ok(5));
+        assert_eq!(match_prefix("\u{ac00}", "\u{1100}\u{1161}", eq), Ok(6));
+        assert_eq!(match_prefix("", "abc", eq), Ok(0));
+        assert_eq!(
+            match_prefix("cafe", "caf\u{e9}", eq),
+            Err(Error::expect("cafe").but_found('\u{e9}'))
+        );
+        assert_eq!(
+            match_prefix("\u{ac00}", "\u{ac01}", eq),
+            Err(Error::expect("\u{ac00}").but_found('\u{ac01}'))
+        );
+        assert_eq!(
+            match_prefix("caf\u{e9}", "cafe", eq),
+            Err(Error::expect('\u{e9}').but_found_end())
+        );
+        assert_eq!(
+            match_prefix("caf\u{e9}", "cafx", eq),
+            Err(Error::expect('\u{e9}').but_found('x'))
+        );
+    }
+}
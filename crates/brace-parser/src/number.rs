@@ -0,0 +1,996 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::error::Error;
+use crate::parser::{Output, Parser};
+use crate::sequence::Sequence;
+
+pub trait Integer: Copy + fmt::Display {
+    const NAME: &'static str;
+    const ZERO: Self;
+    const MIN: Self;
+    const MAX: Self;
+    const SIGNED: bool;
+    const BITS: u32;
+
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! integer_impl {
+    ($($ty:ident $signed:literal),+) => {
+        $(
+            impl Integer for $ty {
+                const NAME: &'static str = stringify!($ty);
+                const ZERO: Self = 0;
+                const MIN: Self = $ty::MIN;
+                const MAX: Self = $ty::MAX;
+                const SIGNED: bool = $signed;
+                const BITS: u32 = $ty::BITS;
+
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let digit = digit as $ty;
+
+                    self.checked_mul(10).and_then(|out| match negative {
+                        true => out.checked_sub(digit),
+                        false => out.checked_add(digit),
+                    })
+                }
+            }
+        )+
+    };
+}
+
+integer_impl!(
+    u8 false, u16 false, u32 false, u64 false, u128 false, usize false,
+    i8 true, i16 true, i32 true, i64 true, i128 true, isize true
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Constraint {
+    Range {
+        name: &'static str,
+        bits: u32,
+        signed: bool,
+    },
+    FractionDigits(u32),
+    Grouping(usize),
+    NonZeroDenominator,
+    ProperFraction,
+}
+
+impl Constraint {
+    pub fn range<T: Integer>() -> Self {
+        Self::Range {
+            name: T::NAME,
+            bits: T::BITS,
+            signed: T::SIGNED,
+        }
+    }
+
+    pub fn min(&self) -> i128 {
+        match self {
+            Self::Range {
+                bits, signed: true, ..
+            } => i128::MIN >> (128 - bits),
+            _ => 0,
+        }
+    }
+
+    pub fn max(&self) -> u128 {
+        match self {
+            Self::Range { bits, signed, .. } => u128::MAX >> (128 - bits + u32::from(*signed)),
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Range { name, .. } => {
+                write!(f, "{} in range {}..={}", name, self.min(), self.max())
+            }
+            Self::FractionDigits(digits) => write!(f, "at most {} fractional digits", digits),
+            Self::Grouping(size) => write!(f, "digit groups of {}", size),
+            Self::NonZeroDenominator => write!(f, "non-zero denominator"),
+            Self::ProperFraction => write!(f, "proper fraction"),
+        }
+    }
+}
+
+pub fn unsigned(input: &str) -> Output<u64> {
+    int(input)
+}
+
+pub fn integer(input: &str) -> Output<i64> {
+    int(input)
+}
+
+pub fn int<T: Integer>(input: &str) -> Output<T> {
+    let (negative, rest) = match T::SIGNED {
+        true => sign(input),
+        false => (false, input),
+    };
+    let len = digits(rest)?;
+    let mut out = T::ZERO;
+
+    for byte in rest[..len].bytes() {
+        out = match out.push_digit(byte - b'0', negative) {
+            Some(out) => out,
+            None => return Err(overflow::<T>(&input[..input.len() - rest.len() + len])),
+        };
+    }
+
+    Ok((out, &rest[len..]))
+}
+
+pub fn decimal_fixed<'a>(scale: u32) -> impl Parser<'a, i64> {
+    move |input: &'a str| {
+        let ((mantissa, exp), rem) = decimal_parts(input)?;
+        let literal = &input[..input.len() - rem.len()];
+
+        if exp > scale {
+            return match 10i64.checked_pow(exp - scale) {
+                Some(excess) if mantissa % excess == 0 => Ok((mantissa / excess, rem)),
+                _ if mantissa == 0 => Ok((0, rem)),
+                _ => Err(violation(Constraint::FractionDigits(scale), literal)),
+            };
+        }
+
+        10i64
+            .checked_pow(scale - exp)
+            .and_then(|factor| mantissa.checked_mul(factor))
+            .map(|out| (out, rem))
+            .ok_or_else(|| overflow::<i64>(literal))
+    }
+}
+
+pub fn decimal_parts(input: &str) -> Output<(i64, u32)> {
+    let (negative, rest) = sign(input);
+    let int = digits(rest)?;
+    let frac = match rest[int..].strip_prefix('.') {
+        Some(frac) => frac.bytes().take_while(u8::is_ascii_digit).count(),
+        None => 0,
+    };
+    let len = match frac {
+        0 => int,
+        _ => int + 1 + frac,
+    };
+    let literal = &input[..input.len() - rest.len() + len];
+    let mut out = 0i64;
+
+    for byte in rest[..len].bytes().filter(u8::is_ascii_digit) {
+        out = out
+            .push_digit(byte - b'0', negative)
+            .ok_or_else(|| overflow::<i64>(literal))?;
+    }
+
+    Ok(((out, frac as u32), &rest[len..]))
+}
+
+pub fn float(input: &str) -> Output<f64> {
+    let (negative, rest) = sign(input);
+    let int = rest.bytes().take_while(u8::is_ascii_digit).count();
+    let mut len = int;
+    let mut frac = 0;
+
+    if rest[len..].starts_with('.') {
+        frac = rest[len + 1..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+
+        if int > 0 || frac > 0 {
+            len += 1 + frac;
+        }
+    }
+
+    if int == 0 && frac == 0 {
+        return Err(match rest.chars().next() {
+            Some(ch) => Error::expect(Sequence::Decimal).but_found(ch),
+            None => Error::expect(Sequence::Decimal).but_found_end(),
+        });
+    }
+
+    let mut exp = 0;
+
+    if let Some(b'e') | Some(b'E') = rest.as_bytes().get(len) {
+        let (exp_negative, exp_rest) = sign(&rest[len + 1..]);
+        let exp_len = exp_rest.bytes().take_while(u8::is_ascii_digit).count();
+
+        if exp_len > 0 {
+            exp = exp_rest[..exp_len].bytes().fold(0i64, |exp, byte| {
+                (exp * 10 + i64::from(byte - b'0')).min(i64::from(i32::MAX))
+            });
+
+            if exp_negative {
+                exp = -exp;
+            }
+
+            len = rest.len() - exp_rest.len() + exp_len;
+        }
+    }
+
+    let text = &rest[..len];
+    let value = convert(text, int, frac, exp).ok_or_else(Error::invalid)?;
+
+    match negative {
+        true => Ok((-value, &rest[len..])),
+        false => Ok((value, &rest[len..])),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Grouping {
+    separator: char,
+    decimal_point: char,
+    size: usize,
+}
+
+impl Grouping {
+    pub fn new(separator: char) -> Self {
+        Self {
+            separator,
+            decimal_point: '.',
+            size: 3,
+        }
+    }
+
+    pub fn decimal_point(mut self, decimal_point: char) -> Self {
+        self.decimal_point = decimal_point;
+        self
+    }
+
+    pub fn group_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn get_separator(&self) -> char {
+        self.separator
+    }
+
+    pub fn get_decimal_point(&self) -> char {
+        self.decimal_point
+    }
+
+    pub fn get_group_size(&self) -> usize {
+        self.size
+    }
+
+    fn digits<'a>(&self, input: &'a str) -> Result<(String, &'a str), Error> {
+        let lead = digits(input)?;
+        let mut out = input[..lead].to_owned();
+        let mut len = lead;
+
+        while let Some(rest) = input[len..].strip_prefix(self.separator) {
+            let group = rest.bytes().take_while(u8::is_ascii_digit).count();
+
+            if group == 0 {
+                break;
+            }
+
+            if group != self.size || lead > self.size {
+                let literal = &input[..input.len() - rest.len() + group];
+
+                return Err(violation(Constraint::Grouping(self.size), literal));
+            }
+
+            out.push_str(&rest[..group]);
+            len = input.len() - rest.len() + group;
+        }
+
+        Ok((out, &input[len..]))
+    }
+}
+
+impl Default for Grouping {
+    fn default() -> Self {
+        Self::new(',')
+    }
+}
+
+pub fn int_grouped<'a, T: Integer>(grouping: Grouping) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let (negative, rest) = match T::SIGNED {
+            true => sign(input),
+            false => (false, input),
+        };
+        let (digits, rem) = grouping.digits(rest)?;
+        let mut out = T::ZERO;
+
+        for byte in digits.bytes() {
+            out = match out.push_digit(byte - b'0', negative) {
+                Some(out) => out,
+                None => return Err(overflow::<T>(&input[..input.len() - rem.len()])),
+            };
+        }
+
+        Ok((out, rem))
+    }
+}
+
+pub fn float_grouped<'a>(grouping: Grouping) -> impl Parser<'a, f64> {
+    move |input: &'a str| {
+        let (negative, rest) = sign(input);
+        let (mut text, mut rem) = grouping.digits(rest)?;
+
+        if let Some(frac) = rem.strip_prefix(grouping.decimal_point) {
+            let len = frac.bytes().take_while(u8::is_ascii_digit).count();
+
+            if len > 0 {
+                text.push('.');
+                text.push_str(&frac[..len]);
+                rem = &frac[len..];
+            }
+        }
+
+        let (value, _) = float(&text)?;
+
+        match negative {
+            true => Ok((-value, rem)),
+            false => Ok((value, rem)),
+        }
+    }
+}
+
+pub const SI: &[(&str, i32)] = &[
+    ("E", 18),
+    ("P", 15),
+    ("T", 12),
+    ("G", 9),
+    ("M", 6),
+    ("k", 3),
+    ("m", -3),
+    ("u", -6),
+    ("\u{b5}", -6),
+    ("n", -9),
+    ("p", -12),
+    ("f", -15),
+    ("a", -18),
+];
+
+pub fn si(input: &str) -> Output<f64> {
+    si_with(SI).parse(input)
+}
+
+pub fn si_with<'a, 't>(table: &'t [(&'t str, i32)]) -> impl Parser<'a, f64> + 't {
+    move |input: &'a str| {
+        let (value, rem) = float(input)?;
+        let literal = &input[..input.len() - rem.len()];
+
+        if literal.contains(&['e', 'E'][..]) {
+            return Ok((value, rem));
+        }
+
+        match suffix(table, rem) {
+            Some((exp, len)) => {
+                let (value, _) = float(&format!("{}e{}", literal, exp))?;
+
+                Ok((value, &rem[len..]))
+            }
+            None => Ok((value, rem)),
+        }
+    }
+}
+
+pub fn si_fixed<'a, 't>(table: &'t [(&'t str, i32)], scale: u32) -> impl Parser<'a, i64> + 't {
+    move |input: &'a str| {
+        let ((mantissa, frac), rem) = decimal_parts(input)?;
+        let (exp, rem) = match suffix(table, rem) {
+            Some((exp, len)) => (exp, &rem[len..]),
+            None => (0, rem),
+        };
+        let literal = &input[..input.len() - rem.len()];
+        let shift = i64::from(scale) + i64::from(exp) - i64::from(frac);
+
+        if shift < 0 {
+            let excess = u32::try_from(-shift)
+                .ok()
+                .and_then(|shift| 10i64.checked_pow(shift));
+
+            return match excess {
+                Some(excess) if mantissa % excess == 0 => Ok((mantissa / excess, rem)),
+                _ if mantissa == 0 => Ok((0, rem)),
+                _ => Err(violation(Constraint::FractionDigits(scale), literal)),
+            };
+        }
+
+        u32::try_from(shift)
+            .ok()
+            .and_then(|shift| 10i64.checked_pow(shift))
+            .and_then(|factor| mantissa.checked_mul(factor))
+            .map(|out| (out, rem))
+            .ok_or_else(|| overflow::<i64>(literal))
+    }
+}
+
+fn suffix(table: &[(&str, i32)], input: &str) -> Option<(i32, usize)> {
+    table
+        .iter()
+        .filter(|(suffix, _)| !suffix.is_empty() && input.starts_with(suffix))
+        .max_by_key(|(suffix, _)| suffix.len())
+        .map(|(suffix, exp)| (*exp, suffix.len()))
+}
+
+pub fn fraction(input: &str) -> Output<(i64, u64)> {
+    let (negative, rest) = sign(input);
+    let (whole, rem) = int::<u64>(rest)?;
+    let (whole, numerator, denominator, rem) = match rem.strip_prefix('/') {
+        Some(after) => {
+            let (denominator, rem) = int::<u64>(after)?;
+
+            (0, whole, denominator, rem)
+        }
+        None => match mixed(rem) {
+            Some((numerator, denominator, rem)) => (whole, numerator, denominator, rem),
+            None => (0, whole, 1, rem),
+        },
+    };
+    let literal = &input[..input.len() - rem.len()];
+
+    if denominator == 0 {
+        return Err(violation(Constraint::NonZeroDenominator, literal));
+    }
+
+    if whole > 0 && numerator >= denominator {
+        return Err(violation(Constraint::ProperFraction, literal));
+    }
+
+    let numerator = whole
+        .checked_mul(denominator)
+        .and_then(|whole| whole.checked_add(numerator))
+        .ok_or_else(|| overflow::<i64>(literal))?;
+    let divisor = gcd(numerator, denominator);
+    let numerator = i64::try_from(numerator / divisor).map_err(|_| overflow::<i64>(literal))?;
+
+    match negative {
+        true => Ok(((-numerator, denominator / divisor), rem)),
+        false => Ok(((numerator, denominator / divisor), rem)),
+    }
+}
+
+fn mixed(input: &str) -> Option<(u64, u64, &str)> {
+    let rest = input.trim_start_matches(' ');
+
+    if rest.len() == input.len() {
+        return None;
+    }
+
+    let (numerator, rest) = int::<u64>(rest).ok()?;
+    let (denominator, rest) = int::<u64>(rest.strip_prefix('/')?).ok()?;
+
+    Some((numerator, denominator, rest))
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let rem = a % b;
+
+        a = b;
+        b = rem;
+    }
+
+    a
+}
+
+pub const BASE36: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+pub const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Radix {
+    alphabet: Vec<char>,
+}
+
+pub fn radix(base: u32, alphabet: &str) -> Result<Radix, Error> {
+    let alphabet = alphabet.chars().collect::<Vec<_>>();
+
+    if base < 2 {
+        return Err(Error::invalid().with_context(format!("radix {} is below 2", base)));
+    }
+
+    if alphabet.len() != base as usize {
+        return Err(Error::invalid().with_context(format!(
+            "radix {} needs {} digits but the alphabet has {}",
+            base,
+            base,
+            alphabet.len()
+        )));
+    }
+
+    if let Some(ch) = alphabet
+        .iter()
+        .enumerate()
+        .find_map(|(idx, ch)| alphabet[..idx].contains(ch).then_some(ch))
+    {
+        return Err(
+            Error::invalid().with_context(format!("radix alphabet repeats the digit {:?}", ch))
+        );
+    }
+
+    Ok(Radix { alphabet })
+}
+
+impl Radix {
+    pub fn get_base(&self) -> u32 {
+        self.alphabet.len() as u32
+    }
+
+    pub fn value<'a>(self) -> impl Parser<'a, u128> {
+        move |input: &'a str| {
+            let (digits, rem) = self.parse(input)?;
+            let mut out = 0u128;
+
+            for digit in digits {
+                out = match out
+                    .checked_mul(self.get_base() as u128)
+                    .and_then(|out| out.checked_add(digit as u128))
+                {
+                    Some(out) => out,
+                    None => return Err(overflow::<u128>(&input[..input.len() - rem.len()])),
+                };
+            }
+
+            Ok((out, rem))
+        }
+    }
+
+    fn digit(&self, ch: char) -> Option<u32> {
+        self.alphabet
+            .iter()
+            .position(|digit| *digit == ch)
+            .map(|idx| idx as u32)
+    }
+}
+
+impl<'a> Parser<'a, Vec<u32>> for Radix {
+    fn parse(&self, input: &'a str) -> Output<'a, Vec<u32>> {
+        let mut out = Vec::new();
+        let mut len = 0;
+
+        for ch in input.chars() {
+            match self.digit(ch) {
+                Some(digit) => out.push(digit),
+                None => break,
+            }
+
+            len += ch.len_utf8();
+        }
+
+        if out.is_empty() {
+            let expect = Sequence::custom(format!("base {} digit", self.get_base()));
+
+            return Err(match input.chars().next() {
+                Some(ch) => Error::expect(expect).but_found(ch),
+                None => Error::expect(expect).but_found_end(),
+            });
+        }
+
+        Ok((out, &input[len..]))
+    }
+}
+
+fn sign(input: &str) -> (bool, &str) {
+    match input.as_bytes().first() {
+        Some(b'-') => (true, &input[1..]),
+        Some(b'+') => (false, &input[1..]),
+        _ => (false, input),
+    }
+}
+
+fn digits(input: &str) -> Result<usize, Error> {
+    match input.bytes().take_while(u8::is_ascii_digit).count() {
+        0 => match input.chars().next() {
+            Some(ch) => Err(Error::expect(Sequence::Decimal).but_found(ch)),
+            None => Err(Error::expect(Sequence::Decimal).but_found_end()),
+        },
+        len => Ok(len),
+    }
+}
+
+fn overflow<T: Integer>(literal: &str) -> Error {
+    violation(Constraint::range::<T>(), literal)
+}
+
+fn violation(constraint: Constraint, literal: &str) -> Error {
+    Error::expect(constraint)
+        .but_found(Sequence::custom(literal))
+        .into_fail()
+}
+
+#[cfg(not(feature = "fast-number"))]
+fn convert(text: &str, _: usize, _: usize, _: i64) -> Option<f64> {
+    text.parse().ok()
+}
+
+#[cfg(feature = "fast-number")]
+fn convert(text: &str, int: usize, frac: usize, exp: i64) -> Option<f64> {
+    const POW10: [f64; 23] = [
+        1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+        1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+    ];
+    const MAX_MANTISSA: u64 = 1 << 53;
+
+    if int + frac <= 19 {
+        let mantissa = text
+            .bytes()
+            .take_while(|byte| *byte != b'e' && *byte != b'E')
+            .filter(u8::is_ascii_digit)
+            .fold(0u64, |out, byte| out * 10 + u64::from(byte - b'0'));
+        let exp = exp - frac as i64;
+
+        if mantissa <= MAX_MANTISSA && (-22..=22).contains(&exp) {
+            let value = mantissa as f64;
+
+            return match exp < 0 {
+                true => Some(value / POW10[-exp as usize]),
+                false => Some(value * POW10[exp as usize]),
+            };
+        }
+    }
+
+    text.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_unsigned() {
+        assert_eq!(parse("0", unsigned), Ok((0, "")));
+        assert_eq!(parse("1234x", unsigned), Ok((1234, "x")));
+        assert_eq!(parse("18446744073709551615", unsigned), Ok((u64::MAX, "")));
+        assert_eq!(
+            parse("18446744073709551616", unsigned),
+            Err(overflow::<u64>("18446744073709551616"))
+        );
+        assert_eq!(
+            parse("-1", unsigned),
+            Err(Error::expect(Sequence::Decimal).but_found('-'))
+        );
+        assert_eq!(
+            parse("", unsigned),
+            Err(Error::expect(Sequence::Decimal).but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(parse("42", integer), Ok((42, "")));
+        assert_eq!(parse("-42,", integer), Ok((-42, ",")));
+        assert_eq!(parse("+7", integer), Ok((7, "")));
+        assert_eq!(parse("-9223372036854775808", integer), Ok((i64::MIN, "")));
+        assert_eq!(
+            parse("9223372036854775808", integer),
+            Err(overflow::<i64>("9223372036854775808"))
+        );
+        assert_eq!(
+            parse("-x", integer),
+            Err(Error::expect(Sequence::Decimal).but_found('x'))
+        );
+    }
+
+    #[test]
+    fn test_int() {
+        assert_eq!(parse("255", int::<u8>), Ok((255, "")));
+        assert_eq!(parse("-128]", int::<i8>), Ok((-128, "]")));
+        assert_eq!(parse("+65535", int::<i32>), Ok((65535, "")));
+        assert_eq!(parse("007", int::<u16>), Ok((7, "")));
+        assert_eq!(
+            parse("340282366920938463463374607431768211455", int::<u128>),
+            Ok((u128::MAX, ""))
+        );
+        assert_eq!(
+            parse("256", int::<u8>),
+            Err(Error::expect(Constraint::Range {
+                name: "u8",
+                bits: 8,
+                signed: false,
+            })
+            .but_found(Sequence::custom("256"))
+            .into_fail())
+        );
+        assert_eq!(
+            parse("256", int::<u8>).unwrap_err().to_string(),
+            "Error:\nExpected u8 in range 0..=255\nFound sequence: \"256\""
+        );
+        assert_eq!(parse("-129", int::<i8>), Err(overflow::<i8>("-129")));
+        assert_eq!(
+            parse("+1000000", int::<i16>),
+            Err(overflow::<i16>("+1000000"))
+        );
+        assert_eq!(
+            parse("-1", int::<u32>),
+            Err(Error::expect(Sequence::Decimal).but_found('-'))
+        );
+        assert_eq!(Constraint::range::<i8>().min(), -128);
+        assert_eq!(Constraint::range::<i8>().max(), 127);
+        assert_eq!(Constraint::range::<u16>().min(), 0);
+        assert_eq!(Constraint::range::<u16>().max(), 65535);
+        assert_eq!(Constraint::range::<i128>().min(), i128::MIN);
+        assert_eq!(Constraint::range::<u128>().max(), u128::MAX);
+    }
+
+    #[test]
+    fn test_decimal_fixed() {
+        assert_eq!(parse("19.99", decimal_fixed(2)), Ok((1999, "")));
+        assert_eq!(parse("19.9 EUR", decimal_fixed(2)), Ok((1990, " EUR")));
+        assert_eq!(parse("-19", decimal_fixed(2)), Ok((-1900, "")));
+        assert_eq!(parse("0.050", decimal_fixed(2)), Ok((5, "")));
+        assert_eq!(parse("7.", decimal_fixed(3)), Ok((7000, ".")));
+        assert_eq!(
+            parse("12.5", decimal_fixed(0)),
+            Err(violation(Constraint::FractionDigits(0), "12.5"))
+        );
+        assert_eq!(
+            parse("0.125", decimal_fixed(2)),
+            Err(violation(Constraint::FractionDigits(2), "0.125"))
+        );
+        assert_eq!(
+            parse("92233720368547758.08", decimal_fixed(3)),
+            Err(overflow::<i64>("92233720368547758.08"))
+        );
+        assert_eq!(
+            parse("0.0000000000000000000", decimal_fixed(0)),
+            Ok((0, ""))
+        );
+        assert_eq!(
+            parse("0.0000000000000000001", decimal_fixed(0)),
+            Err(violation(
+                Constraint::FractionDigits(0),
+                "0.0000000000000000001"
+            ))
+        );
+        assert_eq!(
+            parse(".5", decimal_fixed(2)),
+            Err(Error::expect(Sequence::Decimal).but_found('.'))
+        );
+    }
+
+    #[test]
+    fn test_decimal_parts() {
+        assert_eq!(parse("19.99", decimal_parts), Ok(((1999, 2), "")));
+        assert_eq!(parse("-0.0010", decimal_parts), Ok(((-10, 4), "")));
+        assert_eq!(parse("42%", decimal_parts), Ok(((42, 0), "%")));
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(parse("1.5", float), Ok((1.5, "")));
+        assert_eq!(parse("-0.25rest", float), Ok((-0.25, "rest")));
+        assert_eq!(parse("3", float), Ok((3.0, "")));
+        assert_eq!(parse(".5", float), Ok((0.5, "")));
+        assert_eq!(parse("5.", float), Ok((5.0, "")));
+        assert_eq!(parse("1e3", float), Ok((1000.0, "")));
+        assert_eq!(parse("2.5E-3", float), Ok((0.0025, "")));
+        assert_eq!(parse("1e", float), Ok((1.0, "e")));
+        assert_eq!(parse("1e+", float), Ok((1.0, "e+")));
+        assert_eq!(parse("1.x", float), Ok((1.0, "x")));
+        assert_eq!(
+            parse(".", float),
+            Err(Error::expect(Sequence::Decimal).but_found('.'))
+        );
+        assert_eq!(
+            parse("-", float),
+            Err(Error::expect(Sequence::Decimal).but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_float_precision() {
+        let cases = [
+            "0.1",
+            "0.3",
+            "123456789.123456789",
+            "9007199254740993",
+            "1.7976931348623157e308",
+            "2.2250738585072014e-308",
+            "4.9e-324",
+            "1e400",
+            "12345678901234567890123e-5",
+            "0.000000000000000000000000000001",
+        ];
+
+        for case in cases.iter() {
+            assert_eq!(parse(case, float), Ok((str::parse(case).unwrap(), "")));
+        }
+    }
+
+    #[test]
+    fn test_int_grouped() {
+        let comma = || int_grouped::<i64>(Grouping::default());
+
+        assert_eq!(parse("1,000,000", comma()), Ok((1_000_000, "")));
+        assert_eq!(parse("-12,345 x", comma()), Ok((-12_345, " x")));
+        assert_eq!(parse("1234567", comma()), Ok((1_234_567, "")));
+        assert_eq!(parse("1,000, 2", comma()), Ok((1000, ", 2")));
+        assert_eq!(parse("1,000,", comma()), Ok((1000, ",")));
+        assert_eq!(
+            parse("1,00", comma()),
+            Err(violation(Constraint::Grouping(3), "1,00"))
+        );
+        assert_eq!(
+            parse("1234,567", comma()),
+            Err(violation(Constraint::Grouping(3), "1234,567"))
+        );
+        assert_eq!(
+            parse("1,000,0000", comma()),
+            Err(violation(Constraint::Grouping(3), "1,000,0000"))
+        );
+        assert_eq!(
+            parse("1 000 000", int_grouped::<u32>(Grouping::new(' '))),
+            Ok((1_000_000, ""))
+        );
+        assert_eq!(
+            parse("1.000.000", int_grouped::<u8>(Grouping::new('.'))),
+            Err(overflow::<u8>("1.000.000"))
+        );
+        assert_eq!(
+            parse(",000", comma()),
+            Err(Error::expect(Sequence::Decimal).but_found(','))
+        );
+    }
+
+    #[test]
+    fn test_float_grouped() {
+        let german = Grouping::new('.').decimal_point(',');
+
+        assert_eq!(
+            parse("1.234.567,89", float_grouped(german)),
+            Ok((1_234_567.89, ""))
+        );
+        assert_eq!(parse("-0,5", float_grouped(german)), Ok((-0.5, "")));
+        assert_eq!(parse("12,", float_grouped(german)), Ok((12.0, ",")));
+        assert_eq!(
+            parse("1,234.5;", float_grouped(Grouping::default())),
+            Ok((1234.5, ";"))
+        );
+        assert_eq!(
+            parse("1'00'000", float_grouped(Grouping::new('\'').group_size(2))),
+            Err(violation(Constraint::Grouping(2), "1'00'000"))
+        );
+        assert_eq!(
+            parse(
+                "12'34'56",
+                int_grouped::<u32>(Grouping::new('\'').group_size(2))
+            ),
+            Ok((123_456, ""))
+        );
+    }
+
+    #[test]
+    fn test_radix() {
+        assert_eq!(
+            parse("zz!", radix(36, BASE36).unwrap()),
+            Ok((vec![35, 35], "!"))
+        );
+        assert_eq!(
+            parse("zz!", radix(36, BASE36).unwrap().value()),
+            Ok((1295, "!"))
+        );
+        assert_eq!(
+            parse("ff", radix(16, &BASE36[..16]).unwrap().value()),
+            Ok((255, ""))
+        );
+        assert_eq!(
+            parse("fg", radix(16, &BASE36[..16]).unwrap().value()),
+            Ok((15, "g"))
+        );
+        assert_eq!(parse("101", radix(2, "01").unwrap().value()), Ok((5, "")));
+        assert_eq!(
+            parse("3yQ", radix(58, BASE58).unwrap()),
+            Ok((vec![2, 56, 23], ""))
+        );
+        assert_eq!(
+            parse("211", radix(58, BASE58).unwrap().value()),
+            Ok((3364, ""))
+        );
+        assert_eq!(
+            parse("0OIl", radix(58, BASE58).unwrap()),
+            Err(Error::expect(Sequence::custom("base 58 digit")).but_found('0'))
+        );
+        assert_eq!(
+            parse("", radix(36, BASE36).unwrap()),
+            Err(Error::expect(Sequence::custom("base 36 digit")).but_found_end())
+        );
+        assert_eq!(
+            parse(&"z".repeat(25), radix(36, BASE36).unwrap().value()),
+            Err(overflow::<u128>(&"z".repeat(25)))
+        );
+        assert_eq!(
+            parse(&"f".repeat(32), radix(16, &BASE36[..16]).unwrap().value()),
+            Ok((u128::MAX, ""))
+        );
+    }
+
+    #[test]
+    fn test_radix_alphabet() {
+        assert_eq!(
+            radix(3, "aba"),
+            Err(Error::invalid().with_context("radix alphabet repeats the digit 'a'"))
+        );
+        assert_eq!(
+            radix(16, BASE36),
+            Err(Error::invalid().with_context("radix 16 needs 16 digits but the alphabet has 36"))
+        );
+        assert_eq!(
+            radix(4, "01"),
+            Err(Error::invalid().with_context("radix 4 needs 4 digits but the alphabet has 2"))
+        );
+        assert_eq!(
+            radix(1, "0"),
+            Err(Error::invalid().with_context("radix 1 is below 2"))
+        );
+        assert_eq!(radix(3, "012").map(|radix| radix.get_base()), Ok(3));
+    }
+
+    #[test]
+    fn test_fraction() {
+        assert_eq!(parse("3/4", fraction), Ok(((3, 4), "")));
+        assert_eq!(parse("6/8 cup", fraction), Ok(((3, 4), " cup")));
+        assert_eq!(parse("1 1/2 cups", fraction), Ok(((3, 2), " cups")));
+        assert_eq!(parse("-2 3/4", fraction), Ok(((-11, 4), "")));
+        assert_eq!(parse("2 cups", fraction), Ok(((2, 1), " cups")));
+        assert_eq!(parse("4/2", fraction), Ok(((2, 1), "")));
+        assert_eq!(parse("0/5", fraction), Ok(((0, 1), "")));
+        assert_eq!(parse("1 /2", fraction), Ok(((1, 1), " /2")));
+        assert_eq!(
+            parse("1/0", fraction),
+            Err(violation(Constraint::NonZeroDenominator, "1/0"))
+        );
+        assert_eq!(
+            parse("1 3/2", fraction),
+            Err(violation(Constraint::ProperFraction, "1 3/2"))
+        );
+        assert_eq!(
+            parse("3/x", fraction),
+            Err(Error::expect(Sequence::Decimal).but_found('x'))
+        );
+        assert_eq!(
+            parse("x", fraction),
+            Err(Error::expect(Sequence::Decimal).but_found('x'))
+        );
+        assert_eq!(
+            parse("18446744073709551615/1", fraction),
+            Err(overflow::<i64>("18446744073709551615/1"))
+        );
+    }
+
+    #[test]
+    fn test_si() {
+        assert_eq!(parse("1.5k", si), Ok((1500.0, "")));
+        assert_eq!(parse("2M", si), Ok((2_000_000.0, "")));
+        assert_eq!(parse("4.7uF", si), Ok((4.7e-6, "F")));
+        assert_eq!(parse("4.7\u{b5}F", si), Ok((4.7e-6, "F")));
+        assert_eq!(parse("3n", si), Ok((3e-9, "")));
+        assert_eq!(parse("-10m", si), Ok((-0.01, "")));
+        assert_eq!(parse("100 ohm", si), Ok((100.0, " ohm")));
+        assert_eq!(parse("1e3k", si), Ok((1000.0, "k")));
+        assert_eq!(
+            parse("k", si),
+            Err(Error::expect(Sequence::Decimal).but_found('k'))
+        );
+
+        let spice = [("meg", 6), ("m", -3), ("k", 3)];
+
+        assert_eq!(parse("2meg", si_with(&spice)), Ok((2e6, "")));
+        assert_eq!(parse("2ms", si_with(&spice)), Ok((0.002, "s")));
+        assert_eq!(parse("2M", si_with(&spice)), Ok((2.0, "M")));
+    }
+
+    #[test]
+    fn test_si_fixed() {
+        assert_eq!(parse("1.5k", si_fixed(SI, 0)), Ok((1500, "")));
+        assert_eq!(parse("4.7u", si_fixed(SI, 9)), Ok((4700, "")));
+        assert_eq!(parse("-2.5m", si_fixed(SI, 4)), Ok((-25, "")));
+        assert_eq!(parse("10.00n", si_fixed(SI, 8)), Ok((1, "")));
+        assert_eq!(parse("0.0n", si_fixed(SI, 0)), Ok((0, "")));
+        assert_eq!(
+            parse("1.5n", si_fixed(SI, 6)),
+            Err(violation(Constraint::FractionDigits(6), "1.5n"))
+        );
+        assert_eq!(parse("10E", si_fixed(SI, 3)), Err(overflow::<i64>("10E")));
+    }
+}
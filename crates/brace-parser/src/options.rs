@@ -0,0 +1,190 @@
+use crate::limit::ParseBudget;
+use crate::position::Columns;
+use crate::state;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NewlinePolicy {
+    Lf,
+    Any,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    max_depth: Option<usize>,
+    fuel: Option<ParseBudget>,
+    fold_case: bool,
+    newline: NewlinePolicy,
+    tab_width: usize,
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn current() -> Self {
+        state::options()
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn fuel(mut self, fuel: ParseBudget) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    pub fn fold_case(mut self, fold_case: bool) -> Self {
+        self.fold_case = fold_case;
+        self
+    }
+
+    pub fn newline(mut self, newline: NewlinePolicy) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    pub fn get_max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    pub fn get_fuel(&self) -> Option<ParseBudget> {
+        self.fuel
+    }
+
+    pub fn get_fold_case(&self) -> bool {
+        self.fold_case
+    }
+
+    pub fn get_newline(&self) -> NewlinePolicy {
+        self.newline
+    }
+
+    pub fn get_tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    pub fn columns(&self) -> Columns {
+        Columns::new()
+            .tab_width(self.tab_width)
+            .newline(self.newline)
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            fuel: None,
+            fold_case: false,
+            newline: NewlinePolicy::Lf,
+            tab_width: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::series::{delimited, list};
+    use crate::error::Error;
+    use crate::limit::Limit;
+    use crate::parser::{parse_with, Output, Parser};
+    use crate::position::{Columns, Position};
+    use crate::sequence::{alphabetic, newline};
+
+    crate::rule! {
+        fn nested(input) -> usize {
+            match '('.parse(input) {
+                Ok((_, rem)) => delimited((), nested, ')')
+                    .parse(rem)
+                    .map(|(depth, rem)| (depth + 1, rem)),
+                Err(_) => Ok((0, input)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let options = ParseOptions::new().fold_case(true);
+
+        assert_eq!(parse_with("SELECT", "select", options), Ok(("SELECT", "")));
+        assert_eq!(
+            parse_with("SELECT", "select", ParseOptions::new()),
+            Err(Error::expect('s').but_found('S'))
+        );
+        assert_eq!(ParseOptions::current(), ParseOptions::default());
+    }
+
+    #[test]
+    fn test_parse_with_limits() {
+        let options = ParseOptions::new().max_depth(20);
+
+        assert_eq!(parse_with("((()))", nested, options), Ok((3, "")));
+        assert_eq!(
+            parse_with(&"(".repeat(50), nested, options)
+                .unwrap_err()
+                .get_limit(),
+            Some(Limit::Depth(20))
+        );
+        assert_eq!(
+            parse_with("((()))", nested, ParseOptions::new().max_depth(4)),
+            Ok((3, ""))
+        );
+        assert_eq!(
+            parse_with("((()))", nested, ParseOptions::new().max_depth(3))
+                .unwrap_err()
+                .get_limit(),
+            Some(Limit::Depth(3))
+        );
+        assert_eq!(
+            crate::parser::parse(&("(".repeat(50) + &")".repeat(50)), nested),
+            Ok((50, ""))
+        );
+
+        let options = ParseOptions::new().fuel(ParseBudget::new(3));
+
+        assert_eq!(
+            parse_with("a b c d", list(alphabetic, ' '), options)
+                .unwrap_err()
+                .get_limit(),
+            Some(Limit::Budget(3))
+        );
+    }
+
+    #[test]
+    fn test_parse_with_newline_and_tabs() {
+        let any = ParseOptions::new().newline(NewlinePolicy::Any);
+
+        assert_eq!(parse_with("\r\nx", newline, any), Ok(("\r\n", "x")));
+        assert_eq!(parse_with("\rx", newline, any), Ok(("\r", "x")));
+        assert_eq!(
+            parse_with("\rx", newline, ParseOptions::new()),
+            Err(Error::expect('\n').but_found('\r'))
+        );
+
+        let tabs = ParseOptions::new().tab_width(4);
+        let column = |_: &str| -> Output<usize> {
+            let columns = ParseOptions::current().columns();
+
+            Ok((Position::new_with("\tx", 1, columns).column, ""))
+        };
+
+        assert_eq!(parse_with("", column, tabs), Ok((5, "")));
+        assert_eq!(crate::parser::parse("", column), Ok((2, "")));
+        assert_eq!(Position::new_with("\tx", 1, Columns::default()).column, 2);
+
+        let columns = any.columns();
+
+        assert_eq!(Position::locate_with("a\rb\r\nc", 2, columns), (2, 1));
+        assert_eq!(Position::locate_with("a\rb\r\nc", 5, columns), (3, 1));
+        assert_eq!(Position::locate("a\rb\r\nc", 5), (2, 1));
+    }
+}
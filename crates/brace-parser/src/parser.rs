@@ -1,5 +1,11 @@
+use crate::character::CharPredicate;
 use crate::combinator::series::Series;
 use crate::error::Error;
+#[cfg(feature = "unicode")]
+use crate::normalize;
+use crate::options::ParseOptions;
+use crate::sequence::Sequence;
+use crate::state;
 
 pub type Output<'a, O> = Result<(O, &'a str), Error>;
 
@@ -7,16 +13,106 @@ pub fn parse<'a, P, O>(input: &'a str, parser: P) -> Output<'a, O>
 where
     P: Parser<'a, O>,
 {
-    parser.parse(input)
+    state::with_source(input, || parser.parse(input))
+}
+
+pub fn parse_at<'a, P, O>(input: &'a str, start: usize, parser: P) -> Output<'a, O>
+where
+    P: Parser<'a, O>,
+{
+    match input.get(start..) {
+        Some(rest) => state::with_source(input, || parser.parse(rest)),
+        None => Err(Error::invalid()),
+    }
+}
+
+pub fn parse_with<'a, P, O>(input: &'a str, parser: P, options: ParseOptions) -> Output<'a, O>
+where
+    P: Parser<'a, O>,
+{
+    state::with_source(input, || {
+        state::with_options(options, || {
+            state::with_fold_case(options.get_fold_case(), || match options.get_fuel() {
+                Some(fuel) => state::with_budget(fuel.steps(), || parser.parse(input)),
+                None => parser.parse(input),
+            })
+        })
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prefix<'a, O> {
+    pub result: Output<'a, O>,
+    pub valid_up_to: usize,
+}
+
+impl<'a, O> Prefix<'a, O> {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    pub fn valid<'b>(&self, input: &'b str) -> &'b str {
+        &input[..self.valid_up_to.min(input.len())]
+    }
+}
+
+pub fn parse_prefix<'a, P, O>(input: &'a str, parser: P) -> Prefix<'a, O>
+where
+    P: Parser<'a, O>,
+{
+    let (result, reach) =
+        state::with_source(input, || state::with_reach(input, || parser.parse(input)));
+    let valid_up_to = match &result {
+        Ok((_, rem)) => input.len() - rem.len(),
+        Err(err) => match err.get_span() {
+            Some(span) => span.start.offset.max(reach),
+            None => reach,
+        },
+    };
+
+    Prefix {
+        result,
+        valid_up_to: valid_up_to.min(input.len()),
+    }
+}
+
+pub fn offset(original: &str, remainder: &str) -> Option<usize> {
+    let start = original.as_ptr() as usize;
+    let pos = remainder.as_ptr() as usize;
+
+    if start <= pos && pos + remainder.len() <= start + original.len() {
+        Some(pos - start)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn source_offset(source: &str, input: &str) -> Result<usize, Error> {
+    match offset(source, input) {
+        Some(offset) => Ok(offset),
+        None => Err(Error::invalid().with_context("input outside of source")),
+    }
+}
+
+pub fn peek_char(input: &str) -> Option<char> {
+    input.chars().next()
+}
+
+pub fn peek_slice(input: &str, count: usize) -> Option<&str> {
+    match input.char_indices().nth(count) {
+        Some((idx, _)) => Some(&input[..idx]),
+        None if input.chars().count() == count => Some(input),
+        None => None,
+    }
 }
 
 pub fn take<'a, P>(predicate: P) -> impl Parser<'a, &'a str>
 where
-    P: Fn(char) -> bool,
+    P: CharPredicate,
 {
     move |input: &'a str| match input.chars().next() {
         Some(ch) => {
-            if predicate(ch) {
+            if predicate.is_match(ch) {
                 Ok(input.split_at(ch.len_utf8()))
             } else {
                 Err(Error::found(ch))
@@ -28,7 +124,7 @@ where
 
 pub fn take_while<'a, P>(predicate: P) -> impl Parser<'a, &'a str>
 where
-    P: Fn(char) -> bool,
+    P: CharPredicate,
 {
     move |input: &'a str| {
         let mut iter = input.chars();
@@ -36,11 +132,11 @@ where
 
         match iter.next() {
             Some(ch) => {
-                if predicate(ch) {
+                if predicate.is_match(ch) {
                     pos = ch.len_utf8();
 
                     for ch in iter {
-                        if !predicate(ch) {
+                        if !predicate.is_match(ch) {
                             break;
                         }
 
@@ -57,6 +153,48 @@ where
     }
 }
 
+pub fn take_while0<'a, P>(predicate: P) -> impl Parser<'a, &'a str>
+where
+    P: CharPredicate,
+{
+    move |input: &'a str| {
+        let pos = input
+            .char_indices()
+            .find(|(_, ch)| !predicate.is_match(*ch))
+            .map(|(idx, _)| idx)
+            .unwrap_or_else(|| input.len());
+
+        Ok(input.split_at(pos))
+    }
+}
+
+pub fn take_till<'a, P>(predicate: P) -> impl Parser<'a, &'a str>
+where
+    P: CharPredicate,
+{
+    take_while0(predicate.negate())
+}
+
+pub fn take_bytes<'a>(count: usize) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let expect = || Error::expect(Sequence::custom(format!("{} bytes", count)));
+
+        if input.len() < count {
+            Err(expect().but_found_end())
+        } else if !input.is_char_boundary(count) {
+            let start = (0..count).rev().find(|idx| input.is_char_boundary(*idx));
+            let ch = input[start.unwrap_or(0)..]
+                .chars()
+                .next()
+                .unwrap_or_default();
+
+            Err(expect().but_found(ch))
+        } else {
+            Ok(input.split_at(count))
+        }
+    }
+}
+
 pub trait Parser<'a, O> {
     fn parse(&self, input: &'a str) -> Output<'a, O>;
 }
@@ -66,7 +204,26 @@ where
     T: Fn(&'a str) -> Output<'a, O>,
 {
     fn parse(&self, input: &'a str) -> Output<'a, O> {
-        (self)(input)
+        if !state::flags().hooks() {
+            return (self)(input);
+        }
+
+        state::tick(input)?;
+        state::rewinding(|| (self)(input))
+    }
+}
+
+pub struct BoxedParser<'a, O>(Box<dyn Parser<'a, O> + 'a>);
+
+impl<'a, O> BoxedParser<'a, O> {
+    pub fn new(parser: impl Parser<'a, O> + 'a) -> Self {
+        Self(Box::new(parser))
+    }
+}
+
+impl<'a, O> Parser<'a, O> for BoxedParser<'a, O> {
+    fn parse(&self, input: &'a str) -> Output<'a, O> {
+        self.0.parse(input)
     }
 }
 
@@ -78,7 +235,15 @@ impl<'a> Parser<'a, ()> for () {
 
 impl<'a> Parser<'a, char> for char {
     fn parse(&self, input: &'a str) -> Output<'a, char> {
-        take(|ch| ch == *self)
+        #[cfg(feature = "unicode")]
+        if state::flags().normalize() {
+            let mut buf = [0; 4];
+
+            return parse_normalized(self.encode_utf8(&mut buf), input)
+                .map(|(_, rem)| (*self, rem));
+        }
+
+        take(|ch: char| ch == *self || state::fold_case() && fold_eq(ch, *self))
             .parse(input)
             .map(|(_, rem)| (*self, rem))
             .map_err(|err| err.but_expect(*self))
@@ -87,6 +252,21 @@ impl<'a> Parser<'a, char> for char {
 
 impl<'a, 'b> Parser<'a, &'a str> for &'b str {
     fn parse(&self, input: &'a str) -> Output<'a, &'a str> {
+        let flags = state::flags();
+
+        if flags.hooks() {
+            state::tick(input)?;
+        }
+
+        #[cfg(feature = "unicode")]
+        if flags.normalize() {
+            return parse_normalized(self, input);
+        }
+
+        if flags.fold_case() {
+            return parse_folded(self, input);
+        }
+
         let mut iter = input.chars();
         let mut idx = 0;
 
@@ -113,6 +293,82 @@ impl<'a> Parser<'a, &'a str> for String {
     }
 }
 
+#[cfg(not(feature = "unicode"))]
+fn fold_eq(a: char, b: char) -> bool {
+    a.eq_ignore_ascii_case(&b)
+}
+
+#[cfg(feature = "unicode")]
+fn fold_eq(a: char, b: char) -> bool {
+    fold(a).eq(fold(b))
+}
+
+#[cfg(feature = "unicode")]
+fn fold(ch: char) -> impl Iterator<Item = char> {
+    ch.to_lowercase()
+        .flat_map(char::to_uppercase)
+        .flat_map(char::to_lowercase)
+}
+
+#[cfg(not(feature = "unicode"))]
+fn parse_folded<'a>(literal: &str, input: &'a str) -> Output<'a, &'a str> {
+    let mut iter = input.chars();
+    let mut idx = 0;
+
+    for ch in literal.chars() {
+        match iter.next() {
+            Some(character) => {
+                if fold_eq(ch, character) {
+                    idx += character.len_utf8();
+                } else {
+                    return Err(Error::expect(ch).but_found(character));
+                }
+            }
+            None => return Err(Error::expect(ch).but_found_end()),
+        }
+    }
+
+    Ok(input.split_at(idx))
+}
+
+#[cfg(feature = "unicode")]
+fn parse_folded<'a>(literal: &str, input: &'a str) -> Output<'a, &'a str> {
+    let mut expected = literal
+        .chars()
+        .flat_map(|ch| fold(ch).map(move |folded| (ch, folded)))
+        .peekable();
+    let mut idx = 0;
+
+    for character in input.chars() {
+        if expected.peek().is_none() {
+            break;
+        }
+
+        for folded in fold(character) {
+            match expected.next() {
+                Some((_, ch)) if ch == folded => {}
+                Some((ch, _)) => return Err(Error::expect(ch).but_found(character)),
+                None => return Err(Error::expect(literal).but_found(character)),
+            }
+        }
+
+        idx += character.len_utf8();
+    }
+
+    match expected.next() {
+        Some((ch, _)) => Err(Error::expect(ch).but_found_end()),
+        None => Ok(input.split_at(idx)),
+    }
+}
+
+#[cfg(feature = "unicode")]
+fn parse_normalized<'a>(literal: &str, input: &'a str) -> Output<'a, &'a str> {
+    let fold = state::fold_case();
+    let len = normalize::match_prefix(literal, input, |a, b| a == b || fold && fold_eq(a, b))?;
+
+    Ok(input.split_at(len))
+}
+
 macro_rules! impl_parser {
     ($(($a:tt, $b:ident, $c:ident),)+) => {
         impl_parser!(@iter $(($a, $b, $c),)+;);
@@ -133,37 +389,48 @@ macro_rules! impl_parser {
             $($T: Parser<'a, $O>,)+
         {
             fn parse(&self, input: &'a str) -> Output<'a, ($($O,)+)> {
-                self.parse_series(input)
+                state::rewinding(|| self.parse_series(input))
             }
         }
     };
 }
 
 impl_parser! {
-    (0, A, N),
-    (1, B, O),
-    (2, C, P),
-    (3, D, Q),
-    (4, E, R),
-    (5, F, S),
-    (6, G, T),
-    (7, H, U),
-    (8, I, V),
-    (9, J, W),
-    (10, K, X),
-    (11, L, Y),
+    (0, A, OA),
+    (1, B, OB),
+    (2, C, OC),
+    (3, D, OD),
+    (4, E, OE),
+    (5, F, OF),
+    (6, G, OG),
+    (7, H, OH),
+    (8, I, OI),
+    (9, J, OJ),
+    (10, K, OK),
+    (11, L, OL),
+    (12, M, OM),
+    (13, N, ON),
+    (14, P, OP),
+    (15, Q, OQ),
+    (16, R, OR),
+    (17, S, OS),
+    (18, T, OT),
+    (19, U, OU),
+    (20, V, OV),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::character::is_alphabetic;
+    use crate::combinator::series::{list, trio};
+    use crate::sequence::{alphabetic, decimal};
 
     struct Custom;
 
     impl<'a> Parser<'a, &'a str> for Custom {
         fn parse(&self, input: &'a str) -> Output<'a, &'a str> {
-            take(|ch| ch == '$').parse(input)
+            take(|ch: char| ch == '$').parse(input)
         }
     }
 
@@ -334,17 +601,17 @@ mod tests {
             Ok(("h", "ello world"))
         );
         assert_eq!(
-            parse("hello world", take(|_| true)),
+            parse("hello world", take(|_: char| true)),
             Ok(("h", "ello world"))
         );
         assert_eq!(
-            parse("hello world", take(|_| false)),
+            parse("hello world", take(|_: char| false)),
             Err(Error::found('h'))
         );
-        assert_eq!(parse("ß", take(|_| true)), Ok(("ß", "")));
-        assert_eq!(parse("ℝ", take(|_| true)), Ok(("ℝ", "")));
-        assert_eq!(parse("💣", take(|_| true)), Ok(("💣", "")));
-        assert_eq!(parse("ßℝ💣", take(|_| true)), Ok(("ß", "ℝ💣")));
+        assert_eq!(parse("ß", take(|_: char| true)), Ok(("ß", "")));
+        assert_eq!(parse("ℝ", take(|_: char| true)), Ok(("ℝ", "")));
+        assert_eq!(parse("💣", take(|_: char| true)), Ok(("💣", "")));
+        assert_eq!(parse("ßℝ💣", take(|_: char| true)), Ok(("ß", "ℝ💣")));
     }
 
     #[test]
@@ -360,16 +627,205 @@ mod tests {
             Ok(("hello", " world"))
         );
         assert_eq!(
-            parse("hello world", take_while(|_| true)),
+            parse("hello world", take_while(|_: char| true)),
             Ok(("hello world", ""))
         );
         assert_eq!(
-            parse("hello world", take_while(|_| false)),
+            parse("hello world", take_while(|_: char| false)),
             Err(Error::found('h'))
         );
-        assert_eq!(parse("ß", take_while(|_| true)), Ok(("ß", "")));
-        assert_eq!(parse("ℝ", take_while(|_| true)), Ok(("ℝ", "")));
-        assert_eq!(parse("💣", take_while(|_| true)), Ok(("💣", "")));
-        assert_eq!(parse("ßℝ💣", take_while(|_| true)), Ok(("ßℝ💣", "")));
+        assert_eq!(parse("ß", take_while(|_: char| true)), Ok(("ß", "")));
+        assert_eq!(parse("ℝ", take_while(|_: char| true)), Ok(("ℝ", "")));
+        assert_eq!(parse("💣", take_while(|_: char| true)), Ok(("💣", "")));
+        assert_eq!(parse("ßℝ💣", take_while(|_: char| true)), Ok(("ßℝ💣", "")));
+    }
+
+    #[test]
+    fn test_take_while0() {
+        assert_eq!(parse("", take_while0(is_alphabetic)), Ok(("", "")));
+        assert_eq!(parse("h", take_while0(is_alphabetic)), Ok(("h", "")));
+        assert_eq!(
+            parse("hello world", take_while0(is_alphabetic)),
+            Ok(("hello", " world"))
+        );
+        assert_eq!(
+            parse("hello world", take_while0(|_: char| true)),
+            Ok(("hello world", ""))
+        );
+        assert_eq!(
+            parse("hello world", take_while0(|_: char| false)),
+            Ok(("", "hello world"))
+        );
+        assert_eq!(
+            parse("ßℝ💣", take_while0(|ch: char| ch != '💣')),
+            Ok(("ßℝ", "💣"))
+        );
+    }
+
+    #[test]
+    fn test_take_bytes() {
+        assert_eq!(parse("abcdef", take_bytes(4)), Ok(("abcd", "ef")));
+        assert_eq!(parse("abc", take_bytes(0)), Ok(("", "abc")));
+        assert_eq!(parse("aß€", take_bytes(6)), Ok(("aß€", "")));
+        assert_eq!(
+            parse("aß€", take_bytes(7)),
+            Err(Error::expect(Sequence::custom("7 bytes")).but_found_end())
+        );
+        assert_eq!(
+            parse("aß€", take_bytes(4)),
+            Err(Error::expect(Sequence::custom("4 bytes")).but_found('€'))
+        );
+        assert_eq!(
+            (take_bytes(2), take_bytes(3)).parse("0042x"),
+            Ok((("00", "42x"), ""))
+        );
+    }
+
+    #[test]
+    fn test_take_till() {
+        assert_eq!(parse("", take_till(|ch: char| ch == ',')), Ok(("", "")));
+        assert_eq!(parse(",", take_till(|ch: char| ch == ',')), Ok(("", ",")));
+        assert_eq!(
+            parse("hello, world", take_till(|ch: char| ch == ',')),
+            Ok(("hello", ", world"))
+        );
+        assert_eq!(
+            parse("hello world", take_till(|ch: char| ch == ',')),
+            Ok(("hello world", ""))
+        );
+        assert_eq!(
+            parse("hello world", take_till(is_alphabetic)),
+            Ok(("", "hello world"))
+        );
+        assert_eq!(
+            parse("ßℝ💣", take_till(|ch: char| ch == '💣')),
+            Ok(("ßℝ", "💣"))
+        );
+    }
+
+    #[test]
+    fn test_take_pattern() {
+        assert_eq!(parse("x1", take('x')), Ok(("x", "1")));
+        assert_eq!(parse("c", take('a'..='f')), Ok(("c", "")));
+        assert_eq!(parse("+1", take("+-")), Ok(("+", "1")));
+        assert_eq!(parse("*1", take("+-")), Err(Error::found('*')));
+        assert_eq!(
+            parse("1_000_000.5", take_while("0123456789_")),
+            Ok(("1_000_000", ".5"))
+        );
+        assert_eq!(
+            parse(
+                "fe09z",
+                take_while(['a', 'b', 'c', 'd', 'e', 'f'].or('0'..='9'))
+            ),
+            Ok(("fe09", "z"))
+        );
+        assert_eq!(parse("  x", take_while0(&[' ', '\t'][..])), Ok(("  ", "x")));
+        assert_eq!(parse("key=value", take_till('=')), Ok(("key", "=value")));
+        assert_eq!(parse("a; b", take_till(";,")), Ok(("a", "; b")));
+        assert_eq!(
+            parse("ab c", take_while(|ch: char| ch.is_ascii_graphic())),
+            Ok(("ab", " c"))
+        );
+    }
+
+    #[test]
+    fn test_parse_prefix() {
+        let assign = || trio(alphabetic, " = ", decimal);
+        let prefix = parse_prefix("x = y", assign());
+
+        assert_eq!(prefix.valid_up_to, 4);
+        assert_eq!(prefix.valid("x = y"), "x = ");
+        assert_eq!(
+            prefix.result,
+            Err(Error::expect(Sequence::Decimal).but_found('y'))
+        );
+
+        let prefix = parse_prefix("x = 1;", assign());
+
+        assert!(prefix.is_ok());
+        assert_eq!(prefix.valid_up_to, 5);
+
+        let prefix = parse_prefix("1,2,3,x,4", trio(list(decimal, ','), ',', decimal));
+
+        assert_eq!(prefix.valid_up_to, 6);
+        assert_eq!(parse_prefix("", decimal).valid_up_to, 0);
+    }
+
+    #[test]
+    fn test_parse_prefix_state() {
+        let counting = |input| {
+            assert!(!state::flags().branch_hooks());
+            decimal.parse(input)
+        };
+
+        assert!(parse_prefix("12", counting).is_ok());
+
+        let panicked = std::panic::catch_unwind(|| {
+            parse_prefix("12", |_: &str| -> Output<'_, ()> {
+                panic!("parser panicked")
+            })
+        });
+
+        assert!(panicked.is_err());
+        assert!(!state::flags().hooks());
+        assert_eq!(parse_prefix("1x", decimal).valid_up_to, 1);
+    }
+
+    #[test]
+    fn test_parse_at() {
+        assert_eq!(parse_at("let x", 4, 'x'), Ok(('x', "")));
+        assert_eq!(parse_at("let x", 5, ""), Ok(("", "")));
+        assert_eq!(
+            parse_at("let x", 0, 'x'),
+            Err(Error::expect('x').but_found('l'))
+        );
+        assert_eq!(parse_at("let x", 6, 'x'), Err(Error::invalid()));
+        assert_eq!(parse_at("é", 1, 'x'), Err(Error::invalid()));
+    }
+
+    #[test]
+    fn test_offset() {
+        let input = "hello world";
+        let (_, rem) = parse(input, "hello").unwrap();
+
+        assert_eq!(offset(input, input), Some(0));
+        assert_eq!(offset(input, rem), Some(5));
+        assert_eq!(offset(input, &input[11..]), Some(11));
+        assert_eq!(offset(&input[6..], &input[8..10]), Some(2));
+    }
+
+    #[test]
+    fn test_offset_foreign() {
+        let input = "hello world";
+
+        assert_eq!(offset(&input[6..], input), None);
+        assert_eq!(offset(input, "hello"), None);
+        assert_eq!(
+            source_offset(&input[6..], input),
+            Err(Error::invalid().with_context("input outside of source"))
+        );
+    }
+
+    #[test]
+    fn test_peek() {
+        assert_eq!(peek_char("ßx"), Some('ß'));
+        assert_eq!(peek_char(""), None);
+        assert_eq!(peek_slice("ß€x", 2), Some("ß€"));
+        assert_eq!(peek_slice("ß€x", 3), Some("ß€x"));
+        assert_eq!(peek_slice("ß€x", 4), None);
+        assert_eq!(peek_slice("abc", 0), Some(""));
+        assert_eq!(peek_slice("", 0), Some(""));
+
+        let dispatch = |input| match peek_slice(input, 2) {
+            Some("0x") => take_while(|ch: char| ch.is_ascii_hexdigit()).parse(&input[2..]),
+            _ => match peek_char(input) {
+                Some('0'..='9') => crate::sequence::decimal.parse(input),
+                _ => Err(Error::invalid()),
+            },
+        };
+
+        assert_eq!(dispatch("0xff;"), Ok(("ff", ";")));
+        assert_eq!(dispatch("42;"), Ok(("42", ";")));
     }
 }
@@ -0,0 +1,670 @@
+use crate::combinator::branch::{branch, either, opt_or_else, optional};
+use crate::combinator::series::{delimited, leading, pair, repeat, trailing};
+use crate::combinator::{complete, consume, context, fail, map};
+use crate::error::Error;
+use crate::parser::{take, take_till, take_while0, Output, Parser};
+use crate::sequence::decimal;
+
+pub use crate::grammar::{
+    Analysis, Backend, CharSet, Conflict, ConflictKind, Expr, Generator, Grammar, Modifier, Pair,
+};
+
+impl Grammar {
+    pub fn new(source: &str) -> Result<Self, Error> {
+        Self::with_backend(source, Backend::Peg)
+    }
+
+    pub fn with_backend(source: &str, backend: Backend) -> Result<Self, Error> {
+        let (rules, _) = complete(leading(
+            whitespace,
+            opt_or_else(repeat(trailing(rule, whitespace)), Vec::new),
+        ))
+        .parse(source)?;
+
+        rules
+            .into_iter()
+            .fold(Grammar::builder(), |builder, (name, modifier, expr)| {
+                builder.rule_with(name, modifier, expr)
+            })
+            .backend(backend)
+            .build()
+    }
+}
+
+enum Postfix {
+    Opt,
+    Rep(usize, Option<usize>),
+}
+
+fn rule(input: &str) -> Output<'_, (&str, Modifier, Expr)> {
+    map(
+        (
+            ident,
+            whitespace,
+            '=',
+            whitespace,
+            opt_or_else(trailing(modifier, whitespace), || Modifier::Normal),
+            '{',
+            fail(expr),
+            fail('}'),
+        ),
+        |(name, _, _, _, modifier, _, expr, _)| (name, modifier, expr),
+    )
+    .parse(input)
+}
+
+fn modifier(input: &str) -> Output<Modifier> {
+    branch((
+        map('_', |_| Modifier::Silent),
+        map('@', |_| Modifier::Atomic),
+        map('$', |_| Modifier::CompoundAtomic),
+        map('!', |_| Modifier::NonAtomic),
+    ))
+    .parse(input)
+}
+
+fn expr(input: &str) -> Output<Expr> {
+    map(
+        leading(
+            (whitespace, optional('|')),
+            pair(seq, opt_or_else(repeat(leading('|', seq)), Vec::new)),
+        ),
+        |(first, rest)| match rest.is_empty() {
+            true => first,
+            false => Expr::Choice(std::iter::once(first).chain(rest).collect()),
+        },
+    )
+    .parse(input)
+}
+
+fn seq(input: &str) -> Output<Expr> {
+    map(
+        pair(term, opt_or_else(repeat(leading('~', term)), Vec::new)),
+        |(first, rest)| match rest.is_empty() {
+            true => first,
+            false => Expr::Seq(std::iter::once(first).chain(rest).collect()),
+        },
+    )
+    .parse(input)
+}
+
+fn term(input: &str) -> Output<Expr> {
+    map(
+        delimited(
+            whitespace,
+            (
+                opt_or_else(repeat(trailing(either('&', '!'), whitespace)), Vec::new),
+                primary,
+                opt_or_else(repeat(leading(whitespace, postfix)), Vec::new),
+            ),
+            whitespace,
+        ),
+        |(prefixes, primary, postfixes)| {
+            let expr = postfixes
+                .into_iter()
+                .fold(primary, |expr, postfix| match postfix {
+                    Postfix::Opt => Expr::Opt(Box::new(expr)),
+                    Postfix::Rep(min, max) => Expr::Rep(Box::new(expr), min, max),
+                });
+
+            prefixes
+                .into_iter()
+                .rev()
+                .fold(expr, |expr, prefix| match prefix {
+                    '&' => Expr::Pos(Box::new(expr)),
+                    _ => Expr::Neg(Box::new(expr)),
+                })
+        },
+    )
+    .parse(input)
+}
+
+fn primary(input: &str) -> Output<Expr> {
+    context(
+        "expression",
+        branch((
+            delimited('(', expr, fail(')')),
+            map(leading('^', string), Expr::Insensitive),
+            map(string, Expr::Str),
+            map(
+                pair(
+                    character,
+                    optional(leading((whitespace, "..", whitespace), fail(character))),
+                ),
+                |(lo, hi)| match hi {
+                    Some(hi) => Expr::Range(lo, hi),
+                    None => Expr::Str(lo.to_string()),
+                },
+            ),
+            map(ident, |name| Expr::Ident(name.to_owned())),
+        )),
+    )
+    .parse(input)
+}
+
+fn postfix(input: &str) -> Output<Postfix> {
+    branch((
+        map('?', |_| Postfix::Opt),
+        map('*', |_| Postfix::Rep(0, None)),
+        map('+', |_| Postfix::Rep(1, None)),
+        map(
+            delimited(
+                '{',
+                pair(optional(number), optional(leading(',', optional(number)))),
+                fail('}'),
+            ),
+            |(min, max)| match max {
+                Some(max) => Postfix::Rep(min.unwrap_or(0), max),
+                None => Postfix::Rep(min.unwrap_or(0), min),
+            },
+        ),
+    ))
+    .parse(input)
+}
+
+fn number(input: &str) -> Output<usize> {
+    delimited(whitespace, decimal, whitespace)
+        .parse(input)
+        .and_then(|(out, rem)| match out.parse() {
+            Ok(num) => Ok((num, rem)),
+            Err(_) => Err(Error::invalid()),
+        })
+}
+
+fn ident(input: &str) -> Output<&str> {
+    consume(pair(
+        take(|ch: char| ch.is_ascii_alphabetic() || ch == '_'),
+        take_while0(|ch: char| ch.is_ascii_alphanumeric() || ch == '_'),
+    ))
+    .parse(input)
+}
+
+fn string(input: &str) -> Output<String> {
+    let (_, mut rem) = '"'.parse(input)?;
+    let mut out = String::new();
+
+    loop {
+        match rem.chars().next() {
+            Some('"') => return Ok((out, &rem[1..])),
+            Some(_) => {
+                let (ch, next) = escape(rem)?;
+
+                out.push(ch);
+                rem = next;
+            }
+            None => return Err(Error::expect('"').but_found_end().into_fail()),
+        }
+    }
+}
+
+fn character(input: &str) -> Output<char> {
+    delimited('\'', escape, fail('\'')).parse(input)
+}
+
+fn escape(input: &str) -> Output<char> {
+    let mut iter = input.chars();
+
+    match iter.next() {
+        Some('\\') => match iter.next() {
+            Some('n') => Ok(('\n', &input[2..])),
+            Some('r') => Ok(('\r', &input[2..])),
+            Some('t') => Ok(('\t', &input[2..])),
+            Some('0') => Ok(('\0', &input[2..])),
+            Some('u') => {
+                let (hex, rem) = delimited("{", take_till('}'), fail('}')).parse(&input[2..])?;
+
+                match u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) {
+                    Some(ch) => Ok((ch, rem)),
+                    None => Err(Error::invalid().with_context("unicode escape")),
+                }
+            }
+            Some(ch) => Ok((ch, &input[1 + ch.len_utf8()..])),
+            None => Err(Error::found_end().into_fail()),
+        },
+        Some(ch) => Ok((ch, &input[ch.len_utf8()..])),
+        None => Err(Error::found_end()),
+    }
+}
+
+fn whitespace(input: &str) -> Output<()> {
+    let mut rem = input;
+
+    loop {
+        rem = rem.trim_start();
+
+        if let Some(comment) = rem.strip_prefix("//") {
+            rem = take_till('\n').parse(comment)?.1;
+        } else if let Some(comment) = rem.strip_prefix("/*") {
+            match comment.find("*/") {
+                Some(idx) => rem = &comment[idx + 2..],
+                None => return Err(Error::expect("*/").but_found_end().into_fail()),
+            }
+        } else {
+            return Ok(((), rem));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = r#"
+        // A small CSV grammar.
+        field = { (ASCII_DIGIT | "." | "-")+ }
+        record = { field ~ ("," ~ field)* }
+        file = { SOI ~ (record ~ ("\r\n" | "\n"))* ~ EOI }
+    "#;
+
+    fn texts<'a>(pairs: &[Pair<'a>]) -> Vec<&'a str> {
+        pairs.iter().map(|pair| pair.text).collect()
+    }
+
+    #[test]
+    fn test_grammar() {
+        let grammar = Grammar::new(CSV).unwrap();
+
+        assert_eq!(
+            grammar.rules().collect::<Vec<_>>(),
+            vec!["field", "record", "file"]
+        );
+
+        let (pairs, rem) = grammar.parse("file", "1,2.5\n-3,4\n").unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].rule, "file");
+        assert_eq!(texts(&pairs[0].children), vec!["1,2.5", "-3,4"]);
+        assert_eq!(
+            pairs[0].children[0]
+                .children
+                .iter()
+                .map(|pair| (pair.rule.as_str(), pair.text))
+                .collect::<Vec<_>>(),
+            vec![("field", "1"), ("field", "2.5")]
+        );
+    }
+
+    #[test]
+    fn test_grammar_error() {
+        let grammar = Grammar::new(CSV).unwrap();
+
+        assert_eq!(
+            grammar.parse("field", "x"),
+            Err(Error::expect('-').but_found('x').with_context("field"))
+        );
+        assert_eq!(
+            grammar.parse("file", "1,2\nx"),
+            Err(Error::expect(crate::error::Expect::End)
+                .but_found('x')
+                .with_context("file"))
+        );
+        assert_eq!(
+            grammar.parse("missing", "x"),
+            Err(Error::invalid().with_context("undefined rule `missing`"))
+        );
+        assert_eq!(
+            Grammar::new("a = { b }"),
+            Err(Error::invalid().with_context("undefined rule `b`"))
+        );
+        assert!(Grammar::new("a = { \"x\" ").is_err());
+        assert_eq!(
+            Grammar::new("a = { 1 }").map(|_| ()),
+            Err(Error::found('1').with_context("expression").into_fail())
+        );
+    }
+
+    #[test]
+    fn test_grammar_modifiers() {
+        let grammar = Grammar::new(
+            r##"
+            WHITESPACE = _{ " " | "\t" }
+            COMMENT = _{ "#" ~ (!"\n" ~ ANY)* }
+            ident = @{ ASCII_ALPHA ~ (ASCII_ALPHANUMERIC | "_")* }
+            number = @{ ASCII_DIGIT+ }
+            string = ${ "\"" ~ inner ~ "\"" }
+            inner = @{ (!"\"" ~ ANY)* }
+            value = _{ number | string | ident }
+            assign = { ^"let" ~ ident ~ "=" ~ value }
+            "##,
+        )
+        .unwrap();
+        let (pairs, rem) = grammar.parse("assign", "LET x1 = \"a b\" # note").unwrap();
+        let children = pairs[0]
+            .children
+            .iter()
+            .map(|pair| (pair.rule.as_str(), pair.text, pair.children.len()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(rem, " # note");
+        assert_eq!(children, vec![("ident", "x1", 0), ("string", "\"a b\"", 1)]);
+        assert_eq!(pairs[0].children[1].children[0].text, "a b");
+        assert_eq!(
+            grammar.parse("assign", "let x = 12").unwrap().0[0].children[1].rule,
+            "number"
+        );
+    }
+
+    #[test]
+    fn test_grammar_repetition() {
+        let grammar = Grammar::new(
+            r##"
+            hex = { '0'..'9' | 'a'..'f' }
+            byte = _{ hex{2} }
+            short = { hex{1,3} }
+            many = { hex{2,} }
+            color = { "#" ~ &hex ~ byte{3} ~ !hex }
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(grammar.parse("short", "abcd").unwrap().1, "d");
+        assert!(grammar.parse("many", "a").is_err());
+        assert_eq!(grammar.parse("many", "abcdz").unwrap().1, "z");
+        assert_eq!(
+            grammar.parse("color", "#00ff7f").unwrap().0[0]
+                .children
+                .len(),
+            6
+        );
+        assert_eq!(
+            grammar.parse("color", "#00ff7f0"),
+            Err(Error::found('0').with_context("color"))
+        );
+    }
+
+    #[test]
+    fn test_to_ebnf() {
+        let grammar = Grammar::new(
+            r##"
+            WHITESPACE = _{ " " }
+            digit = { '0'..'9' }
+            number = @{ "-"? ~ digit+ }
+            pair = { (number | ^"nil") ~ ("," ~ number){1,3} ~ !"\"" }
+            list = { "[" ~ (pair ~ (";" ~ pair)*)? ~ "]" ~ digit{2,} ~ digit{,2} ~ digit{2} }
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            grammar.to_ebnf(),
+            concat!(
+                "WHITESPACE = \" \" ; (* silent *)\n",
+                "digit = ? '0' to '9' ? ;\n",
+                "number = [ \"-\" ] , digit , { digit } ; (* atomic *)\n",
+                "pair = ( number | ? case-insensitive \"nil\" ? ) , ( \",\" , number ) , 2 * [ \",\" , number ] , ? not followed by '\"' ? ;\n",
+                "list = \"[\" , [ pair , { \";\" , pair } ] , \"]\" , 2 * digit , { digit } , 2 * [ digit ] , 2 * digit ;\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_generator() {
+        let grammar = Grammar::new(
+            r#"
+            WHITESPACE = _{ " " | "\n" }
+            value = { object | array | string | number | ^"null" }
+            object = { "{" ~ (member ~ ("," ~ member)*)? ~ "}" }
+            member = { string ~ ":" ~ value }
+            array = { "[" ~ (value ~ ("," ~ value){0,4})? ~ "]" }
+            string = @{ "\"" ~ ('a'..'z' | ASCII_DIGIT)* ~ "\"" }
+            number = @{ "-"? ~ ASCII_NONZERO_DIGIT ~ ASCII_DIGIT{,3} }
+            "#,
+        )
+        .unwrap();
+        let mut generator = grammar.generator(7).max_depth(8);
+
+        for _ in 0..100 {
+            let valid = generator.valid("value").unwrap();
+            let invalid = generator.invalid("value").unwrap();
+
+            assert!(matches!(grammar.parse("value", &valid), Ok((_, ""))));
+            assert!(!matches!(grammar.parse("value", &invalid), Ok((_, ""))));
+        }
+
+        assert_eq!(
+            grammar.generator(1).valid("object"),
+            grammar.generator(1).valid("object")
+        );
+        assert_eq!(generator.valid("missing"), None);
+    }
+
+    #[test]
+    fn test_earley_matches_peg() {
+        let cases = [
+            (CSV, "file", "1,2.5\n-3,4\n"),
+            (CSV, "record", "1,2,x"),
+            (
+                r##"
+                WHITESPACE = _{ " " | "\t" }
+                COMMENT = _{ "#" ~ (!"\n" ~ ANY)* }
+                ident = @{ ASCII_ALPHA ~ (ASCII_ALPHANUMERIC | "_")* }
+                number = @{ ASCII_DIGIT+ }
+                string = ${ "\"" ~ inner ~ "\"" }
+                inner = @{ (!"\"" ~ ANY)* }
+                value = _{ number | string | ident }
+                assign = { ^"let" ~ ident ~ "=" ~ value }
+                "##,
+                "assign",
+                "LET x1 = \"a b\" # note",
+            ),
+            (
+                r##"
+                hex = { '0'..'9' | 'a'..'f' }
+                byte = _{ hex{2} }
+                short = { hex{1,3} }
+                color = { "#" ~ &hex ~ byte{3} ~ !hex }
+                "##,
+                "color",
+                "#00ff7f",
+            ),
+            (
+                r##"
+                WHITESPACE = _{ " " }
+                num = @{ ASCII_DIGIT+ }
+                some = { num{2,4} }
+                "##,
+                "some",
+                "1 2 3 4 5",
+            ),
+            (
+                r##"
+                WHITESPACE = _{ " " }
+                num = @{ ASCII_DIGIT+ }
+                few = { num{,2} ~ "." }
+                "##,
+                "few",
+                "1 2 .",
+            ),
+        ];
+
+        for (source, rule, input) in cases.iter() {
+            let grammar = Grammar::new(source).unwrap();
+            let earley = Grammar::with_backend(source, Backend::Earley).unwrap();
+
+            assert_eq!(earley.parse(rule, input), grammar.parse(rule, input));
+        }
+    }
+
+    #[test]
+    fn test_earley_left_recursion() {
+        let grammar = Grammar::with_backend(
+            r#"
+            WHITESPACE = _{ " " }
+            expr = { expr ~ "-" ~ num | num }
+            num = @{ ASCII_DIGIT+ }
+            "#,
+            Backend::Earley,
+        )
+        .unwrap();
+        let (pairs, rem) = grammar.parse("expr", "10 - 2 - 3;").unwrap();
+
+        assert_eq!(rem, ";");
+        assert_eq!(texts(&pairs), vec!["10 - 2 - 3"]);
+        assert_eq!(texts(&pairs[0].children), vec!["10 - 2", "3"]);
+        assert_eq!(texts(&pairs[0].children[0].children), vec!["10", "2"]);
+        assert_eq!(
+            grammar.parse("expr", "- 1"),
+            Err(Error::found('-').with_context("expr"))
+        );
+        assert_eq!(
+            grammar
+                .parse("expr", "1 -")
+                .map(|(pairs, rem)| (texts(&pairs), rem)),
+            Ok((vec!["1"], " -"))
+        );
+    }
+
+    #[test]
+    fn test_earley_bounded_repetition() {
+        let grammar = Grammar::with_backend(
+            r#"
+            digits = { ASCII_DIGIT{2,5000} }
+            "#,
+            Backend::Earley,
+        )
+        .unwrap();
+
+        assert_eq!(
+            grammar
+                .parse("digits", "123a")
+                .map(|(pairs, rem)| (texts(&pairs), rem)),
+            Ok((vec!["123"], "a"))
+        );
+        assert_eq!(
+            grammar.parse("digits", "1a"),
+            Err(Error::found('a').with_context("digits"))
+        );
+    }
+
+    #[test]
+    fn test_earley_ambiguity() {
+        let grammar = Grammar::with_backend(
+            r#"
+            list = { item ~ ("," ~ item)* }
+            item = { word ~ word? | word }
+            word = @{ ASCII_ALPHA+ ~ " "? }
+            "#,
+            Backend::Earley,
+        )
+        .unwrap();
+        let (pairs, rem) = grammar.parse("list", "a b,c").unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(texts(&pairs[0].children), vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn test_analyze() {
+        let grammar = Grammar::new(
+            r#"
+            ident = { ASCII_ALPHA ~ ASCII_ALPHANUMERIC* }
+            sign = { "+" | "-" }
+            signs = { sign* }
+            number = { sign? ~ ASCII_DIGIT+ }
+            assign = { ident ~ "=" ~ number ~ ";" }
+            program = { SOI ~ assign* ~ EOI }
+            "#,
+        )
+        .unwrap();
+        let analysis = grammar.analyze();
+        let first = |rule| analysis.first(rule).map(ToString::to_string);
+        let follow = |rule| analysis.follow(rule).map(ToString::to_string);
+
+        assert_eq!(first("number"), Some("['+', '-', '0'..'9']".to_owned()));
+        assert_eq!(
+            first("program"),
+            Some("['A'..'Z', 'a'..'z', EOI]".to_owned())
+        );
+        assert_eq!(follow("ident"), Some("['=']".to_owned()));
+        assert_eq!(follow("sign"), Some("['+', '-', '0'..'9']".to_owned()));
+        assert_eq!(
+            follow("assign"),
+            Some("['A'..'Z', 'a'..'z', EOI]".to_owned())
+        );
+        assert_eq!(first("missing"), None);
+        assert!(analysis.is_nullable("signs"));
+        assert!(!analysis.is_nullable("number"));
+        assert!(analysis.first("assign").unwrap().contains('q'));
+        assert!(analysis.follow("assign").unwrap().contains_end());
+        assert!(analysis.is_ll1());
+    }
+
+    #[test]
+    fn test_analyze_conflicts() {
+        let grammar = Grammar::new(
+            r#"
+            ident = { ASCII_ALPHA+ }
+            op = { "<" | "<=" | ">" }
+            digit = { '0'..'9' }
+            zero = { digit | "0" }
+            keyword = { "let" | ident }
+            empty = { "a"? | "b" }
+            tail = { ("x" ~ "y" | ASCII_DIGIT*) ~ "x" }
+            "#,
+        )
+        .unwrap();
+        let analysis = grammar.analyze();
+
+        assert_eq!(
+            analysis
+                .conflicts()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "rule `op`: alternative 2 of `\"<\" | \"<=\" | \">\"` can never match, alternative 1 always matches first",
+                "rule `zero`: alternative 2 of `digit | \"0\"` can never match, alternative 1 always matches first",
+                "rule `keyword`: alternatives 1 and 2 of `\"let\" | ident` both start with ['l']",
+                "rule `empty`: alternative 2 of `[ \"a\" ] | \"b\"` can never match, alternative 1 always matches first",
+                "rule `tail`: alternatives 1 and 2 of `\"x\" , \"y\" | { ASCII_DIGIT }` both start with ['x']",
+            ]
+        );
+        assert_eq!(analysis.conflicts()[0].kind, ConflictKind::Dead);
+        assert_eq!(analysis.conflicts()[2].kind, ConflictKind::Backtrack);
+        assert_eq!(
+            (analysis.conflicts()[2].other, analysis.conflicts()[2].arm),
+            (0, 1)
+        );
+        assert!(!analysis.is_ll1());
+    }
+
+    #[test]
+    fn test_left_recursion() {
+        let sources = [
+            r#"expr = { expr ~ "+" ~ "1" | "1" }"#,
+            r#"a = { b ~ "x" } b = { "y"? ~ a }"#,
+            r#"a = { &a ~ "x" }"#,
+        ];
+
+        for source in sources.iter() {
+            assert!(Grammar::new(source).is_err());
+            assert!(Grammar::with_backend(source, Backend::Earley).is_ok());
+        }
+
+        assert_eq!(
+            Grammar::new(r#"expr = { expr ~ "+" ~ "1" | "1" }"#),
+            Err(Error::invalid().with_context("rule `expr` is left-recursive"))
+        );
+        assert!(Grammar::new(r#"expr = { "1" ~ ("+" ~ expr)? }"#).is_ok());
+
+        let grammar = Grammar::with_backend(
+            r#"
+            expr = { expr ~ "+" ~ num | num }
+            num = { ASCII_DIGIT }
+            "#,
+            Backend::Earley,
+        )
+        .unwrap();
+        let analysis = grammar.analyze();
+
+        assert_eq!(analysis.left_recursive(), ["expr".to_owned()]);
+        assert!(!analysis.is_ll1());
+
+        let mut generator = grammar.generator(7);
+        let valid = generator.valid("expr").unwrap();
+
+        assert!(grammar.accepts("expr", &valid));
+        assert!(generator.invalid("expr").is_some());
+    }
+}
@@ -0,0 +1,465 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use crate::options::NewlinePolicy;
+use crate::state;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(input: &str, offset: usize) -> Self {
+        Self::new_with(input, offset, Columns::default())
+    }
+
+    pub fn new_with(input: &str, offset: usize, columns: Columns) -> Self {
+        let offset = offset.min(input.len());
+        let (line, column) = Self::locate_with(input, offset, columns);
+
+        Self {
+            offset,
+            line,
+            column,
+        }
+    }
+
+    pub fn locate(input: &str, offset: usize) -> (usize, usize) {
+        Self::locate_with(input, offset, Columns::default())
+    }
+
+    pub fn locate_with(input: &str, offset: usize, columns: Columns) -> (usize, usize) {
+        let offset = offset.min(input.len());
+        let end = floor(input, offset);
+        let (line, start) = line_starts(input, columns.newline)
+            .take_while(|start| *start <= end)
+            .fold((1, 0), |(line, _), start| (line + 1, start));
+
+        (line, column(&input[start..], offset - start, columns))
+    }
+
+    pub(crate) fn advance(self, source: &str, offset: usize, columns: Columns) -> Self {
+        let mut line = self.line;
+        let mut column = self.column.saturating_sub(1);
+
+        for (idx, ch) in source[self.offset..offset].char_indices() {
+            match is_break(source, self.offset + idx, columns.newline) {
+                true => {
+                    line += 1;
+                    column = 0;
+                }
+                false => column = step(column, ch, columns),
+            }
+        }
+
+        Self {
+            offset,
+            line,
+            column: column + 1,
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(input: &str, start: usize, end: usize) -> Self {
+        Self::new_with(input, start, end, Columns::default())
+    }
+
+    pub fn new_with(input: &str, start: usize, end: usize, columns: Columns) -> Self {
+        Self {
+            start: Position::new_with(input, start, columns),
+            end: Position::new_with(input, end.max(start), columns),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.offset - self.start.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start.offset <= offset && offset < self.end.offset
+    }
+
+    pub fn slice<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start.offset..self.end.offset]
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(value: T, span: Span) -> Self {
+        Self { value, span }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U> {
+        Spanned {
+            value: f(self.value),
+            span: self.span,
+        }
+    }
+
+    pub fn as_ref(&self) -> Spanned<&T> {
+        Spanned {
+            value: &self.value,
+            span: self.span,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    pub fn slice<'a>(&self, input: &'a str) -> &'a str {
+        self.span.slice(input)
+    }
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Spanned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.value, self.span)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Columns {
+    tab_width: usize,
+    utf16: bool,
+    newline: NewlinePolicy,
+}
+
+impl Columns {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    pub fn utf16(mut self, utf16: bool) -> Self {
+        self.utf16 = utf16;
+        self
+    }
+
+    pub fn newline(mut self, newline: NewlinePolicy) -> Self {
+        self.newline = newline;
+        self
+    }
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            tab_width: 1,
+            utf16: false,
+            newline: NewlinePolicy::Lf,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineIndex<'a> {
+    input: &'a str,
+    lines: Arc<[usize]>,
+    columns: Columns,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::with_columns(input, Columns::default())
+    }
+
+    pub fn with_columns(input: &'a str, columns: Columns) -> Self {
+        Self {
+            input,
+            lines: starts(input, columns.newline),
+            columns,
+        }
+    }
+
+    pub(crate) fn shared(input: &'a str) -> Self {
+        let columns = Columns::default();
+
+        match state::line_starts(input, || starts(input, columns.newline)) {
+            Some(lines) => Self {
+                input,
+                lines,
+                columns,
+            },
+            None => Self::with_columns(input, columns),
+        }
+    }
+
+    pub fn locate(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.input.len());
+        let line = match self.lines.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let start = self.lines[line];
+
+        (
+            line + 1,
+            column(&self.input[start..], offset - start, self.columns),
+        )
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.input.len());
+        let (line, column) = self.locate(offset);
+
+        Position {
+            offset,
+            line,
+            column,
+        }
+    }
+
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        Span {
+            start: self.position(start),
+            end: self.position(end.max(start)),
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        let start = *self.lines.get(line.checked_sub(1)?)?;
+        let end = self
+            .lines
+            .get(line)
+            .map(|end| end - 1)
+            .unwrap_or_else(|| self.input.len());
+
+        Some(self.input[start..end].trim_end_matches('\r'))
+    }
+}
+
+fn starts(input: &str, newline: NewlinePolicy) -> Arc<[usize]> {
+    let mut lines = vec![0];
+
+    lines.extend(line_starts(input, newline));
+    lines.into()
+}
+
+fn line_starts(input: &str, newline: NewlinePolicy) -> impl Iterator<Item = usize> + '_ {
+    input
+        .match_indices(['\n', '\r'])
+        .filter(move |(idx, _)| is_break(input, *idx, newline))
+        .map(|(idx, _)| idx + 1)
+}
+
+fn is_break(input: &str, idx: usize, newline: NewlinePolicy) -> bool {
+    match input.as_bytes()[idx] {
+        b'\n' => true,
+        b'\r' => newline == NewlinePolicy::Any && input.as_bytes().get(idx + 1) != Some(&b'\n'),
+        _ => false,
+    }
+}
+
+fn floor(input: &str, mut offset: usize) -> usize {
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
+
+fn column(line: &str, offset: usize, columns: Columns) -> usize {
+    let mut column = 0;
+
+    for (_, ch) in line.char_indices().take_while(|(idx, _)| *idx < offset) {
+        column = step(column, ch, columns);
+    }
+
+    column + 1
+}
+
+fn step(column: usize, ch: char, columns: Columns) -> usize {
+    match ch {
+        '\t' if columns.tab_width > 1 => (column / columns.tab_width + 1) * columns.tab_width,
+        _ if columns.utf16 => column + ch.len_utf16(),
+        _ => column + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_locate() {
+        let input = "ab\ncdé\n\nf";
+
+        assert_eq!(Position::locate(input, 0), (1, 1));
+        assert_eq!(Position::locate(input, 2), (1, 3));
+        assert_eq!(Position::locate(input, 3), (2, 1));
+        assert_eq!(Position::locate(input, 5), (2, 3));
+        assert_eq!(Position::locate(input, 7), (2, 4));
+        assert_eq!(Position::locate(input, 8), (3, 1));
+        assert_eq!(Position::locate(input, 9), (4, 1));
+        assert_eq!(Position::locate(input, 10), (4, 2));
+        assert_eq!(Position::locate(input, 100), (4, 2));
+        assert_eq!(Position::locate("", 0), (1, 1));
+    }
+
+    #[test]
+    fn test_shared_line_index() {
+        let source = "a\nb\nc";
+        let shared = |input| {
+            let (first, second) = (LineIndex::shared(source), LineIndex::shared(source));
+
+            Ok((Arc::ptr_eq(&first.lines, &second.lines), input))
+        };
+
+        assert_eq!(parse(source, shared), Ok((true, source)));
+        assert_eq!(parse(&source[2..], shared), Ok((false, "b\nc")));
+        assert_eq!(LineIndex::shared(source), LineIndex::new(source));
+    }
+
+    #[test]
+    fn test_line_index() {
+        let input = "ab\r\ncdé\n\nf";
+        let index = LineIndex::new(input);
+
+        for offset in 0..=input.len() + 1 {
+            assert_eq!(index.locate(offset), Position::locate(input, offset));
+        }
+
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line(0), None);
+        assert_eq!(index.line(1), Some("ab"));
+        assert_eq!(index.line(2), Some("cdé"));
+        assert_eq!(index.line(3), Some(""));
+        assert_eq!(index.line(4), Some("f"));
+        assert_eq!(index.line(5), None);
+
+        let input = "a\rb\r\nc";
+        let columns = Columns::new().newline(NewlinePolicy::Any);
+        let index = LineIndex::with_columns(input, columns);
+
+        for offset in 0..=input.len() {
+            assert_eq!(
+                index.locate(offset),
+                Position::locate_with(input, offset, columns)
+            );
+            assert_eq!(
+                Position::new_with(input, 0, columns).advance(input, offset, columns),
+                Position::new_with(input, offset, columns)
+            );
+        }
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line(2), Some("b"));
+    }
+
+    #[test]
+    fn test_position() {
+        let input = "ab\ncd";
+
+        assert_eq!(
+            Position::new(input, 4),
+            Position {
+                offset: 4,
+                line: 2,
+                column: 2,
+            }
+        );
+        assert_eq!(Position::new(input, 99).offset, 5);
+        assert_eq!(LineIndex::new(input).position(4), Position::new(input, 4));
+        assert_eq!(Position::new(input, 4).to_string(), "2:2");
+    }
+
+    #[test]
+    fn test_span() {
+        let input = "ab\ncd";
+        let span = Span::new(input, 1, 4);
+
+        assert_eq!(span.start, Position::new(input, 1));
+        assert_eq!(span.end, Position::new(input, 4));
+        assert_eq!(span.len(), 3);
+        assert!(!span.is_empty());
+        assert!(span.contains(1));
+        assert!(!span.contains(4));
+        assert_eq!(span.slice(input), "b\nc");
+        assert_eq!(span.to_string(), "1:2-2:2");
+        assert_eq!(LineIndex::new(input).span(1, 4), span);
+        assert!(Span::new(input, 3, 1).is_empty());
+    }
+
+    #[test]
+    fn test_columns() {
+        let input = "\tab\n  \tc\ta\u{1F600}b";
+        let tabs = Columns::new().tab_width(4);
+        let utf16 = Columns::new().utf16(true);
+
+        assert_eq!(Position::locate_with(input, 1, tabs), (1, 5));
+        assert_eq!(Position::locate_with(input, 2, tabs), (1, 6));
+        assert_eq!(Position::locate_with(input, 7, tabs), (2, 5));
+        assert_eq!(Position::locate_with(input, 9, tabs), (2, 9));
+        assert_eq!(Position::locate_with(input, 10, Columns::new()), (2, 7));
+        assert_eq!(Position::locate_with(input, 14, Columns::new()), (2, 8));
+        assert_eq!(Position::locate_with(input, 14, utf16), (2, 9));
+        assert_eq!(Position::new_with(input, 14, utf16).column, 9);
+        assert_eq!(Span::new_with(input, 7, 14, tabs).end.column, 11);
+
+        let index = LineIndex::with_columns(input, tabs.utf16(true));
+
+        for offset in 0..=input.len() {
+            assert_eq!(
+                index.locate(offset),
+                Position::locate_with(input, offset, tabs.utf16(true))
+            );
+        }
+    }
+}
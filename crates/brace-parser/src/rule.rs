@@ -0,0 +1,105 @@
+use std::sync::Mutex;
+
+use crate::combinator::context;
+use crate::parser::{Output, Parser};
+use crate::state;
+
+static RULES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+#[macro_export]
+macro_rules! rule {
+    ($($(#[$attr:meta])* $vis:vis fn $name:ident($input:ident) -> $out:ty $body:block)+) => {
+        $(
+            $(#[$attr])*
+            $vis fn $name($input: &str) -> $crate::parser::Output<'_, $out> {
+                fn body($input: &str) -> $crate::parser::Output<'_, $out> $body
+
+                $crate::rule::parse_rule(stringify!($name), body, $input)
+            }
+        )+
+    };
+}
+
+#[macro_export]
+macro_rules! register_rules {
+    ($($name:ident),* $(,)?) => {
+        $(
+            let _ = $name;
+
+            $crate::rule::register(stringify!($name));
+        )*
+    };
+}
+
+#[doc(hidden)]
+pub fn register(name: &'static str) {
+    let mut rules = RULES.lock().unwrap_or_else(|err| err.into_inner());
+
+    if !rules.contains(&name) {
+        rules.push(name);
+    }
+}
+
+#[doc(hidden)]
+pub fn parse_rule<'a, O>(
+    name: &'static str,
+    body: impl Parser<'a, O>,
+    input: &'a str,
+) -> Output<'a, O> {
+    let _depth = state::enter()?;
+
+    context(name, body).parse(input)
+}
+
+pub fn registered() -> Vec<&'static str> {
+    RULES.lock().unwrap_or_else(|err| err.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::parser::{parse, Parser};
+    use crate::sequence::{alphabetic, decimal, Sequence};
+
+    rule! {
+        fn assignment(input) -> (&str, char, &str) {
+            (name, '=', decimal).parse(input)
+        }
+
+        pub(crate) fn name(input) -> &str {
+            alphabetic(input)
+        }
+    }
+
+    #[test]
+    fn test_register_rules() {
+        register_rules!(assignment, name);
+
+        let rules = registered();
+
+        assert!(rules.contains(&"assignment"));
+        assert!(rules.contains(&"name"));
+
+        register_rules!(name);
+
+        assert_eq!(registered().len(), rules.len());
+    }
+
+    #[test]
+    fn test_rule() {
+        assert_eq!(parse("a=1", assignment), Ok((("a", '=', "1"), "")));
+        assert_eq!(
+            parse("a=x", assignment),
+            Err(Error::expect(Sequence::Decimal)
+                .but_found('x')
+                .with_context("assignment"))
+        );
+        assert_eq!(
+            parse("1=1", assignment),
+            Err(Error::expect(Sequence::Alphabetic)
+                .but_found('1')
+                .with_context("name"))
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use std::fmt;
 
+use crate::character::Character;
 use crate::error::{Error, Expect};
-use crate::parser::{take_while, Output, Parser};
+use crate::options::{NewlinePolicy, ParseOptions};
+use crate::parser::{take_while, take_while0, Output, Parser};
+use crate::state;
 
 pub fn any(input: &str) -> Output<&str> {
-    take_while(|_| true)
+    take_while(|_: char| true)
         .parse(input)
         .map_err(|err| err.but_expect(Sequence::Any))
 }
@@ -33,6 +36,12 @@ pub fn alphanumeric(input: &str) -> Output<&str> {
         .map_err(|err| err.but_expect(Sequence::Alphanumeric))
 }
 
+pub fn word(input: &str) -> Output<&str> {
+    take_while(crate::character::is_word)
+        .parse(input)
+        .map_err(|err| err.but_expect(Sequence::Word))
+}
+
 pub fn lowercase(input: &str) -> Output<&str> {
     take_while(crate::character::is_lowercase)
         .parse(input)
@@ -63,6 +72,117 @@ pub fn whitespace(input: &str) -> Output<&str> {
         .map_err(|err| err.but_expect(Sequence::Whitespace))
 }
 
+pub fn any_opt(input: &str) -> Output<&str> {
+    take_while0(|_: char| true).parse(input)
+}
+
+pub fn decimal_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_decimal).parse(input)
+}
+
+pub fn hexadecimal_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_hexadecimal).parse(input)
+}
+
+pub fn alphabetic_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_alphabetic).parse(input)
+}
+
+pub fn alphanumeric_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_alphanumeric).parse(input)
+}
+
+pub fn word_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_word).parse(input)
+}
+
+pub fn lowercase_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_lowercase).parse(input)
+}
+
+pub fn uppercase_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_uppercase).parse(input)
+}
+
+pub fn indent_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_indent).parse(input)
+}
+
+pub fn linebreak_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_linebreak).parse(input)
+}
+
+pub fn whitespace_opt(input: &str) -> Output<&str> {
+    take_while0(crate::character::is_whitespace).parse(input)
+}
+
+pub fn newline(input: &str) -> Output<&str> {
+    match ParseOptions::current().get_newline() {
+        NewlinePolicy::Lf => Parser::parse(&"\n", input),
+        NewlinePolicy::Any => match input.as_bytes() {
+            [b'\r', b'\n', ..] => Ok(input.split_at(2)),
+            [b'\r', ..] | [b'\n', ..] => Ok(input.split_at(1)),
+            _ => Parser::parse(&"\n", input),
+        },
+    }
+}
+
+pub fn ws(input: &str) -> Output<&str> {
+    whitespace_opt(input)
+}
+
+pub fn at_least<'a, T>(min: usize, class: T) -> impl Parser<'a, &'a str>
+where
+    T: Into<Character>,
+{
+    let class = class.into();
+
+    move |input: &'a str| {
+        let mut rem = input;
+        let mut count = 0;
+
+        loop {
+            match class.parse(rem) {
+                Ok((_, next)) => {
+                    count += 1;
+                    rem = next;
+                }
+                Err(Error::Pass(_)) => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        if count < min {
+            let err = Error::expect(Sequence::AtLeast(min, class.clone()));
+
+            match rem.chars().next() {
+                Some(ch) => Err(err.but_found(ch)),
+                None => Err(err.but_found_end()),
+            }
+        } else {
+            Ok(input.split_at(input.len() - rem.len()))
+        }
+    }
+}
+
+pub fn is_a<'a, T>(set: T) -> impl Parser<'a, &'a str>
+where
+    T: Into<String>,
+{
+    let sequence = Sequence::IsA(set.into());
+
+    move |input| sequence.parse(input)
+}
+
+pub fn is_not<'a, T>(set: T) -> impl Parser<'a, &'a str>
+where
+    T: Into<String>,
+{
+    let sequence = Sequence::IsNot(set.into());
+
+    move |input| sequence.parse(input)
+}
+
 pub fn end(input: &str) -> Output<&str> {
     match input.chars().next() {
         Some(ch) => Err(Error::expect(Expect::End).but_found(ch)),
@@ -70,6 +190,17 @@ pub fn end(input: &str) -> Output<&str> {
     }
 }
 
+pub fn at_end(input: &str) -> Output<()> {
+    end(input).map(|(_, rem)| ((), rem))
+}
+
+pub fn not_at_end(input: &str) -> Output<()> {
+    match input.is_empty() {
+        true => Err(Error::expect(Sequence::Any).but_found_end()),
+        false => Ok(((), input)),
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Sequence {
     Any,
@@ -77,12 +208,17 @@ pub enum Sequence {
     Hexadecimal,
     Alphabetic,
     Alphanumeric,
+    Word,
     Lowercase,
     Uppercase,
     Indent,
     Linebreak,
     Whitespace,
+    AtLeast(usize, Character),
+    IsA(String),
+    IsNot(String),
     Custom(String),
+    CustomInsensitive(String),
 }
 
 impl Sequence {
@@ -92,6 +228,20 @@ impl Sequence {
     {
         Self::Custom(sequence.into())
     }
+
+    pub fn custom_insensitive<T>(sequence: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::CustomInsensitive(sequence.into())
+    }
+
+    pub fn at_least<T>(min: usize, class: T) -> Self
+    where
+        T: Into<Character>,
+    {
+        Self::AtLeast(min, class.into())
+    }
 }
 
 impl<'a> Parser<'a, &'a str> for Sequence {
@@ -102,12 +252,21 @@ impl<'a> Parser<'a, &'a str> for Sequence {
             Self::Hexadecimal => hexadecimal.parse(input),
             Self::Alphabetic => alphabetic.parse(input),
             Self::Alphanumeric => alphanumeric.parse(input),
+            Self::Word => word.parse(input),
             Self::Lowercase => lowercase.parse(input),
             Self::Uppercase => uppercase.parse(input),
             Self::Indent => indent.parse(input),
             Self::Linebreak => linebreak.parse(input),
             Self::Whitespace => whitespace.parse(input),
+            Self::AtLeast(min, class) => at_least(*min, class.clone()).parse(input),
+            Self::IsA(set) => take_while(|ch: char| set.contains(ch))
+                .parse(input)
+                .map_err(|err| err.but_expect(self.clone())),
+            Self::IsNot(set) => take_while(|ch: char| !set.contains(ch))
+                .parse(input)
+                .map_err(|err| err.but_expect(self.clone())),
             Self::Custom(string) => string.parse(input),
+            Self::CustomInsensitive(string) => state::with_fold_case(true, || string.parse(input)),
         }
     }
 }
@@ -120,12 +279,17 @@ impl fmt::Display for Sequence {
             Self::Hexadecimal => write!(f, "hexadecimal"),
             Self::Alphabetic => write!(f, "alphabetic"),
             Self::Alphanumeric => write!(f, "alphanumeric"),
+            Self::Word => write!(f, "word"),
             Self::Lowercase => write!(f, "lowercase"),
             Self::Uppercase => write!(f, "uppercase"),
             Self::Indent => write!(f, "indent"),
             Self::Linebreak => write!(f, "linebreak"),
             Self::Whitespace => write!(f, "whitespace"),
+            Self::AtLeast(min, class) => write!(f, "at least {} {}", min, class),
+            Self::IsA(set) => write!(f, "one of {:?}", set),
+            Self::IsNot(set) => write!(f, "none of {:?}", set),
             Self::Custom(string) => write!(f, "\"{}\"", string),
+            Self::CustomInsensitive(string) => write!(f, "\"{}\" (any case)", string),
         }
     }
 }
@@ -818,6 +982,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_word() {
+        for ch in "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_".chars() {
+            assert_eq!(parse(&ch.to_string(), word), Ok((&*ch.to_string(), "")));
+            assert_eq!(
+                parse(&(ch.to_string() + "$"), word),
+                Ok((&*ch.to_string(), "$"))
+            );
+        }
+
+        for ch in "$- \nß".chars() {
+            assert_eq!(
+                parse(&ch.to_string(), word),
+                Err(Error::expect(Sequence::Word).but_found(ch))
+            );
+        }
+
+        assert_eq!(
+            parse("", word),
+            Err(Error::expect(Sequence::Word).but_found_end())
+        );
+        assert_eq!(
+            parse("snake_case_1 rest", word),
+            Ok(("snake_case_1", " rest"))
+        );
+    }
+
+    #[test]
+    fn test_word_variant() {
+        for ch in "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_".chars() {
+            assert_eq!(
+                parse(&ch.to_string(), Sequence::Word),
+                Ok((&*ch.to_string(), ""))
+            );
+            assert_eq!(
+                parse(&(ch.to_string() + "$"), Sequence::Word),
+                Ok((&*ch.to_string(), "$"))
+            );
+        }
+
+        for ch in "$- \nß".chars() {
+            assert_eq!(
+                parse(&ch.to_string(), Sequence::Word),
+                Err(Error::expect(Sequence::Word).but_found(ch))
+            );
+        }
+
+        assert_eq!(
+            parse("", Sequence::Word),
+            Err(Error::expect(Sequence::Word).but_found_end())
+        );
+        assert_eq!(
+            parse("snake_case_1 rest", Sequence::Word),
+            Ok(("snake_case_1", " rest"))
+        );
+    }
+
     #[test]
     fn test_custom_variant() {
         assert_eq!(
@@ -840,6 +1061,164 @@ mod tests {
         assert_eq!(parse("hello", Sequence::custom("")), Ok(("", "hello")));
     }
 
+    #[test]
+    fn test_custom_insensitive_variant() {
+        let select = || Sequence::custom_insensitive("select");
+
+        assert_eq!(parse("SELECT *", select()), Ok(("SELECT", " *")));
+        assert_eq!(parse("Select", select()), Ok(("Select", "")));
+        assert_eq!(
+            parse("SELEKT", select()),
+            Err(Error::expect('c').but_found('K'))
+        );
+        assert_eq!(
+            parse("sel", select()),
+            Err(Error::expect('e').but_found_end())
+        );
+        assert_eq!(
+            Error::expect(select()).but_found('x').to_string(),
+            "Error:\nExpected sequence: \"select\" (any case)\nFound character: 'x'"
+        );
+        assert_eq!(
+            parse("ss", Sequence::custom("SS")),
+            Err(Error::expect('S').but_found('s'))
+        );
+    }
+
+    #[test]
+    fn test_opt() {
+        type Opt = fn(&str) -> Output<&str>;
+
+        let cases: Vec<(Opt, &str, &str)> = vec![
+            (any_opt, "$a 1", ""),
+            (decimal_opt, "0123456789", "a"),
+            (hexadecimal_opt, "0123456789abcdefABCDEF", "g"),
+            (alphabetic_opt, "abcXYZ", "0"),
+            (alphanumeric_opt, "abcXYZ019", "_"),
+            (word_opt, "abcXYZ019_", "-"),
+            (lowercase_opt, "abcxyz", "A"),
+            (uppercase_opt, "ABCXYZ", "a"),
+            (indent_opt, " \t", "\n"),
+            (linebreak_opt, "\n\r\u{000C}", " "),
+            (whitespace_opt, " \t\n\r\u{000C}", "$"),
+            (ws, " \t\n\r\u{000C}", "$"),
+        ];
+
+        for (parser, valid, invalid) in cases {
+            assert_eq!(parse("", parser), Ok(("", "")));
+            assert_eq!(parse(valid, parser), Ok((valid, "")));
+            assert_eq!(
+                parse(&(valid.to_owned() + invalid), parser),
+                Ok((valid, invalid))
+            );
+
+            if !invalid.is_empty() {
+                assert_eq!(parse(invalid, parser), Ok(("", invalid)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_at_least() {
+        assert_eq!(parse("", at_least(0, Character::Decimal)), Ok(("", "")));
+        assert_eq!(parse("$", at_least(0, Character::Decimal)), Ok(("", "$")));
+        assert_eq!(parse("1$", at_least(1, Character::Decimal)), Ok(("1", "$")));
+        assert_eq!(
+            parse("ff0a$", at_least(2, Character::Hexadecimal)),
+            Ok(("ff0a", "$"))
+        );
+        assert_eq!(
+            parse("f$", at_least(2, Character::Hexadecimal)),
+            Err(Error::expect(Sequence::at_least(2, Character::Hexadecimal)).but_found('$'))
+        );
+        assert_eq!(
+            parse("f", at_least(2, Character::Hexadecimal)),
+            Err(Error::expect(Sequence::at_least(2, Character::Hexadecimal)).but_found_end())
+        );
+        assert_eq!(parse("aaab", at_least(3, 'a')), Ok(("aaa", "b")));
+        assert_eq!(
+            parse("aab", at_least(3, 'a')),
+            Err(Error::expect(Sequence::at_least(3, 'a')).but_found('b'))
+        );
+        assert_eq!(
+            Error::expect(Sequence::at_least(2, Character::Hexadecimal))
+                .but_found('$')
+                .to_string(),
+            "Error:\nExpected sequence: at least 2 hexadecimal\nFound character: '$'"
+        );
+    }
+
+    #[test]
+    fn test_at_least_variant() {
+        assert_eq!(
+            parse("ff0a$", Sequence::at_least(2, Character::Hexadecimal)),
+            Ok(("ff0a", "$"))
+        );
+        assert_eq!(
+            parse("f$", Sequence::at_least(2, Character::Hexadecimal)),
+            Err(Error::expect(Sequence::at_least(2, Character::Hexadecimal)).but_found('$'))
+        );
+    }
+
+    #[test]
+    fn test_is_a() {
+        assert_eq!(
+            parse("", is_a("0123456789abcdef")),
+            Err(Error::expect(Sequence::IsA("0123456789abcdef".to_owned())).but_found_end())
+        );
+        assert_eq!(
+            parse("g", is_a("0123456789abcdef")),
+            Err(Error::expect(Sequence::IsA("0123456789abcdef".to_owned())).but_found('g'))
+        );
+        assert_eq!(parse("dead", is_a("0123456789abcdef")), Ok(("dead", "")));
+        assert_eq!(
+            parse("c0ffee!", is_a("0123456789abcdef")),
+            Ok(("c0ffee", "!"))
+        );
+        assert_eq!(parse("ßℝ💣$", is_a("💣ℝß")), Ok(("ßℝ💣", "$")));
+        assert_eq!(
+            Error::expect(Sequence::IsA("abc".to_owned()))
+                .but_found('d')
+                .to_string(),
+            "Error:\nExpected sequence: one of \"abc\"\nFound character: 'd'"
+        );
+    }
+
+    #[test]
+    fn test_is_not() {
+        assert_eq!(
+            parse("", is_not(" \t\n")),
+            Err(Error::expect(Sequence::IsNot(" \t\n".to_owned())).but_found_end())
+        );
+        assert_eq!(
+            parse("\thello", is_not(" \t\n")),
+            Err(Error::expect(Sequence::IsNot(" \t\n".to_owned())).but_found('\t'))
+        );
+        assert_eq!(parse("hello", is_not(" \t\n")), Ok(("hello", "")));
+        assert_eq!(
+            parse("hello world", is_not(" \t\n")),
+            Ok(("hello", " world"))
+        );
+        assert_eq!(
+            Error::expect(Sequence::IsNot(" \t".to_owned()))
+                .but_found(' ')
+                .to_string(),
+            "Error:\nExpected sequence: none of \" \\t\"\nFound character: ' '"
+        );
+    }
+
+    #[test]
+    fn test_is_a_variant() {
+        assert_eq!(
+            parse("c0ffee!", Sequence::IsA("0123456789abcdef".to_owned())),
+            Ok(("c0ffee", "!"))
+        );
+        assert_eq!(
+            parse("hello world", Sequence::IsNot(" ".to_owned())),
+            Ok(("hello", " world"))
+        );
+    }
+
     #[test]
     fn test_end() {
         assert_eq!(parse("", end), Ok(("", "")));
@@ -853,4 +1232,34 @@ mod tests {
             Err(Error::expect(Expect::End).but_found(' '))
         );
     }
+
+    #[test]
+    fn test_at_end() {
+        assert_eq!(parse("", at_end), Ok(((), "")));
+        assert_eq!(
+            parse("x", at_end),
+            Err(Error::expect(Expect::End).but_found('x'))
+        );
+        assert_eq!(parse("x", not_at_end), Ok(((), "x")));
+        assert_eq!(
+            parse("", not_at_end),
+            Err(Error::expect(Sequence::Any).but_found_end())
+        );
+        assert_eq!(
+            parse("a,b", (alphabetic, ',', not_at_end, alphabetic, at_end)),
+            Ok((("a", ',', (), "b", ()), ""))
+        );
+        assert_eq!(
+            parse("a,", (alphabetic, ',', not_at_end)),
+            Err(Error::expect(Sequence::Any).but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        assert_eq!(parse("abcŉ", alphabetic), Ok(("abc", "ŉ")));
+        assert_eq!(parse("01İ", decimal), Ok(("01", "İ")));
+        assert_eq!(parse(" \tĠ", whitespace), Ok((" \t", "Ġ")));
+        assert_eq!(parse("\nĊ", linebreak), Ok(("\n", "Ċ")));
+    }
 }
@@ -0,0 +1,186 @@
+use crate::character::is_whitespace;
+use crate::combinator::branch::optional;
+use crate::combinator::map;
+use crate::combinator::series::{delimited, list};
+use crate::error::Error;
+use crate::parser::{Output, Parser};
+use crate::sequence::whitespace;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Word {
+    pub text: String,
+    pub quoted: bool,
+}
+
+pub fn words(input: &str) -> Output<Vec<String>> {
+    delimited(
+        optional(whitespace),
+        map(optional(list(word, whitespace)), Option::unwrap_or_default),
+        optional(whitespace),
+    )
+    .parse(input)
+}
+
+pub fn word(input: &str) -> Output<String> {
+    map(token, |word| word.text).parse(input)
+}
+
+pub fn tokens(input: &str) -> Output<'_, Vec<Word>> {
+    delimited(
+        optional(whitespace),
+        map(optional(list(token, whitespace)), Option::unwrap_or_default),
+        optional(whitespace),
+    )
+    .parse(input)
+}
+
+pub fn token(input: &str) -> Output<'_, Word> {
+    let mut iter = input.char_indices().peekable();
+    let mut out = String::new();
+    let mut quoted = false;
+
+    match iter.peek() {
+        Some((_, ch)) if is_whitespace(*ch) => return Err(Error::found(*ch)),
+        Some(_) => {}
+        None => return Err(Error::found_end()),
+    }
+
+    while let Some((idx, ch)) = iter.next() {
+        quoted |= matches!(ch, '\'' | '"') || ch == '\\' && iter.peek() != Some(&(idx + 1, '\n'));
+
+        match ch {
+            '\'' => loop {
+                match iter.next() {
+                    Some((_, '\'')) => break,
+                    Some((_, ch)) => out.push(ch),
+                    None => return Err(Error::expect('\'').but_found_end().into_fail()),
+                }
+            },
+            '"' => loop {
+                match iter.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match iter.next() {
+                        Some((_, '\n')) => {}
+                        Some((_, ch)) if matches!(ch, '"' | '\\' | '$' | '`') => out.push(ch),
+                        Some((_, ch)) => {
+                            out.push('\\');
+                            out.push(ch);
+                        }
+                        None => return Err(Error::expect('"').but_found_end().into_fail()),
+                    },
+                    Some((_, ch)) => out.push(ch),
+                    None => return Err(Error::expect('"').but_found_end().into_fail()),
+                }
+            },
+            '\\' => match iter.next() {
+                Some((_, '\n')) => {}
+                Some((_, ch)) => out.push(ch),
+                None => return Err(Error::found('\\').into_fail()),
+            },
+            ch if is_whitespace(ch) => return Ok((Word { text: out, quoted }, &input[idx..])),
+            ch => out.push(ch),
+        }
+    }
+
+    Ok((Word { text: out, quoted }, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_word() {
+        assert_eq!(parse("", word), Err(Error::found_end()));
+        assert_eq!(parse(" a", word), Err(Error::found(' ')));
+        assert_eq!(parse("hello", word), Ok(("hello".to_owned(), "")));
+        assert_eq!(
+            parse("hello world", word),
+            Ok(("hello".to_owned(), " world"))
+        );
+        assert_eq!(
+            parse("'hello world' rest", word),
+            Ok(("hello world".to_owned(), " rest"))
+        );
+        assert_eq!(parse(r#"'a\"b'"#, word), Ok((r#"a\"b"#.to_owned(), "")));
+        assert_eq!(
+            parse(r#""a \"b\" \n""#, word),
+            Ok((r#"a "b" \n"#.to_owned(), ""))
+        );
+        assert_eq!(
+            parse(r#"hello\ world"#, word),
+            Ok(("hello world".to_owned(), ""))
+        );
+        assert_eq!(
+            parse(r#"pre'quoted'"double"post"#, word),
+            Ok(("prequoteddoublepost".to_owned(), ""))
+        );
+        assert_eq!(parse("''", word), Ok((String::new(), "")));
+        assert_eq!(
+            parse("'hello", word),
+            Err(Error::expect('\'').but_found_end().into_fail())
+        );
+        assert_eq!(
+            parse("\"hello", word),
+            Err(Error::expect('"').but_found_end().into_fail())
+        );
+        assert_eq!(parse("hello\\", word), Err(Error::found('\\').into_fail()));
+    }
+
+    #[test]
+    fn test_token() {
+        let token = |text: &str, quoted| Word {
+            text: text.to_owned(),
+            quoted,
+        };
+
+        assert_eq!(
+            parse("-foo x", super::token),
+            Ok((token("-foo", false), " x"))
+        );
+        assert_eq!(
+            parse("\"-foo\"", super::token),
+            Ok((token("-foo", true), ""))
+        );
+        assert_eq!(parse("'--'", super::token), Ok((token("--", true), "")));
+        assert_eq!(parse("-'f'oo", super::token), Ok((token("-foo", true), "")));
+        assert_eq!(parse("\\-f", super::token), Ok((token("-f", true), "")));
+        assert_eq!(parse("-\\\nf", super::token), Ok((token("-f", false), "")));
+        assert_eq!(
+            parse(" a 'b' ", tokens),
+            Ok((vec![token("a", false), token("b", true)], ""))
+        );
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(parse("", words), Ok((vec![], "")));
+        assert_eq!(parse("   ", words), Ok((vec![], "")));
+        assert_eq!(
+            parse("  ls -la  /tmp ", words),
+            Ok((
+                vec!["ls".to_owned(), "-la".to_owned(), "/tmp".to_owned()],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse(r#"echo "hello world" 'it''s' a\ b "" x"#, words),
+            Ok((
+                vec![
+                    "echo".to_owned(),
+                    "hello world".to_owned(),
+                    "its".to_owned(),
+                    "a b".to_owned(),
+                    String::new(),
+                    "x".to_owned(),
+                ],
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("echo 'unterminated", words),
+            Err(Error::expect('\'').but_found_end().into_fail())
+        );
+    }
+}
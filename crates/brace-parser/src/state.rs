@@ -0,0 +1,566 @@
+use std::cell::{Cell, RefCell};
+use std::mem;
+use std::panic::Location;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::limit::Limit;
+use crate::options::ParseOptions;
+
+thread_local! {
+    static FLAGS: Cell<Flags> = const { Cell::new(Flags(0)) };
+    static BUDGET: Cell<Option<(usize, usize)>> = const { Cell::new(None) };
+    static DEADLINE: Cell<Option<(Instant, Duration)>> = const { Cell::new(None) };
+    static TICKS: Cell<usize> = const { Cell::new(0) };
+    static PROGRESS: RefCell<Option<Progress>> = const { RefCell::new(None) };
+    static RECOVERED: RefCell<Option<Vec<(Error, usize)>>> = const { RefCell::new(None) };
+    static OPTIONS: Cell<Option<ParseOptions>> = const { Cell::new(None) };
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    static AMBIGUITIES: RefCell<Option<Vec<Ambiguous>>> = const { RefCell::new(None) };
+    static BACKTRACKS: RefCell<Option<Vec<Backtrack>>> = const { RefCell::new(None) };
+    static REACH: Cell<Option<usize>> = const { Cell::new(None) };
+    static SOURCE: RefCell<Option<Source>> = const { RefCell::new(None) };
+}
+
+pub(crate) type Ambiguous = (usize, Vec<(usize, usize)>);
+pub(crate) type Backtrack = (&'static Location<'static>, usize, Vec<[usize; 3]>);
+
+struct Source {
+    key: (usize, usize),
+    lines: Option<Arc<[usize]>>,
+}
+
+fn source_key(input: &str) -> (usize, usize) {
+    (input.as_ptr() as usize, input.len())
+}
+
+const DEADLINE_INTERVAL: usize = 256;
+const PROGRESS_INTERVAL: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Flags(u16);
+
+impl Flags {
+    const FOLD_CASE: Self = Self(1);
+    const NORMALIZE: Self = Self(1 << 1);
+    const BUDGET: Self = Self(1 << 2);
+    const DEADLINE: Self = Self(1 << 3);
+    const PROGRESS: Self = Self(1 << 4);
+    const RECOVERY: Self = Self(1 << 5);
+    const DEPTH: Self = Self(1 << 6);
+    const REACH: Self = Self(1 << 7);
+    const AMBIGUITY: Self = Self(1 << 8);
+    const BACKTRACKS: Self = Self(1 << 9);
+    const BRANCH: Self = Self(Self::AMBIGUITY.0 | Self::BACKTRACKS.0);
+    const HOOKS: Self = Self(
+        Self::BUDGET.0 | Self::DEADLINE.0 | Self::PROGRESS.0 | Self::RECOVERY.0 | Self::REACH.0,
+    );
+
+    fn contains(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub(crate) fn fold_case(self) -> bool {
+        self.contains(Self::FOLD_CASE)
+    }
+
+    #[cfg(feature = "unicode")]
+    pub(crate) fn normalize(self) -> bool {
+        self.contains(Self::NORMALIZE)
+    }
+
+    pub(crate) fn hooks(self) -> bool {
+        self.contains(Self::HOOKS)
+    }
+
+    pub(crate) fn branch_hooks(self) -> bool {
+        self.contains(Self::BRANCH)
+    }
+
+    pub(crate) fn detecting_ambiguity(self) -> bool {
+        self.contains(Self::AMBIGUITY)
+    }
+}
+
+pub(crate) fn flags() -> Flags {
+    FLAGS.with(Cell::get)
+}
+
+fn set_flag(flag: Flags, on: bool) -> RestoreFlags {
+    FLAGS.with(|cell| {
+        let outer = cell.get();
+
+        cell.set(match on {
+            true => Flags(outer.0 | flag.0),
+            false => Flags(outer.0 & !flag.0),
+        });
+
+        RestoreFlags(outer)
+    })
+}
+
+struct Progress {
+    start: usize,
+    total: usize,
+    reported: Option<usize>,
+    ticks: usize,
+    callback: *mut (dyn FnMut(usize, usize) + 'static),
+}
+
+pub(crate) fn fold_case() -> bool {
+    flags().fold_case()
+}
+
+pub(crate) fn with_fold_case<T>(fold: bool, f: impl FnOnce() -> T) -> T {
+    let _restore = set_flag(Flags::FOLD_CASE, fold);
+
+    f()
+}
+
+#[cfg(feature = "unicode")]
+pub(crate) fn with_normalize<T>(normalize: bool, f: impl FnOnce() -> T) -> T {
+    let _restore = set_flag(Flags::NORMALIZE, normalize);
+
+    f()
+}
+
+pub(crate) fn tick(input: &str) -> Result<(), Error> {
+    let flags = flags();
+
+    if flags.contains(Flags::REACH) {
+        REACH.with(|cell| {
+            if let Some(reach) = cell.get() {
+                cell.set(Some(reach.max(input.as_ptr() as usize)));
+            }
+        });
+    }
+
+    if flags.contains(Flags::PROGRESS) {
+        PROGRESS.with(|cell| {
+            if let Ok(mut progress) = cell.try_borrow_mut() {
+                if let Some(progress) = progress.as_mut() {
+                    progress.ticks += 1;
+
+                    if progress.ticks & (PROGRESS_INTERVAL - 1) == 0 {
+                        progress.report(input);
+                    }
+                }
+            }
+        });
+    }
+
+    if flags.contains(Flags::DEADLINE) {
+        if let Some((deadline, duration)) = DEADLINE.with(Cell::get) {
+            let ticks = TICKS.with(|cell| cell.replace(cell.get().wrapping_add(1)));
+
+            if ticks & (DEADLINE_INTERVAL - 1) == 0 && Instant::now() >= deadline {
+                return Err(Error::exceeded(Limit::Deadline(duration)));
+            }
+        }
+    }
+
+    if !flags.contains(Flags::BUDGET) {
+        return Ok(());
+    }
+
+    BUDGET.with(|cell| match cell.get() {
+        Some((0, steps)) => Err(Error::exceeded(Limit::Budget(steps))),
+        Some((remaining, steps)) => {
+            cell.set(Some((remaining - 1, steps)));
+            Ok(())
+        }
+        None => Ok(()),
+    })
+}
+
+pub(crate) fn with_budget<T>(steps: usize, f: impl FnOnce() -> T) -> T {
+    let outer = BUDGET.with(Cell::get);
+    let start = match outer {
+        Some((remaining, outer_steps)) if remaining < steps => (remaining, outer_steps),
+        _ => (steps, steps),
+    };
+    let _restore = RestoreBudget(outer, start.0);
+    let _flags = set_flag(Flags::BUDGET, true);
+
+    BUDGET.with(|cell| cell.set(Some(start)));
+
+    f()
+}
+
+pub(crate) fn with_deadline<T>(duration: Duration, f: impl FnOnce() -> T) -> T {
+    let outer = DEADLINE.with(Cell::get);
+    let deadline = Instant::now().checked_add(duration);
+    let inner = match (outer, deadline) {
+        (Some(outer), Some(deadline)) if outer.0 <= deadline => Some(outer),
+        (Some(outer), None) => Some(outer),
+        (_, Some(deadline)) => Some((deadline, duration)),
+        (None, None) => None,
+    };
+    let _restore = RestoreDeadline(DEADLINE.with(|cell| cell.replace(inner)));
+    let _flags = set_flag(Flags::DEADLINE, inner.is_some());
+
+    TICKS.with(|cell| cell.set(0));
+
+    f()
+}
+
+pub(crate) fn with_progress<'a, T>(
+    input: &'a str,
+    mut callback: impl FnMut(usize, usize),
+    f: impl FnOnce() -> Result<(T, &'a str), Error>,
+) -> Result<(T, &'a str), Error> {
+    let callback: &mut dyn FnMut(usize, usize) = &mut callback;
+    // The guard below clears the pointer before `callback` goes out of scope.
+    let callback = unsafe {
+        mem::transmute::<
+            *mut (dyn FnMut(usize, usize) + '_),
+            *mut (dyn FnMut(usize, usize) + 'static),
+        >(callback)
+    };
+    let progress = Progress {
+        start: input.as_ptr() as usize,
+        total: input.len(),
+        reported: None,
+        ticks: 0,
+        callback,
+    };
+    let _restore = RestoreProgress(PROGRESS.with(|cell| cell.replace(Some(progress))));
+    let _flags = set_flag(Flags::PROGRESS, true);
+    let output = f();
+
+    if let Ok((_, rem)) = &output {
+        PROGRESS.with(|cell| {
+            if let Some(progress) = cell.borrow_mut().as_mut() {
+                progress.report(rem);
+            }
+        });
+    }
+
+    output
+}
+
+impl Progress {
+    fn report(&mut self, input: &str) {
+        let pos = input.as_ptr() as usize;
+
+        if pos < self.start || pos > self.start + self.total {
+            return;
+        }
+
+        let consumed = pos - self.start;
+
+        if self.reported < Some(consumed) {
+            self.reported = Some(consumed);
+            unsafe { (*self.callback)(consumed, self.total) };
+        }
+    }
+}
+
+pub(crate) fn recover(err: Error, input: &str) -> Result<(), Error> {
+    RECOVERED.with(|cell| match cell.borrow_mut().as_mut() {
+        Some(errors) => {
+            errors.push((err, input.as_ptr() as usize));
+            Ok(())
+        }
+        None => Err(err),
+    })
+}
+
+pub(crate) fn rewinding<'a, O>(
+    f: impl FnOnce() -> Result<(O, &'a str), Error>,
+) -> Result<(O, &'a str), Error> {
+    if !flags().contains(Flags::RECOVERY) {
+        return f();
+    }
+
+    let mark = RECOVERED.with(|cell| cell.borrow().as_ref().map(Vec::len));
+    let out = f();
+
+    if let (Some(mark), Err(Error::Pass(_))) = (mark, &out) {
+        RECOVERED.with(|cell| {
+            if let Some(errors) = cell.borrow_mut().as_mut() {
+                errors.truncate(mark);
+            }
+        });
+    }
+
+    out
+}
+
+pub(crate) fn with_recovery<T>(f: impl FnOnce() -> T) -> (T, Vec<(Error, usize)>) {
+    let _restore = RestoreRecovered(RECOVERED.with(|cell| cell.replace(Some(Vec::new()))));
+    let _flags = set_flag(Flags::RECOVERY, true);
+    let out = f();
+    let errors = RECOVERED.with(|cell| cell.borrow_mut().take());
+
+    (out, errors.unwrap_or_default())
+}
+
+pub(crate) fn report_ambiguity(input: &str, matches: Vec<(usize, usize)>) {
+    AMBIGUITIES.with(|cell| {
+        if let Some(found) = cell.borrow_mut().as_mut() {
+            let ambiguous = (input.as_ptr() as usize, matches);
+
+            if !found.contains(&ambiguous) {
+                found.push(ambiguous);
+            }
+        }
+    })
+}
+
+pub(crate) fn with_ambiguity_detection<T>(f: impl FnOnce() -> T) -> (T, Vec<Ambiguous>) {
+    let _restore = RestoreAmbiguities(AMBIGUITIES.with(|cell| cell.replace(Some(Vec::new()))));
+    let _flags = set_flag(Flags::AMBIGUITY, true);
+    let out = f();
+    let found = AMBIGUITIES.with(|cell| cell.borrow_mut().take());
+
+    (out, found.unwrap_or_default())
+}
+
+pub(crate) fn without_ambiguity_detection<T>(f: impl FnOnce() -> T) -> T {
+    let _restore = set_flag(Flags::AMBIGUITY, false);
+
+    f()
+}
+
+pub(crate) fn start_arm(input: &str) -> Option<usize> {
+    REACH.with(|cell| cell.replace(Some(input.as_ptr() as usize)))
+}
+
+pub(crate) fn finish_arm(
+    site: &'static Location<'static>,
+    input: &str,
+    (arm, arms): (usize, usize),
+    outer: Option<usize>,
+    discarded: bool,
+) {
+    let start = input.as_ptr() as usize;
+    let reach = REACH.with(|cell| {
+        let reach = cell.get().unwrap_or(start);
+
+        cell.set(outer.map(|outer| outer.max(reach)));
+        reach
+    });
+
+    BACKTRACKS.with(|cell| {
+        if let Some(found) = cell.borrow_mut().as_mut() {
+            let idx = match found.iter().position(|(key, _, _)| *key == site) {
+                Some(idx) => idx,
+                None => {
+                    found.push((site, start, vec![[0; 3]; arms]));
+                    found.len() - 1
+                }
+            };
+            let sites = &mut found[idx].2;
+
+            if sites.len() < arms {
+                sites.resize(arms, [0; 3]);
+            }
+
+            let stats = &mut sites[arm];
+
+            stats[0] += 1;
+
+            if discarded {
+                stats[1] += 1;
+                stats[2] += reach - start;
+            }
+        }
+    })
+}
+
+pub(crate) fn with_reach<T>(input: &str, f: impl FnOnce() -> T) -> (T, usize) {
+    let start = input.as_ptr() as usize;
+    let _restore = RestoreReach(REACH.with(|cell| cell.replace(Some(start))));
+    let _flags = set_flag(Flags::REACH, true);
+    let out = f();
+    let reach = REACH.with(Cell::get).unwrap_or(start);
+
+    (out, reach - start)
+}
+
+pub(crate) fn with_backtrack_stats<T>(f: impl FnOnce() -> T) -> (T, Vec<Backtrack>) {
+    let _restore = RestoreBacktracks(
+        BACKTRACKS.with(|cell| cell.replace(Some(Vec::new()))),
+        REACH.with(|cell| cell.replace(Some(0))),
+    );
+    let _flags = set_flag(Flags(Flags::REACH.0 | Flags::BACKTRACKS.0), true);
+    let out = f();
+    let found = BACKTRACKS.with(|cell| cell.borrow_mut().take());
+
+    (out, found.unwrap_or_default())
+}
+
+pub(crate) fn with_source<T>(input: &str, f: impl FnOnce() -> T) -> T {
+    let _restore = RestoreSource(SOURCE.with(|cell| {
+        cell.replace(Some(Source {
+            key: source_key(input),
+            lines: None,
+        }))
+    }));
+
+    f()
+}
+
+pub(crate) fn line_starts(
+    input: &str,
+    compute: impl FnOnce() -> Arc<[usize]>,
+) -> Option<Arc<[usize]>> {
+    SOURCE.with(|cell| match cell.borrow_mut().as_mut() {
+        Some(source) if source.key == source_key(input) => {
+            Some(source.lines.get_or_insert_with(compute).clone())
+        }
+        _ => None,
+    })
+}
+
+pub(crate) fn options() -> ParseOptions {
+    OPTIONS.with(Cell::get).unwrap_or_default()
+}
+
+pub(crate) fn with_options<T>(options: ParseOptions, f: impl FnOnce() -> T) -> T {
+    let _restore = RestoreOptions(
+        OPTIONS.with(|cell| cell.replace(Some(options))),
+        DEPTH.with(|cell| cell.replace(0)),
+    );
+    let _flags = set_flag(Flags::DEPTH, options.get_max_depth().is_some());
+
+    f()
+}
+
+pub(crate) fn enter() -> Result<Option<Depth>, Error> {
+    if !flags().contains(Flags::DEPTH) {
+        return Ok(None);
+    }
+
+    let max = match OPTIONS
+        .with(Cell::get)
+        .and_then(|options| options.get_max_depth())
+    {
+        Some(max) => max,
+        None => return Ok(None),
+    };
+
+    DEPTH.with(|cell| match cell.get() {
+        depth if depth >= max => Err(Error::exceeded(Limit::Depth(max))),
+        depth => {
+            cell.set(depth + 1);
+            Ok(Some(Depth))
+        }
+    })
+}
+
+pub(crate) struct Depth;
+
+impl Drop for Depth {
+    fn drop(&mut self) {
+        DEPTH.with(|cell| cell.set(cell.get().saturating_sub(1)));
+    }
+}
+
+struct RestoreFlags(Flags);
+
+impl Drop for RestoreFlags {
+    fn drop(&mut self) {
+        FLAGS.with(|cell| cell.set(self.0));
+    }
+}
+
+struct RestoreBudget(Option<(usize, usize)>, usize);
+
+impl Drop for RestoreBudget {
+    fn drop(&mut self) {
+        BUDGET.with(|cell| {
+            let used = match cell.get() {
+                Some((remaining, _)) => self.1 - remaining,
+                None => 0,
+            };
+
+            cell.set(self.0.map(|(remaining, steps)| (remaining - used, steps)));
+        });
+    }
+}
+
+struct RestoreDeadline(Option<(Instant, Duration)>);
+
+impl Drop for RestoreDeadline {
+    fn drop(&mut self) {
+        DEADLINE.with(|cell| cell.set(self.0));
+    }
+}
+
+struct RestoreProgress(Option<Progress>);
+
+impl Drop for RestoreProgress {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+
+        PROGRESS.with(|cell| *cell.borrow_mut() = outer);
+    }
+}
+
+struct RestoreRecovered(Option<Vec<(Error, usize)>>);
+
+impl Drop for RestoreRecovered {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+
+        RECOVERED.with(|cell| *cell.borrow_mut() = outer);
+    }
+}
+
+struct RestoreAmbiguities(Option<Vec<Ambiguous>>);
+
+impl Drop for RestoreAmbiguities {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+
+        AMBIGUITIES.with(|cell| *cell.borrow_mut() = outer);
+    }
+}
+
+struct RestoreBacktracks(Option<Vec<Backtrack>>, Option<usize>);
+
+impl Drop for RestoreBacktracks {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+
+        BACKTRACKS.with(|cell| *cell.borrow_mut() = outer);
+        REACH.with(|cell| cell.set(self.1));
+    }
+}
+
+struct RestoreReach(Option<usize>);
+
+impl Drop for RestoreReach {
+    fn drop(&mut self) {
+        REACH.with(|cell| {
+            let reach = cell.get();
+
+            cell.set(
+                self.0
+                    .map(|outer| reach.map_or(outer, |reach| outer.max(reach))),
+            );
+        });
+    }
+}
+
+struct RestoreSource(Option<Source>);
+
+impl Drop for RestoreSource {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+
+        SOURCE.with(|cell| *cell.borrow_mut() = outer);
+    }
+}
+
+struct RestoreOptions(Option<ParseOptions>, usize);
+
+impl Drop for RestoreOptions {
+    fn drop(&mut self) {
+        OPTIONS.with(|cell| cell.set(self.0));
+        DEPTH.with(|cell| cell.set(self.1));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::error::Error;
+use crate::parser::{source_offset, Parser};
+use crate::position::{Columns, Position, Span};
+use crate::sequence::Sequence;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Segment<O> {
+    Literal(String, Span),
+    Expr(O, Span),
+}
+
+impl<O> Segment<O> {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Literal(_, span) => *span,
+            Self::Expr(_, span) => *span,
+        }
+    }
+}
+
+pub fn template<'a, O, P>(
+    source: &'a str,
+    literal_until: P,
+    open: char,
+    expr: impl Parser<'a, O>,
+    close: char,
+) -> impl Parser<'a, Vec<Segment<O>>>
+where
+    P: Fn(char) -> bool,
+{
+    move |input: &'a str| {
+        let mut position = Position::new(source, source_offset(source, input)?);
+        let mut out = Vec::new();
+        let mut literal = String::new();
+        let mut start = input;
+        let mut rem = input;
+
+        while let Some(ch) = rem.chars().next() {
+            if literal_until(ch) {
+                break;
+            }
+
+            let next = &rem[ch.len_utf8()..];
+
+            if ch == open && next.starts_with(open) || ch == close && next.starts_with(close) {
+                literal.push(ch);
+                rem = &next[ch.len_utf8()..];
+            } else if ch == open {
+                flush(source, &mut position, &mut out, &mut literal, start, rem)?;
+
+                let (value, end) = expr.parse(next).map_err(Error::into_fail)?;
+                let span = span(source, &mut position, next, end)?;
+
+                rem = close.parse(end).map_err(Error::into_fail)?.1;
+                start = rem;
+                out.push(Segment::Expr(value, span));
+            } else if ch == close {
+                return Err(unmatched(close));
+            } else {
+                literal.push(ch);
+                rem = next;
+            }
+        }
+
+        flush(source, &mut position, &mut out, &mut literal, start, rem)?;
+
+        Ok((out, rem))
+    }
+}
+
+fn flush<O>(
+    source: &str,
+    position: &mut Position,
+    out: &mut Vec<Segment<O>>,
+    literal: &mut String,
+    start: &str,
+    end: &str,
+) -> Result<(), Error> {
+    if !literal.is_empty() {
+        let span = span(source, position, start, end)?;
+
+        out.push(Segment::Literal(std::mem::take(literal), span));
+    }
+
+    Ok(())
+}
+
+fn span(source: &str, position: &mut Position, start: &str, end: &str) -> Result<Span, Error> {
+    let columns = Columns::default();
+    let start = position.advance(source, source_offset(source, start)?, columns);
+    let end = start.advance(source, source_offset(source, end)?, columns);
+
+    *position = end;
+
+    Ok(Span { start, end })
+}
+
+fn unmatched(close: char) -> Error {
+    Error::expect(Sequence::custom(format!("{0}{0}", close)))
+        .but_found(close)
+        .into_fail()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use crate::sequence::{alphabetic, alphanumeric};
+
+    #[test]
+    fn test_template() {
+        let source = "Hello {name}, you have {n} items";
+
+        assert_eq!(
+            parse(source, template(source, |_| false, '{', alphanumeric, '}')),
+            Ok((
+                vec![
+                    Segment::Literal("Hello ".to_owned(), Span::new(source, 0, 6)),
+                    Segment::Expr("name", Span::new(source, 7, 11)),
+                    Segment::Literal(", you have ".to_owned(), Span::new(source, 12, 23)),
+                    Segment::Expr("n", Span::new(source, 24, 25)),
+                    Segment::Literal(" items".to_owned(), Span::new(source, 26, 32)),
+                ],
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn test_template_escaped() {
+        let source = "\"{{x}} = {x}\" rest";
+        let (_, input) = parse(source, '"').unwrap();
+
+        assert_eq!(
+            parse(
+                input,
+                template(source, |ch| ch == '"', '{', alphabetic, '}')
+            ),
+            Ok((
+                vec![
+                    Segment::Literal("{x} = ".to_owned(), Span::new(source, 1, 9)),
+                    Segment::Expr("x", Span::new(source, 10, 11)),
+                ],
+                "\" rest"
+            ))
+        );
+        assert_eq!(
+            parse("", template("", |_| false, '{', alphabetic, '}')),
+            Ok((vec![], ""))
+        );
+    }
+
+    #[test]
+    fn test_template_lines() {
+        let source = "a\n{b}\nc {d}";
+        let (segments, _) =
+            parse(source, template(source, |_| false, '{', alphabetic, '}')).unwrap();
+        let spans = segments.iter().map(Segment::span).collect::<Vec<_>>();
+
+        assert_eq!(
+            spans,
+            vec![
+                Span::new(source, 0, 2),
+                Span::new(source, 3, 4),
+                Span::new(source, 5, 8),
+                Span::new(source, 9, 10),
+            ]
+        );
+        assert_eq!(spans[3].start.to_string(), "3:4");
+    }
+
+    #[test]
+    fn test_template_error() {
+        let source = "a {1} b";
+
+        assert_eq!(
+            parse(source, template(source, |_| false, '{', alphabetic, '}')),
+            Err(Error::expect(Sequence::Alphabetic)
+                .but_found('1')
+                .into_fail())
+        );
+
+        let source = "a {b c";
+
+        assert_eq!(
+            parse(source, template(source, |_| false, '{', alphabetic, '}')),
+            Err(Error::expect('}').but_found(' ').into_fail())
+        );
+
+        let source = "a } b";
+
+        assert_eq!(
+            parse(source, template(source, |_| false, '{', alphabetic, '}')),
+            Err(Error::expect("}}").but_found('}').into_fail())
+        );
+
+        let source = "a }";
+
+        assert_eq!(
+            parse(source, template(source, |_| false, '{', alphabetic, '}')),
+            Err(Error::expect("}}").but_found('}').into_fail())
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::Debug;
+
+use crate::parser::Parser;
+
+#[macro_export]
+macro_rules! assert_parses {
+    ($parser:expr, $input:expr => $expected:expr, $rest:expr $(,)?) => {
+        if let Err(msg) = $crate::testing::check_parses(&$parser, $input, $expected, $rest) {
+            panic!("{}", msg);
+        }
+    };
+    ($parser:expr, $input:expr => $expected:expr $(,)?) => {
+        $crate::assert_parses!($parser, $input => $expected, "")
+    };
+}
+
+#[macro_export]
+macro_rules! assert_parse_err {
+    ($parser:expr, $input:expr, contains: $text:expr $(,)?) => {
+        if let Err(msg) = $crate::testing::check_parse_err(&$parser, $input, $text) {
+            panic!("{}", msg);
+        }
+    };
+    ($parser:expr, $input:expr $(,)?) => {
+        $crate::assert_parse_err!($parser, $input, contains: "")
+    };
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fixture<'a, O> {
+    Parses(&'a str, O, &'a str),
+    Fails(&'a str, &'a str),
+}
+
+impl<'a, O> Fixture<'a, O> {
+    pub fn input(&self) -> &'a str {
+        match self {
+            Self::Parses(input, _, _) => input,
+            Self::Fails(input, _) => input,
+        }
+    }
+}
+
+pub fn run_fixtures<'a, O, P>(parser: P, fixtures: impl IntoIterator<Item = Fixture<'a, O>>)
+where
+    O: Debug + PartialEq,
+    P: Parser<'a, O>,
+{
+    let mut total = 0;
+    let mut failures = Vec::new();
+
+    for (idx, fixture) in fixtures.into_iter().enumerate() {
+        let result = match fixture {
+            Fixture::Parses(input, expected, rest) => check_parses(&parser, input, expected, rest),
+            Fixture::Fails(input, text) => check_parse_err(&parser, input, text),
+        };
+
+        if let Err(msg) = result {
+            failures.push(format!("fixture #{}: {}", idx, msg));
+        }
+
+        total += 1;
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "{} of {} fixtures failed\n\n{}",
+            failures.len(),
+            total,
+            failures.join("\n\n")
+        );
+    }
+}
+
+#[doc(hidden)]
+pub fn check_parses<'a, O, P>(
+    parser: &P,
+    input: &'a str,
+    expected: O,
+    rest: &str,
+) -> Result<(), String>
+where
+    O: Debug + PartialEq,
+    P: Parser<'a, O>,
+{
+    match parser.parse(input) {
+        Ok((out, rem)) if out == expected && rem == rest => Ok(()),
+        Ok((out, rem)) => Err(format!(
+            "parse of {:?} produced unexpected output\n  expected: ({:?}, {:?})\n     found: ({:?}, {:?})",
+            input, expected, rest, out, rem
+        )),
+        Err(err) => Err(format!(
+            "parse of {:?} failed\n  expected: ({:?}, {:?})\n     error: {}",
+            input,
+            expected,
+            rest,
+            err.to_string().replace('\n', "\n            ")
+        )),
+    }
+}
+
+#[doc(hidden)]
+pub fn check_parse_err<'a, O, P>(parser: &P, input: &'a str, text: &str) -> Result<(), String>
+where
+    O: Debug,
+    P: Parser<'a, O>,
+{
+    match parser.parse(input) {
+        Err(err) if err.to_string().contains(text) => Ok(()),
+        Err(err) => Err(format!(
+            "parse of {:?} failed with an unexpected error\n  expected text: {:?}\n          error: {}",
+            input,
+            text,
+            err.to_string().replace('\n', "\n                 ")
+        )),
+        Ok((out, rem)) => Err(format!(
+            "parse of {:?} unexpectedly succeeded\n  expected text: {:?}\n         output: ({:?}, {:?})",
+            input, text, out, rem
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequence::{alphabetic, decimal};
+
+    #[test]
+    fn test_assert_parses() {
+        assert_parses!(decimal, "123" => "123");
+        assert_parses!(decimal, "123abc" => "123", "abc");
+        assert_parses!((alphabetic, '=', decimal), "a=1;" => ("a", '=', "1"), ";");
+        assert_parse_err!(decimal, "abc", contains: "Expected sequence: decimal");
+        assert_parse_err!(decimal, "");
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check_parses(&decimal, "1", "1", ""), Ok(()));
+        assert_eq!(
+            check_parses(&decimal, "12", "1", ""),
+            Err("parse of \"12\" produced unexpected output\n  expected: (\"1\", \"\")\n     found: (\"12\", \"\")".to_owned())
+        );
+        assert!(check_parses(&decimal, "a", "1", "")
+            .unwrap_err()
+            .starts_with("parse of \"a\" failed"));
+        assert_eq!(check_parse_err(&decimal, "a", "found"), Err(
+            "parse of \"a\" failed with an unexpected error\n  expected text: \"found\"\n          error: Error:\n                 Expected sequence: decimal\n                 Found character: 'a'".to_owned()
+        ));
+        assert!(check_parse_err(&decimal, "1", "")
+            .unwrap_err()
+            .starts_with("parse of \"1\" unexpectedly succeeded"));
+    }
+
+    #[test]
+    fn test_run_fixtures() {
+        run_fixtures(
+            decimal,
+            vec![
+                Fixture::Parses("1", "1", ""),
+                Fixture::Parses("12 ", "12", " "),
+                Fixture::Fails("x", "Found"),
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "2 of 3 fixtures failed")]
+    fn test_run_fixtures_failed() {
+        run_fixtures(
+            decimal,
+            vec![
+                Fixture::Parses("1", "1", ""),
+                Fixture::Parses("12", "1", ""),
+                Fixture::Fails("1", "Found"),
+            ],
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::borrow::Cow;
+use std::char;
+
+use crate::error::Error;
+use crate::parser::Output;
+use crate::sequence::Sequence;
+
+const ENTITIES: [(&str, char); 31] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("hellip", '…'),
+    ("mdash", '—'),
+    ("ndash", '–'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("bull", '•'),
+    ("middot", '·'),
+    ("deg", '°'),
+    ("plusmn", '±'),
+    ("times", '×'),
+    ("divide", '÷'),
+    ("cent", '¢'),
+    ("pound", '£'),
+    ("yen", '¥'),
+    ("euro", '€'),
+    ("sect", '§'),
+    ("para", '¶'),
+    ("shy", '\u{ad}'),
+];
+
+pub fn entity_decoded(input: &str) -> Output<Cow<str>> {
+    let len = input.find('<').unwrap_or(input.len());
+
+    if len == 0 {
+        return Err(match input.chars().next() {
+            Some(ch) => Error::expect(Sequence::custom("text")).but_found(ch),
+            None => Error::expect(Sequence::custom("text")).but_found_end(),
+        });
+    }
+
+    Ok((decode_entities(&input[..len]), &input[len..]))
+}
+
+pub fn decode_entities(text: &str) -> Cow<str> {
+    let mut out = String::new();
+    let mut rest = text;
+    let mut decoded_any = false;
+
+    while let Some(idx) = rest.find('&') {
+        let (decoded, len) = match entity(&rest[idx + 1..]) {
+            Some(found) => found,
+            None => {
+                out.push_str(&rest[..=idx]);
+                rest = &rest[idx + 1..];
+                continue;
+            }
+        };
+
+        out.push_str(&rest[..idx]);
+        out.push(decoded);
+        rest = &rest[idx + 1 + len..];
+        decoded_any = true;
+    }
+
+    match decoded_any {
+        false => Cow::Borrowed(text),
+        true => {
+            out.push_str(rest);
+            Cow::Owned(out)
+        }
+    }
+}
+
+fn entity(input: &str) -> Option<(char, usize)> {
+    let end = input.find(';')?;
+    let name = &input[..end];
+    let decoded = match name.strip_prefix('#') {
+        Some(code) => match code.strip_prefix('x').or_else(|| code.strip_prefix('X')) {
+            Some(hex) if !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                u32::from_str_radix(hex, 16).ok()
+            }
+            None if !code.is_empty() && code.bytes().all(|b| b.is_ascii_digit()) => {
+                code.parse().ok()
+            }
+            _ => None,
+        }
+        .and_then(char::from_u32)?,
+        None => ENTITIES
+            .iter()
+            .find(|(entity, _)| *entity == name)
+            .map(|(_, ch)| *ch)?,
+    };
+
+    Some((decoded, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_entity_decoded() {
+        assert_eq!(
+            parse("Tom &amp; Jerry</b>", entity_decoded),
+            Ok((Cow::Owned("Tom & Jerry".to_owned()), "</b>"))
+        );
+        assert_eq!(
+            parse("&#38;&#x26;&#X26;&lt;&gt;", entity_decoded),
+            Ok((Cow::Owned("&&&<>".to_owned()), ""))
+        );
+        assert_eq!(
+            parse("plain text<", entity_decoded),
+            Ok((Cow::Borrowed("plain text"), "<"))
+        );
+        assert_eq!(
+            parse("<p>", entity_decoded),
+            Err(Error::expect(Sequence::custom("text")).but_found('<'))
+        );
+        assert_eq!(
+            parse("", entity_decoded),
+            Err(Error::expect(Sequence::custom("text")).but_found_end())
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &copy; b"), "a © b");
+        assert_eq!(decode_entities("5&nbsp;&euro;"), "5\u{a0}€");
+        assert_eq!(decode_entities("AT&T"), "AT&T");
+        assert_eq!(
+            decode_entities("&bogus; &#; &#xZZ; &amp"),
+            "&bogus; &#; &#xZZ; &amp"
+        );
+        assert_eq!(decode_entities("&#xD800;&amp;"), "&#xD800;&");
+        assert_eq!(decode_entities("&&amp;"), "&&");
+        assert!(matches!(decode_entities("no & entity"), Cow::Borrowed(_)));
+        assert!(matches!(decode_entities("none"), Cow::Borrowed(_)));
+    }
+}
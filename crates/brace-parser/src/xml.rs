@@ -0,0 +1,285 @@
+use std::borrow::Cow;
+
+use crate::combinator::branch::either;
+use crate::combinator::series::{delimited, leading, pair, trio};
+use crate::combinator::{context, fail, map};
+use crate::error::Error;
+use crate::parser::{take_while, take_while0, Output, Parser};
+use crate::sequence::Sequence;
+use crate::text::decode_entities;
+
+pub fn name(input: &str) -> Output<&str> {
+    match input.chars().next() {
+        Some(ch) if is_name_start(ch) => take_while(is_name_char).parse(input),
+        Some(ch) => Err(Error::expect(Sequence::custom("XML name")).but_found(ch)),
+        None => Err(Error::expect(Sequence::custom("XML name")).but_found_end()),
+    }
+}
+
+pub fn attribute(input: &str) -> Output<(&str, Cow<str>)> {
+    pair(
+        name,
+        leading(
+            trio(take_while0(is_space), fail('='), take_while0(is_space)),
+            fail(attribute_value),
+        ),
+    )
+    .parse(input)
+}
+
+pub fn start_tag(input: &str) -> Output<StartTag> {
+    let (name, mut rem) = leading('<', name).parse(input)?;
+    let mut attributes: Vec<(&str, Cow<str>)> = Vec::new();
+
+    loop {
+        match leading(take_while(is_space), attribute).parse(rem) {
+            Ok(((key, _), _)) if attributes.iter().any(|(other, _)| *other == key) => {
+                return Err(Error::invalid()
+                    .with_context(format!("duplicate attribute `{}` in <{}>", key, name)));
+            }
+            Ok((attribute, next)) => {
+                attributes.push(attribute);
+                rem = next;
+            }
+            Err(err) if err.is_fail() => return Err(err),
+            Err(_) => break,
+        }
+    }
+
+    let (self_closing, rem) = leading(
+        take_while0(is_space),
+        fail(either(map("/>", |_| true), map('>', |_| false))),
+    )
+    .parse(rem)?;
+
+    Ok((
+        StartTag {
+            name,
+            attributes,
+            self_closing,
+        },
+        rem,
+    ))
+}
+
+pub fn end_tag(input: &str) -> Output<&str> {
+    delimited("</", fail(name), pair(take_while0(is_space), fail('>'))).parse(input)
+}
+
+pub fn comment(input: &str) -> Output<&str> {
+    let rem = match input.strip_prefix("<!--") {
+        Some(rem) => rem,
+        None => return Err(Error::expect(Sequence::custom("<!--")).with_context("comment")),
+    };
+
+    match rem.find("--") {
+        Some(idx) if rem[idx..].starts_with("-->") => Ok((&rem[..idx], &rem[idx + 3..])),
+        Some(_) => Err(Error::invalid().with_context("`--` is not allowed inside a comment")),
+        None => Err(Error::expect(Sequence::custom("-->"))
+            .but_found_end()
+            .into_fail()
+            .with_context("comment")),
+    }
+}
+
+pub fn cdata(input: &str) -> Output<&str> {
+    let rem = match input.strip_prefix("<![CDATA[") {
+        Some(rem) => rem,
+        None => return Err(Error::expect(Sequence::custom("<![CDATA[")).with_context("cdata")),
+    };
+
+    match rem.find("]]>") {
+        Some(idx) => Ok((&rem[..idx], &rem[idx + 3..])),
+        None => Err(Error::expect(Sequence::custom("]]>"))
+            .but_found_end()
+            .into_fail()
+            .with_context("cdata")),
+    }
+}
+
+fn attribute_value(input: &str) -> Output<Cow<str>> {
+    context(
+        "attribute value",
+        map(
+            either(
+                delimited(
+                    '"',
+                    take_while0(|ch: char| ch != '"' && ch != '<'),
+                    fail('"'),
+                ),
+                delimited(
+                    '\'',
+                    take_while0(|ch: char| ch != '\'' && ch != '<'),
+                    fail('\''),
+                ),
+            ),
+            decode_entities,
+        ),
+    )
+    .parse(input)
+}
+
+fn is_space(ch: char) -> bool {
+    matches!(ch, ' ' | '\t' | '\r' | '\n')
+}
+
+fn is_name_start(ch: char) -> bool {
+    matches!(ch,
+        ':' | '_' | 'A'..='Z' | 'a'..='z'
+        | '\u{C0}'..='\u{D6}'
+        | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}'
+        | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+fn is_name_char(ch: char) -> bool {
+    is_name_start(ch)
+        || matches!(ch,
+            '-' | '.' | '0'..='9' | '\u{B7}'
+            | '\u{300}'..='\u{36F}'
+            | '\u{203F}'..='\u{2040}'
+        )
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StartTag<'a> {
+    pub name: &'a str,
+    pub attributes: Vec<(&'a str, Cow<'a, str>)>,
+    pub self_closing: bool,
+}
+
+impl<'a> StartTag<'a> {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_name() {
+        assert_eq!(parse("svg:rect x", name), Ok(("svg:rect", " x")));
+        assert_eq!(parse("_a-1.b>", name), Ok(("_a-1.b", ">")));
+        assert_eq!(parse("élément/", name), Ok(("élément", "/")));
+        assert_eq!(
+            parse("1a", name),
+            Err(Error::expect(Sequence::custom("XML name")).but_found('1'))
+        );
+        assert_eq!(
+            parse("-a", name),
+            Err(Error::expect(Sequence::custom("XML name")).but_found('-'))
+        );
+    }
+
+    #[test]
+    fn test_attribute() {
+        assert_eq!(
+            parse("href=\"a?b=1&amp;c=2\"", attribute),
+            Ok((("href", Cow::Owned("a?b=1&c=2".to_owned())), ""))
+        );
+        assert_eq!(
+            parse("alt = 'say \"hi\"' ", attribute),
+            Ok((("alt", Cow::Borrowed("say \"hi\"")), " "))
+        );
+        assert_eq!(
+            parse("x=\"a<b\"", attribute),
+            Err(Error::expect('"')
+                .but_found('<')
+                .into_fail()
+                .with_context("attribute value"))
+        );
+        assert!(parse("checked>", attribute).unwrap_err().is_fail());
+    }
+
+    #[test]
+    fn test_start_tag() {
+        let (tag, rem) = parse("<a href='/' class=\"x y\">link", start_tag).unwrap();
+
+        assert_eq!(tag.name, "a");
+        assert_eq!(tag.attribute("href"), Some("/"));
+        assert_eq!(tag.attribute("class"), Some("x y"));
+        assert_eq!(tag.attribute("id"), None);
+        assert!(!tag.self_closing);
+        assert_eq!(rem, "link");
+        assert_eq!(
+            parse("<br/>", start_tag),
+            Ok((
+                StartTag {
+                    name: "br",
+                    attributes: Vec::new(),
+                    self_closing: true,
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("<img\n  src=\"a.png\"\n/>", start_tag).map(|(tag, _)| tag.self_closing),
+            Ok(true)
+        );
+        assert_eq!(
+            parse("<a x='1' x='2'>", start_tag),
+            Err(Error::invalid().with_context("duplicate attribute `x` in <a>"))
+        );
+        assert!(parse("<a x='1'", start_tag).unwrap_err().is_fail());
+        assert!(parse("</a>", start_tag).unwrap_err().is_pass());
+    }
+
+    #[test]
+    fn test_end_tag() {
+        assert_eq!(parse("</a>", end_tag), Ok(("a", "")));
+        assert_eq!(parse("</ns:item  >\n", end_tag), Ok(("ns:item", "\n")));
+        assert_eq!(
+            parse("</a b>", end_tag),
+            Err(Error::expect('>').but_found('b').into_fail())
+        );
+        assert!(parse("<a>", end_tag).unwrap_err().is_pass());
+    }
+
+    #[test]
+    fn test_comment() {
+        assert_eq!(parse("<!-- hi -->x", comment), Ok((" hi ", "x")));
+        assert_eq!(parse("<!---->", comment), Ok(("", "")));
+        assert_eq!(
+            parse("<!-- a -- b -->", comment),
+            Err(Error::invalid().with_context("`--` is not allowed inside a comment"))
+        );
+        assert_eq!(
+            parse("<!-- open", comment),
+            Err(Error::expect(Sequence::custom("-->"))
+                .but_found_end()
+                .into_fail()
+                .with_context("comment"))
+        );
+    }
+
+    #[test]
+    fn test_cdata() {
+        assert_eq!(
+            parse("<![CDATA[<b>&amp;</b>]]>rest", cdata),
+            Ok(("<b>&amp;</b>", "rest"))
+        );
+        assert_eq!(
+            parse("<![CDATA[open", cdata),
+            Err(Error::expect(Sequence::custom("]]>"))
+                .but_found_end()
+                .into_fail()
+                .with_context("cdata"))
+        );
+        assert!(parse("<!-- -->", cdata).unwrap_err().is_pass());
+    }
+}